@@ -0,0 +1,233 @@
+use std::convert::TryFrom;
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, Fraction, MessageInfo, OverflowError,
+    OverflowOperation, Response, StdError, StdResult, Uint128, Uint256,
+};
+use cw2::set_contract_version;
+use cw_core_interface::voting::{
+    self, IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, ModuleInfo, QueryMsg};
+use crate::state::{ChildModule, DAO, MODULES};
+
+const CONTRACT_NAME: &str = "crates.io:cw-composite-voting";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let modules = add_modules(deps.as_ref(), vec![], msg.modules)?;
+    if modules.is_empty() {
+        return Err(ContractError::NoModules {});
+    }
+    MODULES.save(deps.storage, &modules, env.block.height)?;
+    DAO.save(deps.storage, &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", info.sender)
+        .add_attribute("module_count", modules.len().to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateModules { to_add, to_remove } => {
+            execute_update_modules(deps, env, info, to_add, to_remove)
+        }
+    }
+}
+
+pub fn execute_update_modules(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    to_add: Vec<ModuleInfo>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut modules = MODULES.load(deps.storage)?;
+    for address in to_remove {
+        let address = deps.api.addr_validate(&address)?;
+        let len = modules.len();
+        modules.retain(|module| module.address != address);
+        if modules.len() == len {
+            return Err(ContractError::ModuleNotFound {
+                address: address.to_string(),
+            });
+        }
+    }
+    let modules = add_modules(deps.as_ref(), modules, to_add)?;
+    if modules.is_empty() {
+        return Err(ContractError::NoModules {});
+    }
+    MODULES.save(deps.storage, &modules, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_modules")
+        .add_attribute("module_count", modules.len().to_string()))
+}
+
+/// Adds the modules in `to_add` to `modules`, replacing the weight of
+/// any module that is already present. Errors if a module in `to_add`
+/// does not implement the voting module interface.
+fn add_modules(
+    deps: Deps,
+    mut modules: Vec<ChildModule>,
+    to_add: Vec<ModuleInfo>,
+) -> Result<Vec<ChildModule>, ContractError> {
+    let mut added: Vec<Addr> = Vec::with_capacity(to_add.len());
+    for ModuleInfo { address, weight } in to_add {
+        let address = deps.api.addr_validate(&address)?;
+        if weight.is_zero() {
+            return Err(ContractError::ZeroWeight {});
+        }
+        if added.contains(&address) {
+            return Err(ContractError::DuplicateModule {
+                address: address.to_string(),
+            });
+        }
+
+        // Make sure the module is a voting module. If it is not
+        // every voting power query would fail.
+        let _: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
+            address.clone(),
+            &voting::Query::TotalPowerAtHeight { height: None },
+        )?;
+
+        match modules.iter_mut().find(|module| module.address == address) {
+            Some(module) => module.weight = weight,
+            None => modules.push(ChildModule {
+                address: address.clone(),
+                weight,
+            }),
+        }
+        added.push(address);
+    }
+    Ok(modules)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            query_voting_power_at_height(deps, env, address, height)
+        }
+        QueryMsg::TotalPowerAtHeight { height } => query_total_power_at_height(deps, env, height),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::IsActive {} => query_is_active(deps),
+        QueryMsg::Modules { height } => query_modules(deps, height),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+    }
+}
+
+fn load_modules_at_height(deps: Deps, height: u64) -> StdResult<Vec<ChildModule>> {
+    // Snapshots taken at a height are only visible from the next
+    // height on, so there are no modules at the instantiation
+    // height. Use the initial modules in that case so that proposals
+    // created in the same block as this module see voting power.
+    match MODULES.may_load_at_height(deps.storage, height)? {
+        Some(modules) => Ok(modules),
+        None => Ok(MODULES
+            .may_load_at_height(deps.storage, height + 1)?
+            .unwrap_or_default()),
+    }
+}
+
+/// Multiplies `power` by `weight`, returning an overflow error
+/// instead of panicking if the result does not fit in a Uint128.
+fn weighted_power(power: Uint128, weight: Decimal) -> StdResult<Uint128> {
+    let weighted = power.full_mul(weight.numerator()) / Uint256::from(weight.denominator());
+    Uint128::try_from(weighted)
+        .map_err(|_| StdError::overflow(OverflowError::new(OverflowOperation::Mul, power, weight)))
+}
+
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+
+    let mut power = Uint128::zero();
+    for module in load_modules_at_height(deps, height)? {
+        let res: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+            module.address,
+            &voting::Query::VotingPowerAtHeight {
+                address: address.to_string(),
+                height: Some(height),
+            },
+        )?;
+        power = power
+            .checked_add(weighted_power(res.power, module.weight)?)
+            .map_err(StdError::overflow)?;
+    }
+
+    to_binary(&VotingPowerAtHeightResponse { power, height })
+}
+
+pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    let height = height.unwrap_or(env.block.height);
+
+    let mut power = Uint128::zero();
+    for module in load_modules_at_height(deps, height)? {
+        let res: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
+            module.address,
+            &voting::Query::TotalPowerAtHeight {
+                height: Some(height),
+            },
+        )?;
+        power = power
+            .checked_add(weighted_power(res.power, module.weight)?)
+            .map_err(StdError::overflow)?;
+    }
+
+    to_binary(&TotalPowerAtHeightResponse { power, height })
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&voting::InfoResponse { info })
+}
+
+pub fn query_is_active(deps: Deps) -> StdResult<Binary> {
+    // Voting modules are not required to implement this
+    // query. Lacking an implementation they are active by default.
+    let active = MODULES.load(deps.storage)?.into_iter().all(|module| {
+        deps.querier
+            .query_wasm_smart(module.address, &voting::Query::IsActive {})
+            .unwrap_or(IsActiveResponse { active: true })
+            .active
+    });
+    to_binary(&IsActiveResponse { active })
+}
+
+pub fn query_modules(deps: Deps, height: Option<u64>) -> StdResult<Binary> {
+    let modules = match height {
+        Some(height) => load_modules_at_height(deps, height)?,
+        None => MODULES.load(deps.storage)?,
+    };
+    to_binary(&modules)
+}
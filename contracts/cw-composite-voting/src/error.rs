@@ -0,0 +1,23 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The composite voting module must have at least one child module")]
+    NoModules {},
+
+    #[error("Child module weights must be greater than zero")]
+    ZeroWeight {},
+
+    #[error("Child module ({address}) was listed more than once")]
+    DuplicateModule { address: String },
+
+    #[error("Child module ({address}) is not registered")]
+    ModuleNotFound { address: String },
+}
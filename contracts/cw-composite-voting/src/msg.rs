@@ -0,0 +1,45 @@
+use cosmwasm_std::Decimal;
+use cw_core_macros::{active_query, voting_query};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Information about a child voting module.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ModuleInfo {
+    /// The address of an existing voting module.
+    pub address: String,
+    /// The amount to multiply the voting power from this module by.
+    pub weight: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The voting modules to combine.
+    pub modules: Vec<ModuleInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Callable by the DAO. Adds the modules in `to_add` and removes
+    /// the modules in `to_remove`. Adding a module that is already
+    /// present updates its weight.
+    UpdateModules {
+        to_add: Vec<ModuleInfo>,
+        to_remove: Vec<String>,
+    },
+}
+
+#[voting_query]
+#[active_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the child modules and their weights at `height`, or at
+    /// the current height if none is provided. Returns
+    /// Vec<ChildModule>.
+    Modules {
+        height: Option<u64>,
+    },
+    Dao {},
+}
@@ -0,0 +1,22 @@
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::{Item, SnapshotItem, Strategy};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A child voting module.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ChildModule {
+    pub address: Addr,
+    pub weight: Decimal,
+}
+
+/// The child modules. Snapshotted so that voting power queries at a
+/// past height use the modules that were present at that height.
+pub const MODULES: SnapshotItem<Vec<ChildModule>> = SnapshotItem::new(
+    "modules",
+    "modules__checkpoints",
+    "modules__changelog",
+    Strategy::EveryBlock,
+);
+
+pub const DAO: Item<Addr> = Item::new("dao");
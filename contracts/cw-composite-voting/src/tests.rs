@@ -0,0 +1,415 @@
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
+use cw_core_interface::voting::{
+    IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, ModuleInfo, QueryMsg},
+    state::ChildModule,
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+
+fn cw4_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw4_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_voting::contract::execute,
+        cw4_voting::contract::instantiate,
+        cw4_voting::contract::query,
+    )
+    .with_reply(cw4_voting::contract::reply);
+    Box::new(contract)
+}
+
+fn composite_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn instantiate_cw4_voting(app: &mut App, members: Vec<cw4::Member>) -> Addr {
+    let cw4_id = app.store_code(cw4_contract());
+    let voting_id = app.store_code(cw4_voting_contract());
+    app.instantiate_contract(
+        voting_id,
+        Addr::unchecked(DAO_ADDR),
+        &cw4_voting::msg::InstantiateMsg {
+            cw4_group_code_id: cw4_id,
+            initial_members: members,
+            active_threshold: None,
+        },
+        &[],
+        "cw4 voting",
+        None,
+    )
+    .unwrap()
+}
+
+/// Instantiates two cw4 voting modules and a composite module that
+/// combines them with weights of 1 and 0.5.
+fn setup_test_case(app: &mut App) -> (Addr, Addr, Addr) {
+    let first = instantiate_cw4_voting(
+        app,
+        vec![
+            cw4::Member {
+                addr: ADDR1.to_string(),
+                weight: 10,
+            },
+            cw4::Member {
+                addr: ADDR2.to_string(),
+                weight: 2,
+            },
+        ],
+    );
+    let second = instantiate_cw4_voting(
+        app,
+        vec![cw4::Member {
+            addr: ADDR2.to_string(),
+            weight: 8,
+        }],
+    );
+
+    let composite_id = app.store_code(composite_voting_contract());
+    let composite = app
+        .instantiate_contract(
+            composite_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                modules: vec![
+                    ModuleInfo {
+                        address: first.to_string(),
+                        weight: Decimal::one(),
+                    },
+                    ModuleInfo {
+                        address: second.to_string(),
+                        weight: Decimal::percent(50),
+                    },
+                ],
+            },
+            &[],
+            "composite voting",
+            None,
+        )
+        .unwrap();
+    app.update_block(next_block);
+
+    (composite, first, second)
+}
+
+fn voting_power(app: &App, composite: &Addr, address: &str, height: Option<u64>) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            composite,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+fn total_power(app: &App, composite: &Addr, height: Option<u64>) -> Uint128 {
+    let res: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(composite, &QueryMsg::TotalPowerAtHeight { height })
+        .unwrap();
+    res.power
+}
+
+#[test]
+fn test_instantiate() {
+    let mut app = App::default();
+    let (composite, _, _) = setup_test_case(&mut app);
+
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(composite.clone(), &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(dao, Addr::unchecked(DAO_ADDR));
+
+    let modules: Vec<ChildModule> = app
+        .wrap()
+        .query_wasm_smart(composite, &QueryMsg::Modules { height: None })
+        .unwrap();
+    assert_eq!(modules.len(), 2);
+
+    // Composite modules need at least one child and children must be
+    // voting modules.
+    let composite_id = app.store_code(composite_voting_contract());
+    let err: ContractError = app
+        .instantiate_contract(
+            composite_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg { modules: vec![] },
+            &[],
+            "composite voting",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoModules {});
+
+    app.instantiate_contract(
+        composite_id,
+        Addr::unchecked(DAO_ADDR),
+        &InstantiateMsg {
+            modules: vec![ModuleInfo {
+                address: ADDR1.to_string(),
+                weight: Decimal::one(),
+            }],
+        },
+        &[],
+        "composite voting",
+        None,
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_weighted_power() {
+    let mut app = App::default();
+    let (composite, _, _) = setup_test_case(&mut app);
+
+    // 10 * 1
+    assert_eq!(
+        voting_power(&app, &composite, ADDR1, None),
+        Uint128::new(10)
+    );
+    // 2 * 1 + 8 * 0.5
+    assert_eq!(voting_power(&app, &composite, ADDR2, None), Uint128::new(6));
+    // 12 * 1 + 8 * 0.5
+    assert_eq!(total_power(&app, &composite, None), Uint128::new(16));
+
+    let active: IsActiveResponse = app
+        .wrap()
+        .query_wasm_smart(composite, &QueryMsg::IsActive {})
+        .unwrap();
+    assert!(active.active);
+}
+
+#[test]
+fn test_update_modules() {
+    let mut app = App::default();
+    let (composite, first, second) = setup_test_case(&mut app);
+    let before = app.block_info().height;
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            composite.clone(),
+            &ExecuteMsg::UpdateModules {
+                to_add: vec![],
+                to_remove: vec![second.to_string()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            composite.clone(),
+            &ExecuteMsg::UpdateModules {
+                to_add: vec![ModuleInfo {
+                    address: second.to_string(),
+                    weight: Decimal::zero(),
+                }],
+                to_remove: vec![],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroWeight {});
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            composite.clone(),
+            &ExecuteMsg::UpdateModules {
+                to_add: vec![],
+                to_remove: vec![first.to_string(), second.to_string()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoModules {});
+
+    // Remove the first module and double the weight of the second.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        composite.clone(),
+        &ExecuteMsg::UpdateModules {
+            to_add: vec![ModuleInfo {
+                address: second.to_string(),
+                weight: Decimal::percent(200),
+            }],
+            to_remove: vec![first.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let modules: Vec<ChildModule> = app
+        .wrap()
+        .query_wasm_smart(composite.clone(), &QueryMsg::Modules { height: None })
+        .unwrap();
+    assert_eq!(
+        modules,
+        vec![ChildModule {
+            address: second.clone(),
+            weight: Decimal::percent(200),
+        }]
+    );
+
+    assert_eq!(voting_power(&app, &composite, ADDR1, None), Uint128::zero());
+    assert_eq!(
+        voting_power(&app, &composite, ADDR2, None),
+        Uint128::new(16)
+    );
+    assert_eq!(total_power(&app, &composite, None), Uint128::new(16));
+
+    // Queries at past heights use the modules present at that
+    // height.
+    assert_eq!(
+        voting_power(&app, &composite, ADDR1, Some(before)),
+        Uint128::new(10)
+    );
+    assert_eq!(
+        total_power(&app, &composite, Some(before)),
+        Uint128::new(16)
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            composite,
+            &ExecuteMsg::UpdateModules {
+                to_add: vec![],
+                to_remove: vec![first.to_string()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ModuleNotFound {
+            address: first.to_string()
+        }
+    );
+}
+
+#[test]
+fn test_power_at_instantiation_height() {
+    let mut app = App::default();
+    let child = instantiate_cw4_voting(
+        &mut app,
+        vec![cw4::Member {
+            addr: ADDR1.to_string(),
+            weight: 10,
+        }],
+    );
+    app.update_block(next_block);
+
+    let composite_id = app.store_code(composite_voting_contract());
+    let composite = app
+        .instantiate_contract(
+            composite_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                modules: vec![ModuleInfo {
+                    address: child.to_string(),
+                    weight: Decimal::percent(50),
+                }],
+            },
+            &[],
+            "composite voting",
+            None,
+        )
+        .unwrap();
+
+    // Proposals created in the same block as the composite module
+    // see the initial modules.
+    let height = app.block_info().height;
+    assert_eq!(
+        voting_power(&app, &composite, ADDR1, Some(height)),
+        Uint128::new(5)
+    );
+    assert_eq!(total_power(&app, &composite, Some(height)), Uint128::new(5));
+}
+
+#[test]
+fn test_weighted_power_overflow() {
+    let mut app = App::default();
+    let child = instantiate_cw4_voting(
+        &mut app,
+        vec![cw4::Member {
+            addr: ADDR1.to_string(),
+            weight: u64::MAX,
+        }],
+    );
+    app.update_block(next_block);
+
+    let composite_id = app.store_code(composite_voting_contract());
+    let composite = app
+        .instantiate_contract(
+            composite_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                modules: vec![ModuleInfo {
+                    address: child.to_string(),
+                    weight: Decimal::from_ratio(10u128.pow(20), 1u128),
+                }],
+            },
+            &[],
+            "composite voting",
+            None,
+        )
+        .unwrap();
+    app.update_block(next_block);
+
+    // u64::MAX * 10^20 does not fit in a Uint128. The queries error
+    // instead of panicking.
+    app.wrap()
+        .query_wasm_smart::<VotingPowerAtHeightResponse>(
+            composite.clone(),
+            &QueryMsg::VotingPowerAtHeight {
+                address: ADDR1.to_string(),
+                height: None,
+            },
+        )
+        .unwrap_err();
+    app.wrap()
+        .query_wasm_smart::<TotalPowerAtHeightResponse>(
+            composite,
+            &QueryMsg::TotalPowerAtHeight { height: None },
+        )
+        .unwrap_err();
+}
@@ -2,11 +2,14 @@ use std::env::current_dir;
 use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Coin};
+use cw2::ContractVersion;
 use cw_core::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    query::{Cw20BalanceResponse, DumpStateResponse, GetItemResponse, PauseInfoResponse},
-    state::Config,
+    query::{
+        Cw20BalanceResponse, DumpStateResponse, GetItemResponse, ListItemsEntry, PauseInfoResponse,
+    },
+    state::{Allowance, Config, DaoAdmin, PauseGuardian, ProposalModule, SubDao},
 };
 use cw_core_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
@@ -40,10 +43,63 @@ fn main() {
     export_schema_with_title(&schema_for!(Vec<Addr>), &out_dir, "Cw20TokenListResponse");
     export_schema_with_title(&schema_for!(Vec<Addr>), &out_dir, "Cw721TokenListResponse");
     export_schema_with_title(
-        &schema_for!(Vec<Addr>),
+        &schema_for!(cw721::TokensResponse),
+        &out_dir,
+        "Cw721HoldingsResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(Vec<String>),
+        &out_dir,
+        "Cw721ReceivedTokensResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(Vec<ProposalModule>),
         &out_dir,
         "GovernanceModulesResponse",
     );
-    export_schema_with_title(&schema_for!(Vec<String>), &out_dir, "ListItemsResponse");
+    export_schema_with_title(
+        &schema_for!(Vec<ProposalModule>),
+        &out_dir,
+        "ActiveProposalModulesResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(Option<ProposalModule>),
+        &out_dir,
+        "ProposalModuleResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(Vec<ListItemsEntry>),
+        &out_dir,
+        "ListItemsResponse",
+    );
+    export_schema_with_title(&schema_for!(Vec<Coin>), &out_dir, "NativeBalancesResponse");
     export_schema_with_title(&schema_for!(Addr), &out_dir, "VotingModuleResponse");
+    export_schema_with_title(
+        &schema_for!(Option<PauseGuardian>),
+        &out_dir,
+        "PauseGuardianResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(Option<Allowance>),
+        &out_dir,
+        "AllowanceResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(Vec<Allowance>),
+        &out_dir,
+        "ListAllowancesResponse",
+    );
+    export_schema_with_title(&schema_for!(Option<DaoAdmin>), &out_dir, "AdminResponse");
+    export_schema_with_title(
+        &schema_for!(Option<Addr>),
+        &out_dir,
+        "AdminNominationResponse",
+    );
+    export_schema_with_title(&schema_for!(Option<Addr>), &out_dir, "ParentDaoResponse");
+    export_schema_with_title(
+        &schema_for!(Option<ContractVersion>),
+        &out_dir,
+        "ModuleVersionResponse",
+    );
+    export_schema_with_title(&schema_for!(Vec<SubDao>), &out_dir, "ListSubDaosResponse");
 }
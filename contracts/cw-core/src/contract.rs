@@ -3,27 +3,40 @@ use std::convert::TryInto;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
-    StdResult, SubMsg,
+    coins, from_binary, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty,
+    Env, MessageInfo, Reply, Response, StdResult, SubMsg, Uint128, WasmMsg,
 };
-use cw2::{get_contract_version, set_contract_version};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw_storage_plus::{Bound, Map};
 use cw_utils::{parse_reply_instantiate_data, Duration};
 
-use cw_core_interface::voting;
+use cw_core_interface::{govmod, voting};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InitialItemInfo, InstantiateMsg, ModuleInstantiateInfo, QueryMsg};
-use crate::query::{Cw20BalanceResponse, DumpStateResponse, GetItemResponse, PauseInfoResponse};
+use crate::msg::{
+    AllowanceAssetInfo, AllowanceInfo, AllowanceKey, DaoAdminInfo, ExecuteMsg, InitialItemInfo,
+    InstantiateMsg, ItemInfo, ModuleInstantiateInfo, PauseGuardianInfo, QueryMsg,
+};
+use crate::query::{
+    Cw20BalanceResponse, DumpStateResponse, GetItemResponse, ListItemsEntry, PauseInfoResponse,
+};
 use crate::state::{
-    Config, CONFIG, CW20_LIST, CW721_LIST, ITEMS, PAUSED, PENDING_ITEM_INSTANTIATION_NAMES,
-    PROPOSAL_MODULES, VOTING_MODULE,
+    Allowance, AllowanceAsset, Config, DaoAdmin, ItemEntry, ItemValue, PauseGuardian, PauseReason,
+    ProposalModule, ProposalModuleStatus, SubDao, ACTIVE_NON_PROPOSING_MODULE_COUNT,
+    ACTIVE_PROPOSAL_MODULE_COUNT, ALLOWANCES, CONFIG, CW20_LIST, CW721_LIST, CW721_TOKENS,
+    DAO_ADMIN, GUARDIAN_PAUSE_COOLDOWN, ITEMS, MODULE_VERSIONS, NATIVE_LIST, NOMINATED_DAO_ADMIN,
+    PARENT_DAO, PAUSED, PAUSE_GUARDIAN, PAUSE_REASON, PENDING_ITEM_INSTANTIATION_NAMES,
+    PROPOSAL_MODULES, SUB_DAOS, TOTAL_PROPOSAL_MODULE_COUNT, VOTING_MODULE,
 };
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw-governance";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// The cw2 names of proposal modules that execute messages for the
+// DAO but can not create proposals.
+const NON_PROPOSING_MODULE_NAMES: &[&str] = &["crates.io:cw-ragequit"];
+
 const PROPOSAL_MODULE_REPLY_ID: u64 = 0;
 const VOTE_MODULE_INSTANTIATE_REPLY_ID: u64 = 1;
 const VOTE_MODULE_UPDATE_REPLY_ID: u64 = 2;
@@ -54,6 +67,23 @@ pub fn instantiate(
         automatically_add_cw721s: msg.automatically_add_cw721s,
     };
     CONFIG.save(deps.storage, &config)?;
+    ACTIVE_PROPOSAL_MODULE_COUNT.save(deps.storage, &0)?;
+    TOTAL_PROPOSAL_MODULE_COUNT.save(deps.storage, &0)?;
+
+    if let Some(guardian) = msg.pause_guardian {
+        let guardian = guardian.into_checked(deps.as_ref())?;
+        PAUSE_GUARDIAN.save(deps.storage, &guardian)?;
+    }
+
+    if let Some(parent_dao) = msg.parent_dao {
+        let parent_dao = deps.api.addr_validate(&parent_dao)?;
+        PARENT_DAO.save(deps.storage, &parent_dao)?;
+    }
+
+    if let Some(admin) = msg.admin {
+        let admin = admin.into_checked(deps.as_ref(), &env.block)?;
+        DAO_ADMIN.save(deps.storage, &admin)?;
+    }
 
     let vote_module_msg = msg
         .voting_module_instantiate_info
@@ -87,7 +117,7 @@ pub fn instantiate(
                     // Use existing address.
                     InitialItemInfo::Existing { address } => {
                         let addr = deps.api.addr_validate(&address)?;
-                        ITEMS.save(deps.storage, item.name, &addr)?;
+                        ITEMS.save(deps.storage, item.name, &ItemEntry::from_addr(addr))?;
                     }
                     // Instantiate new contract and capture address on successful reply.
                     InitialItemInfo::Instantiate { info } => {
@@ -126,9 +156,10 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    // No actions can be performed while the DAO is paused.
+    // Only whitelisted actions can be performed while the DAO is
+    // paused.
     if let Some(expiration) = PAUSED.may_load(deps.storage)? {
-        if !expiration.is_expired(&env.block) {
+        if !expiration.is_expired(&env.block) && !allowed_while_paused(&env, &msg) {
             return Err(ContractError::Paused {});
         }
     }
@@ -143,20 +174,104 @@ pub fn execute(
         ExecuteMsg::UpdateVotingModule { module } => {
             execute_update_voting_module(env, info.sender, module)
         }
-        ExecuteMsg::UpdateProposalModules { to_add, to_remove } => {
-            execute_update_governance_modules(deps, env, info.sender, to_add, to_remove)
+        ExecuteMsg::UpdateProposalModules {
+            to_add,
+            to_disable,
+            to_remove,
+        } => {
+            execute_update_governance_modules(deps, env, info.sender, to_add, to_disable, to_remove)
         }
-        ExecuteMsg::SetItem { key, addr } => execute_set_item(deps, env, info.sender, key, addr),
+        ExecuteMsg::GrantAllowance { grantee, allowance } => {
+            execute_grant_allowance(deps, env, info.sender, grantee, allowance)
+        }
+        ExecuteMsg::RevokeAllowance { grantee, asset } => {
+            execute_revoke_allowance(deps, env, info.sender, grantee, asset)
+        }
+        ExecuteMsg::SpendAllowance {
+            asset,
+            amount,
+            recipient,
+        } => execute_spend_allowance(deps, env, info.sender, asset, amount, recipient),
+        ExecuteMsg::SetItem { key, item } => execute_set_item(deps, env, info.sender, key, item),
         ExecuteMsg::RemoveItem { key } => execute_remove_item(deps, env, info.sender, key),
         ExecuteMsg::Receive(_) => execute_receive_cw20(deps, info.sender),
-        ExecuteMsg::ReceiveNft(_) => execute_receive_cw721(deps, info.sender),
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_cw721(deps, info.sender, msg.token_id),
         ExecuteMsg::UpdateCw20List { to_add, to_remove } => {
             execute_update_cw20_list(deps, env, info.sender, to_add, to_remove)
         }
         ExecuteMsg::UpdateCw721List { to_add, to_remove } => {
             execute_update_cw721_list(deps, env, info.sender, to_add, to_remove)
         }
-        ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
+        ExecuteMsg::PruneCw721Tokens {
+            collection,
+            token_ids,
+        } => execute_prune_cw721_tokens(deps, env, collection, token_ids),
+        ExecuteMsg::UpdateNativeList { to_add, to_remove } => {
+            execute_update_native_list(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::Pause { duration, reason } => {
+            execute_pause(deps, env, info.sender, duration, reason)
+        }
+        ExecuteMsg::Unpause {} => execute_unpause(deps, env, info.sender),
+        ExecuteMsg::UpdatePauseGuardian { guardian } => {
+            execute_update_pause_guardian(deps, env, info.sender, guardian)
+        }
+        ExecuteMsg::UpdateAdmin { admin } => execute_update_admin(deps, env, info.sender, admin),
+        ExecuteMsg::NominateAdmin { admin } => {
+            execute_nominate_admin(deps, env, info.sender, admin)
+        }
+        ExecuteMsg::AcceptAdminNomination {} => {
+            execute_accept_admin_nomination(deps, env, info.sender)
+        }
+        ExecuteMsg::RenounceAdmin {} => execute_renounce_admin(deps, env, info.sender),
+        ExecuteMsg::UpdateParentDao { parent_dao } => {
+            execute_update_parent_dao(deps, env, info.sender, parent_dao)
+        }
+        ExecuteMsg::ExecuteParentDaoMsgs { msgs } => {
+            execute_parent_dao_msgs(deps.as_ref(), info.sender, msgs)
+        }
+        ExecuteMsg::RegisterSubDao { addr, charter } => {
+            execute_register_sub_dao(deps, env, info.sender, addr, charter)
+        }
+        ExecuteMsg::UnregisterSubDao { addr } => {
+            execute_unregister_sub_dao(deps, env, info.sender, addr)
+        }
+    }
+}
+
+/// Returns true if `msg` may be executed while the contract is
+/// paused. Unpausing and replacing the pause guardian must remain
+/// possible, either directly or via a proposal hook that does nothing
+/// else, so that a misbehaving guardian can always be removed.
+/// Receiving tokens should not make transfers to the DAO fail. The
+/// parent DAO's governance override is also allowed so that it may
+/// rescue a paused subDAO.
+fn allowed_while_paused(env: &Env, msg: &ExecuteMsg) -> bool {
+    match msg {
+        ExecuteMsg::Unpause {}
+        | ExecuteMsg::UpdatePauseGuardian { .. }
+        | ExecuteMsg::Receive(_)
+        | ExecuteMsg::ReceiveNft(_)
+        | ExecuteMsg::ExecuteParentDaoMsgs { .. } => true,
+        ExecuteMsg::ExecuteProposalHook { msgs } => {
+            !msgs.is_empty()
+                && msgs.iter().all(|msg| match msg {
+                    CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr,
+                        msg,
+                        funds,
+                    }) => {
+                        *contract_addr == env.contract.address
+                            && funds.is_empty()
+                            && matches!(
+                                from_binary(msg),
+                                Ok(ExecuteMsg::Unpause {} | ExecuteMsg::UpdatePauseGuardian { .. })
+                            )
+                    }
+                    _ => false,
+                })
+        }
+        _ => false,
     }
 }
 
@@ -165,20 +280,210 @@ pub fn execute_pause(
     env: Env,
     sender: Addr,
     pause_duration: Duration,
+    reason: Option<String>,
 ) -> Result<Response, ContractError> {
-    // Only the core contract may call this method.
+    let until = pause_duration.after(&env.block);
+
+    // Only the core contract or the pause guardian may call this
+    // method. The guardian may only pause for a bounded duration and
+    // must then wait out a cooldown before pausing again.
     if sender != env.contract.address {
-        return Err(ContractError::Unauthorized {});
+        let guardian = PAUSE_GUARDIAN
+            .may_load(deps.storage)?
+            .filter(|guardian| guardian.addr == sender)
+            .ok_or(ContractError::Unauthorized {})?;
+        let within_bounds = match (pause_duration, guardian.max_pause_duration) {
+            (Duration::Height(duration), Duration::Height(max)) => duration <= max,
+            (Duration::Time(duration), Duration::Time(max)) => duration <= max,
+            _ => false,
+        };
+        if !within_bounds {
+            return Err(ContractError::PauseDurationTooLong {});
+        }
+        if let Some(cooldown) = GUARDIAN_PAUSE_COOLDOWN.may_load(deps.storage)? {
+            if !cooldown.is_expired(&env.block) {
+                return Err(ContractError::PauseCooldown { until: cooldown });
+            }
+        }
+        GUARDIAN_PAUSE_COOLDOWN.save(deps.storage, &(until + guardian.max_pause_duration)?)?;
     }
 
-    let until = pause_duration.after(&env.block);
-
     PAUSED.save(deps.storage, &until)?;
+    PAUSE_REASON.save(
+        deps.storage,
+        &PauseReason {
+            paused_by: sender.clone(),
+            reason: reason.clone(),
+        },
+    )?;
 
     Ok(Response::new()
         .add_attribute("action", "execute_pause")
         .add_attribute("sender", sender)
-        .add_attribute("until", until.to_string()))
+        .add_attribute("until", until.to_string())
+        .add_attribute("reason", reason.unwrap_or_else(|| "None".to_string())))
+}
+
+pub fn execute_unpause(deps: DepsMut, env: Env, sender: Addr) -> Result<Response, ContractError> {
+    if sender != env.contract.address {
+        PAUSE_GUARDIAN
+            .may_load(deps.storage)?
+            .filter(|guardian| guardian.addr == sender)
+            .ok_or(ContractError::Unauthorized {})?;
+    }
+
+    PAUSED.remove(deps.storage);
+    PAUSE_REASON.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_unpause")
+        .add_attribute("sender", sender))
+}
+
+pub fn execute_update_pause_guardian(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    guardian: Option<PauseGuardianInfo>,
+) -> Result<Response, ContractError> {
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match guardian {
+        Some(guardian) => {
+            let guardian = guardian.into_checked(deps.as_ref())?;
+            PAUSE_GUARDIAN.save(deps.storage, &guardian)?;
+            Ok(Response::new()
+                .add_attribute("action", "execute_update_pause_guardian")
+                .add_attribute("guardian", guardian.addr))
+        }
+        None => {
+            PAUSE_GUARDIAN.remove(deps.storage);
+            Ok(Response::new()
+                .add_attribute("action", "execute_update_pause_guardian")
+                .add_attribute("guardian", "None"))
+        }
+    }
+}
+
+/// Loads the DAO's admin, returning `None` if there is no admin or
+/// it has expired.
+fn load_active_admin(deps: Deps, env: &Env) -> StdResult<Option<DaoAdmin>> {
+    Ok(DAO_ADMIN
+        .may_load(deps.storage)?
+        .filter(|admin| !admin.expiration.is_expired(&env.block)))
+}
+
+/// Returns true if `sender` is the core contract or the DAO's active
+/// admin.
+fn is_core_or_admin(deps: Deps, env: &Env, sender: &Addr) -> StdResult<bool> {
+    Ok(*sender == env.contract.address
+        || load_active_admin(deps, env)?.is_some_and(|admin| admin.addr == *sender))
+}
+
+pub fn execute_update_admin(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    admin: Option<DaoAdminInfo>,
+) -> Result<Response, ContractError> {
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    NOMINATED_DAO_ADMIN.remove(deps.storage);
+    match admin {
+        Some(admin) => {
+            let admin = admin.into_checked(deps.as_ref(), &env.block)?;
+            DAO_ADMIN.save(deps.storage, &admin)?;
+            Ok(Response::default()
+                .add_attribute("action", "execute_update_admin")
+                .add_attribute("admin", admin.addr)
+                .add_attribute("expiration", admin.expiration.to_string()))
+        }
+        None => {
+            DAO_ADMIN.remove(deps.storage);
+            Ok(Response::default()
+                .add_attribute("action", "execute_update_admin")
+                .add_attribute("admin", "None"))
+        }
+    }
+}
+
+pub fn execute_nominate_admin(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    nominee: Option<String>,
+) -> Result<Response, ContractError> {
+    load_active_admin(deps.as_ref(), &env)?
+        .filter(|admin| admin.addr == sender)
+        .ok_or(ContractError::Unauthorized {})?;
+
+    match nominee {
+        Some(nominee) => {
+            let nominee = deps.api.addr_validate(&nominee)?;
+            NOMINATED_DAO_ADMIN.save(deps.storage, &nominee)?;
+            Ok(Response::default()
+                .add_attribute("action", "execute_nominate_admin")
+                .add_attribute("nominee", nominee))
+        }
+        None => {
+            NOMINATED_DAO_ADMIN.remove(deps.storage);
+            Ok(Response::default()
+                .add_attribute("action", "execute_nominate_admin")
+                .add_attribute("nominee", "None"))
+        }
+    }
+}
+
+pub fn execute_accept_admin_nomination(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    let nominee = NOMINATED_DAO_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoAdminNomination {})?;
+    if nominee != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    // The nomination is void if the admin that made it has since
+    // expired.
+    let admin =
+        load_active_admin(deps.as_ref(), &env)?.ok_or(ContractError::AdminExpirationPassed {})?;
+
+    DAO_ADMIN.save(
+        deps.storage,
+        &DaoAdmin {
+            addr: nominee,
+            expiration: admin.expiration,
+        },
+    )?;
+    NOMINATED_DAO_ADMIN.remove(deps.storage);
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_accept_admin_nomination")
+        .add_attribute("previous_admin", admin.addr)
+        .add_attribute("admin", sender))
+}
+
+pub fn execute_renounce_admin(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    load_active_admin(deps.as_ref(), &env)?
+        .filter(|admin| admin.addr == sender)
+        .ok_or(ContractError::Unauthorized {})?;
+
+    DAO_ADMIN.remove(deps.storage);
+    NOMINATED_DAO_ADMIN.remove(deps.storage);
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_renounce_admin")
+        .add_attribute("sender", sender))
 }
 
 pub fn execute_proposal_hook(
@@ -194,13 +499,93 @@ pub fn execute_proposal_hook(
         .add_messages(msgs))
 }
 
+pub fn execute_parent_dao_msgs(
+    deps: Deps,
+    sender: Addr,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    if PARENT_DAO.may_load(deps.storage)? != Some(sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(Response::default()
+        .add_attribute("action", "execute_parent_dao_msgs")
+        .add_attribute("parent_dao", sender)
+        .add_messages(msgs))
+}
+
+pub fn execute_update_parent_dao(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    parent_dao: Option<String>,
+) -> Result<Response, ContractError> {
+    if sender != env.contract.address && PARENT_DAO.may_load(deps.storage)? != Some(sender.clone())
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match parent_dao {
+        Some(parent_dao) => {
+            let parent_dao = deps.api.addr_validate(&parent_dao)?;
+            PARENT_DAO.save(deps.storage, &parent_dao)?;
+            Ok(Response::default()
+                .add_attribute("action", "execute_update_parent_dao")
+                .add_attribute("parent_dao", parent_dao))
+        }
+        None => {
+            PARENT_DAO.remove(deps.storage);
+            Ok(Response::default()
+                .add_attribute("action", "execute_update_parent_dao")
+                .add_attribute("parent_dao", "None"))
+        }
+    }
+}
+
+pub fn execute_register_sub_dao(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    addr: String,
+    charter: Option<String>,
+) -> Result<Response, ContractError> {
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let addr = deps.api.addr_validate(&addr)?;
+    SUB_DAOS.save(deps.storage, addr.clone(), &charter)?;
+    Ok(Response::default()
+        .add_attribute("action", "execute_register_sub_dao")
+        .add_attribute("sub_dao", addr))
+}
+
+pub fn execute_unregister_sub_dao(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    addr: String,
+) -> Result<Response, ContractError> {
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let addr = deps.api.addr_validate(&addr)?;
+    if !SUB_DAOS.has(deps.storage, addr.clone()) {
+        return Err(ContractError::SubDaoNotRegistered {});
+    }
+    SUB_DAOS.remove(deps.storage, addr.clone());
+    Ok(Response::default()
+        .add_attribute("action", "execute_unregister_sub_dao")
+        .add_attribute("sub_dao", addr))
+}
+
 pub fn execute_update_config(
     deps: DepsMut,
     env: Env,
     sender: Addr,
     config: Config,
 ) -> Result<Response, ContractError> {
-    if sender != env.contract.address {
+    if !is_core_or_admin(deps.as_ref(), &env, &sender)? {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -242,14 +627,52 @@ pub fn execute_update_governance_modules(
     env: Env,
     sender: Addr,
     to_add: Vec<ModuleInstantiateInfo>,
+    to_disable: Vec<String>,
     to_remove: Vec<String>,
 ) -> Result<Response, ContractError> {
-    if env.contract.address != sender {
+    if !is_core_or_admin(deps.as_ref(), &env, &sender)? {
         return Err(ContractError::Unauthorized {});
     }
 
+    let mut active_count = ACTIVE_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
+    let mut non_proposing_count = ACTIVE_NON_PROPOSING_MODULE_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    for addr in to_disable {
+        let addr = deps.api.addr_validate(&addr)?;
+        let mut module = PROPOSAL_MODULES
+            .may_load(deps.storage, addr.clone())?
+            .ok_or(ContractError::ProposalModuleDoesNotExist {
+                address: addr.to_string(),
+            })?;
+        if module.status == ProposalModuleStatus::Disabled {
+            return Err(ContractError::ModuleAlreadyDisabled {
+                address: addr.to_string(),
+            });
+        }
+        module.status = ProposalModuleStatus::Disabled;
+        if is_non_proposing_module(deps.as_ref(), &addr)? {
+            non_proposing_count -= 1;
+        }
+        PROPOSAL_MODULES.save(deps.storage, addr, &module)?;
+        active_count -= 1;
+    }
+
+    // Modules must be disabled before they are removed so that
+    // removing a module never changes the active module count.
     for addr in to_remove {
         let addr = deps.api.addr_validate(&addr)?;
+        let module = PROPOSAL_MODULES
+            .may_load(deps.storage, addr.clone())?
+            .ok_or(ContractError::ProposalModuleDoesNotExist {
+                address: addr.to_string(),
+            })?;
+        if module.status != ProposalModuleStatus::Disabled {
+            return Err(ContractError::ModuleNotDisabled {
+                address: addr.to_string(),
+            });
+        }
         PROPOSAL_MODULES.remove(deps.storage, addr);
     }
 
@@ -259,17 +682,14 @@ pub fn execute_update_governance_modules(
         .map(|wasm| SubMsg::reply_on_success(wasm, PROPOSAL_MODULE_REPLY_ID))
         .collect();
 
-    // If we removed all of our proposal modules and we are not adding
-    // any this operation would result in no proposal modules being
-    // present.
-    if PROPOSAL_MODULES
-        .keys_raw(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-        .next()
-        .is_none()
-        && to_add.is_empty()
-    {
+    // If we disabled all of our proposal modules that can create
+    // proposals and we are not adding any this operation would result
+    // in no proposal modules being able to create proposals.
+    if active_count == non_proposing_count && to_add.is_empty() {
         return Err(ContractError::NoProposalModule {});
     }
+    ACTIVE_PROPOSAL_MODULE_COUNT.save(deps.storage, &active_count)?;
+    ACTIVE_NON_PROPOSING_MODULE_COUNT.save(deps.storage, &non_proposing_count)?;
 
     Ok(Response::default()
         .add_attribute("action", "execute_update_governance_modules")
@@ -330,23 +750,178 @@ pub fn execute_update_cw721_list(
     Ok(Response::default().add_attribute("action", "update_cw721_list"))
 }
 
+pub fn execute_prune_cw721_tokens(
+    deps: DepsMut,
+    env: Env,
+    collection: String,
+    token_ids: Vec<String>,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let mut pruned = 0u64;
+    for token_id in token_ids {
+        let key = (collection.clone(), token_id.clone());
+        if !CW721_TOKENS.has(deps.storage, key.clone()) {
+            continue;
+        }
+        // Burned tokens have no owner so the query errors.
+        let owner: StdResult<cw721::OwnerOfResponse> = deps.querier.query_wasm_smart(
+            collection.clone(),
+            &cw721::Cw721QueryMsg::OwnerOf {
+                token_id,
+                include_expired: None,
+            },
+        );
+        if owner.map_or(true, |o| o.owner != env.contract.address) {
+            CW721_TOKENS.remove(deps.storage, key);
+            pruned += 1;
+        }
+    }
+    Ok(Response::default()
+        .add_attribute("action", "prune_cw721_tokens")
+        .add_attribute("collection", collection)
+        .add_attribute("pruned", pruned.to_string()))
+}
+
+pub fn execute_update_native_list(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    for denom in to_add {
+        NATIVE_LIST.save(deps.storage, denom, &Empty {})?;
+    }
+    for denom in to_remove {
+        NATIVE_LIST.remove(deps.storage, denom);
+    }
+    Ok(Response::default().add_attribute("action", "update_native_list"))
+}
+
+pub fn execute_grant_allowance(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    grantee: String,
+    allowance: AllowanceInfo,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let grantee = deps.api.addr_validate(&grantee)?;
+    let allowance = allowance.into_checked(deps.as_ref(), &env.block, grantee.clone())?;
+    ALLOWANCES.save(
+        deps.storage,
+        (grantee.clone(), allowance.asset.key()),
+        &allowance,
+    )?;
+    Ok(Response::default()
+        .add_attribute("action", "execute_grant_allowance")
+        .add_attribute("grantee", grantee)
+        .add_attribute("asset", allowance.asset.key())
+        .add_attribute("amount", allowance.amount))
+}
+
+pub fn execute_revoke_allowance(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    grantee: String,
+    asset: AllowanceAssetInfo,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let grantee = deps.api.addr_validate(&grantee)?;
+    let key = (grantee.clone(), asset.into_checked(deps.as_ref())?.key());
+    if !ALLOWANCES.has(deps.storage, key.clone()) {
+        return Err(ContractError::NoAllowance {});
+    }
+    ALLOWANCES.remove(deps.storage, key.clone());
+    Ok(Response::default()
+        .add_attribute("action", "execute_revoke_allowance")
+        .add_attribute("grantee", grantee)
+        .add_attribute("asset", key.1))
+}
+
+pub fn execute_spend_allowance(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    asset: AllowanceAssetInfo,
+    amount: Uint128,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let asset = asset.into_checked(deps.as_ref())?;
+    let key = (sender.clone(), asset.key());
+    let mut allowance = ALLOWANCES
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::NoAllowance {})?;
+    if allowance.expiration.is_expired(&env.block) {
+        return Err(ContractError::AllowanceExpired {});
+    }
+
+    allowance.refill(&env.block);
+    let remaining = allowance.remaining();
+    if amount > remaining {
+        return Err(ContractError::AllowanceExceeded { remaining });
+    }
+    allowance.spent += amount;
+    ALLOWANCES.save(deps.storage, key, &allowance)?;
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let msg: CosmosMsg = match asset {
+        AllowanceAsset::Native { denom } => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), denom),
+        }
+        .into(),
+        AllowanceAsset::Cw20 { addr } => WasmMsg::Execute {
+            contract_addr: addr.to_string(),
+            msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_spend_allowance")
+        .add_attribute("grantee", sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("asset", allowance.asset.key())
+        .add_attribute("amount", amount)
+        .add_message(msg))
+}
+
 pub fn execute_set_item(
     deps: DepsMut,
     env: Env,
     sender: Addr,
     key: String,
-    addr: String,
+    item: ItemInfo,
 ) -> Result<Response, ContractError> {
-    if env.contract.address != sender {
+    if !is_core_or_admin(deps.as_ref(), &env, &sender)? {
         return Err(ContractError::Unauthorized {});
     }
 
-    let addr = deps.api.addr_validate(&addr)?;
-    ITEMS.save(deps.storage, key.clone(), &addr)?;
+    let item = item.into_checked(deps.as_ref())?;
+    let value = match &item.value {
+        ItemValue::Address { addr } => addr.to_string(),
+        ItemValue::Text { text } => text.clone(),
+    };
+    ITEMS.save(deps.storage, key.clone(), &item)?;
     Ok(Response::default()
         .add_attribute("action", "execute_set_item")
         .add_attribute("key", key)
-        .add_attribute("addr", addr))
+        .add_attribute("value", value))
 }
 
 pub fn execute_remove_item(
@@ -355,7 +930,7 @@ pub fn execute_remove_item(
     sender: Addr,
     key: String,
 ) -> Result<Response, ContractError> {
-    if env.contract.address != sender {
+    if !is_core_or_admin(deps.as_ref(), &env, &sender)? {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -377,7 +952,14 @@ pub fn execute_receive_cw20(deps: DepsMut, sender: Addr) -> Result<Response, Con
     }
 }
 
-pub fn execute_receive_cw721(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
+pub fn execute_receive_cw721(
+    deps: DepsMut,
+    sender: Addr,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    // Received NFTs are always recorded so that holdings are known
+    // even if the collection is registered later.
+    CW721_TOKENS.save(deps.storage, (sender.clone(), token_id.clone()), &Empty {})?;
     let config = CONFIG.load(deps.storage)?;
     if !config.automatically_add_cw721s {
         Ok(Response::new())
@@ -385,7 +967,8 @@ pub fn execute_receive_cw721(deps: DepsMut, sender: Addr) -> Result<Response, Co
         CW721_LIST.save(deps.storage, sender.clone(), &Empty {})?;
         Ok(Response::new()
             .add_attribute("action", "receive_cw721")
-            .add_attribute("token", sender))
+            .add_attribute("token", sender)
+            .add_attribute("token_id", token_id))
     }
 }
 
@@ -397,6 +980,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalModules { start_at, limit } => {
             query_governance_modules(deps, start_at, limit)
         }
+        QueryMsg::ActiveProposalModules { start_at, limit } => {
+            query_active_proposal_modules(deps, start_at, limit)
+        }
+        QueryMsg::ProposalModule { address } => query_proposal_module(deps, address),
         QueryMsg::DumpState {} => query_dump_state(deps, env),
         QueryMsg::VotingPowerAtHeight { address, height } => {
             query_voting_power_at_height(deps, address, height)
@@ -407,10 +994,35 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::Cw20TokenList { start_at, limit } => query_cw20_list(deps, start_at, limit),
         QueryMsg::Cw721TokenList { start_at, limit } => query_cw721_list(deps, start_at, limit),
+        QueryMsg::Cw721Holdings {
+            collection,
+            start_after,
+            limit,
+        } => query_cw721_holdings(deps, env, collection, start_after, limit),
+        QueryMsg::Cw721ReceivedTokens {
+            collection,
+            start_after,
+            limit,
+        } => query_cw721_received_tokens(deps, collection, start_after, limit),
         QueryMsg::Cw20Balances { start_at, limit } => {
             query_cw20_balances(deps, env, start_at, limit)
         }
+        QueryMsg::NativeBalances { start_at, limit } => {
+            query_native_balances(deps, env, start_at, limit)
+        }
         QueryMsg::PauseInfo {} => query_paused(deps, env),
+        QueryMsg::PauseGuardian {} => query_pause_guardian(deps),
+        QueryMsg::ModuleVersion { address } => query_module_version(deps, address),
+        QueryMsg::Allowance { grantee, asset } => query_allowance(deps, env, grantee, asset),
+        QueryMsg::ListAllowances { start_after, limit } => {
+            query_list_allowances(deps, env, start_after, limit)
+        }
+        QueryMsg::Admin {} => query_admin(deps, env),
+        QueryMsg::AdminNomination {} => query_admin_nomination(deps),
+        QueryMsg::ParentDao {} => query_parent_dao(deps),
+        QueryMsg::ListSubDaos { start_after, limit } => {
+            query_list_sub_daos(deps, start_after, limit)
+        }
     }
 }
 
@@ -443,28 +1055,64 @@ pub fn query_governance_modules(
     //
     // Even if this does lock up one can determine the existing
     // governance modules by looking at past transactions on chain.
-    let modules = PROPOSAL_MODULES.keys(
-        deps.storage,
-        start_at.map(Bound::inclusive),
-        None,
-        cosmwasm_std::Order::Descending,
-    );
-    let modules: Vec<Addr> = match limit {
-        Some(limit) => modules
-            .take(limit as usize)
-            .collect::<Result<Vec<Addr>, _>>()?,
-        None => modules.collect::<Result<Vec<Addr>, _>>()?,
+    let modules = PROPOSAL_MODULES
+        .range(
+            deps.storage,
+            start_at.map(Bound::inclusive),
+            None,
+            cosmwasm_std::Order::Descending,
+        )
+        .map(|item| item.map(|(_, module)| module));
+    let modules: Vec<ProposalModule> = match limit {
+        Some(limit) => modules.take(limit as usize).collect::<StdResult<_>>()?,
+        None => modules.collect::<StdResult<_>>()?,
     };
     to_binary(&modules)
 }
 
+pub fn query_active_proposal_modules(
+    deps: Deps,
+    start_at: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let start_at = start_at.map(|a| deps.api.addr_validate(&a)).transpose()?;
+    let modules = PROPOSAL_MODULES
+        .range(
+            deps.storage,
+            start_at.map(Bound::inclusive),
+            None,
+            cosmwasm_std::Order::Descending,
+        )
+        .filter(|item| match item {
+            Ok((_, module)) => module.status == ProposalModuleStatus::Enabled,
+            Err(_) => true,
+        })
+        .map(|item| item.map(|(_, module)| module));
+    let modules: Vec<ProposalModule> = match limit {
+        Some(limit) => modules.take(limit as usize).collect::<StdResult<_>>()?,
+        None => modules.collect::<StdResult<_>>()?,
+    };
+    to_binary(&modules)
+}
+
+pub fn query_proposal_module(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let module: Option<ProposalModule> = PROPOSAL_MODULES.may_load(deps.storage, address)?;
+    to_binary(&module)
+}
+
 fn get_pause_info(deps: Deps, env: Env) -> StdResult<PauseInfoResponse> {
     Ok(match PAUSED.may_load(deps.storage)? {
         Some(expiration) => {
             if expiration.is_expired(&env.block) {
                 PauseInfoResponse::Unpaused {}
             } else {
-                PauseInfoResponse::Paused { expiration }
+                let PauseReason { paused_by, reason } = PAUSE_REASON.load(deps.storage)?;
+                PauseInfoResponse::Paused {
+                    expiration,
+                    paused_by,
+                    reason,
+                }
             }
         }
         None => PauseInfoResponse::Unpaused {},
@@ -475,12 +1123,39 @@ pub fn query_paused(deps: Deps, env: Env) -> StdResult<Binary> {
     to_binary(&get_pause_info(deps, env)?)
 }
 
+pub fn query_pause_guardian(deps: Deps) -> StdResult<Binary> {
+    let guardian: Option<PauseGuardian> = PAUSE_GUARDIAN.may_load(deps.storage)?;
+    to_binary(&guardian)
+}
+
+pub fn query_admin(deps: Deps, env: Env) -> StdResult<Binary> {
+    to_binary(&load_active_admin(deps, &env)?)
+}
+
+pub fn query_admin_nomination(deps: Deps) -> StdResult<Binary> {
+    let nominee: Option<Addr> = NOMINATED_DAO_ADMIN.may_load(deps.storage)?;
+    to_binary(&nominee)
+}
+
+pub fn query_module_version(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let version: Option<ContractVersion> = MODULE_VERSIONS.may_load(deps.storage, address)?;
+    to_binary(&version)
+}
+
 pub fn query_dump_state(deps: Deps, env: Env) -> StdResult<Binary> {
     let config = CONFIG.load(deps.storage)?;
     let voting_module = VOTING_MODULE.load(deps.storage)?;
     let governance_modules = PROPOSAL_MODULES
-        .keys(deps.storage, None, None, cosmwasm_std::Order::Descending)
-        .collect::<Result<Vec<Addr>, _>>()?;
+        .range(deps.storage, None, None, cosmwasm_std::Order::Descending)
+        .map(|item| item.map(|(_, module)| module))
+        .collect::<StdResult<Vec<ProposalModule>>>()?;
+    let active_proposal_module_count = ACTIVE_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
+    let total_proposal_module_count = TOTAL_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
+    let native_balances = get_native_balances(deps, &env, None, None)?;
+    let parent_dao = PARENT_DAO.may_load(deps.storage)?;
+    let admin = load_active_admin(deps, &env)?;
+    let admin_nomination = NOMINATED_DAO_ADMIN.may_load(deps.storage)?;
     let pause_info = get_pause_info(deps, env)?;
     let version = get_contract_version(deps.storage)?;
     to_binary(&DumpStateResponse {
@@ -488,10 +1163,94 @@ pub fn query_dump_state(deps: Deps, env: Env) -> StdResult<Binary> {
         version,
         pause_info,
         governance_modules,
+        active_proposal_module_count,
+        total_proposal_module_count,
         voting_module,
+        native_balances,
+        parent_dao,
+        admin,
+        admin_nomination,
     })
 }
 
+pub fn query_parent_dao(deps: Deps) -> StdResult<Binary> {
+    let parent_dao: Option<Addr> = PARENT_DAO.may_load(deps.storage)?;
+    to_binary(&parent_dao)
+}
+
+pub fn query_list_sub_daos(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|a| deps.api.addr_validate(&a))
+        .transpose()?;
+    let sub_daos = SUB_DAOS.range(
+        deps.storage,
+        start_after.map(Bound::exclusive),
+        None,
+        cosmwasm_std::Order::Ascending,
+    );
+    let sub_daos = match limit {
+        Some(limit) => sub_daos
+            .take(limit as usize)
+            .collect::<StdResult<Vec<(Addr, Option<String>)>>>()?,
+        None => sub_daos.collect::<StdResult<Vec<(Addr, Option<String>)>>>()?,
+    };
+    let sub_daos: Vec<SubDao> = sub_daos
+        .into_iter()
+        .map(|(addr, charter)| SubDao { addr, charter })
+        .collect();
+    to_binary(&sub_daos)
+}
+
+pub fn query_allowance(
+    deps: Deps,
+    env: Env,
+    grantee: String,
+    asset: AllowanceAssetInfo,
+) -> StdResult<Binary> {
+    let grantee = deps.api.addr_validate(&grantee)?;
+    let key = (grantee, asset.into_checked(deps)?.key());
+    let allowance = ALLOWANCES
+        .may_load(deps.storage, key)?
+        .map(|mut allowance| {
+            allowance.refill(&env.block);
+            allowance
+        });
+    to_binary(&allowance)
+}
+
+pub fn query_list_allowances(
+    deps: Deps,
+    env: Env,
+    start_after: Option<AllowanceKey>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let start_after = start_after.map(|key| key.into_checked(deps)).transpose()?;
+    let allowances = ALLOWANCES
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .map(|item| {
+            item.map(|(_, mut allowance)| {
+                allowance.refill(&env.block);
+                allowance
+            })
+        });
+    let allowances = match limit {
+        Some(limit) => allowances
+            .take(limit as usize)
+            .collect::<StdResult<Vec<Allowance>>>()?,
+        None => allowances.collect::<StdResult<Vec<Allowance>>>()?,
+    };
+    to_binary(&allowances)
+}
+
 pub fn query_voting_power_at_height(
     deps: Deps,
     address: String,
@@ -528,7 +1287,7 @@ pub fn query_list_items(
     start_at: Option<String>,
     limit: Option<u64>,
 ) -> StdResult<Binary> {
-    let items = ITEMS.keys(
+    let items = ITEMS.range(
         deps.storage,
         start_at.map(Bound::inclusive),
         None,
@@ -537,9 +1296,13 @@ pub fn query_list_items(
     let items = match limit {
         Some(limit) => items
             .take(limit as usize)
-            .collect::<Result<Vec<String>, _>>()?,
-        None => items.collect::<Result<Vec<String>, _>>()?,
+            .collect::<StdResult<Vec<(String, ItemEntry)>>>()?,
+        None => items.collect::<StdResult<Vec<(String, ItemEntry)>>>()?,
     };
+    let items: Vec<ListItemsEntry> = items
+        .into_iter()
+        .map(|(key, item)| ListItemsEntry { key, item })
+        .collect();
 
     to_binary(&items)
 }
@@ -596,6 +1359,47 @@ pub fn query_cw721_list(
     )?)
 }
 
+pub fn query_cw721_holdings(
+    deps: Deps,
+    env: Env,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let tokens: cw721::TokensResponse = deps.querier.query_wasm_smart(
+        collection,
+        &cw721::Cw721QueryMsg::Tokens {
+            owner: env.contract.address.to_string(),
+            start_after,
+            limit,
+        },
+    )?;
+    to_binary(&tokens)
+}
+
+pub fn query_cw721_received_tokens(
+    deps: Deps,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let token_ids = CW721_TOKENS.prefix(collection).keys(
+        deps.storage,
+        start_after.map(Bound::exclusive),
+        None,
+        cosmwasm_std::Order::Ascending,
+    );
+    let token_ids = match limit {
+        Some(limit) => token_ids
+            .take(limit as usize)
+            .collect::<Result<Vec<String>, _>>()?,
+        None => token_ids.collect::<Result<Vec<String>, _>>()?,
+    };
+    to_binary(&token_ids)
+}
+
 pub fn query_cw20_balances(
     deps: Deps,
     env: Env,
@@ -626,15 +1430,141 @@ pub fn query_cw20_balances(
     to_binary(&balances)
 }
 
+fn get_native_balances(
+    deps: Deps,
+    env: &Env,
+    start_at: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Vec<Coin>> {
+    let denoms = NATIVE_LIST.keys(
+        deps.storage,
+        start_at.map(Bound::inclusive),
+        None,
+        cosmwasm_std::Order::Descending,
+    );
+    let denoms = match limit {
+        Some(limit) => denoms
+            .take(limit as usize)
+            .collect::<Result<Vec<String>, _>>()?,
+        None => denoms.collect::<Result<Vec<String>, _>>()?,
+    };
+    denoms
+        .into_iter()
+        .map(|denom| deps.querier.query_balance(&env.contract.address, denom))
+        .collect()
+}
+
+pub fn query_native_balances(
+    deps: Deps,
+    env: Env,
+    start_at: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    to_binary(&get_native_balances(deps, &env, start_at, limit)?)
+}
+
+/// Derives a proposal module prefix from the number of proposal
+/// modules added before it. `0` is `A`, `25` is `Z`, `26` is `AA`,
+/// and so on.
+pub(crate) fn derive_proposal_module_prefix(mut count: u32) -> String {
+    let mut prefix = Vec::new();
+    loop {
+        prefix.push(b'A' + (count % 26) as u8);
+        if count < 26 {
+            break;
+        }
+        count = count / 26 - 1;
+    }
+    prefix.reverse();
+    String::from_utf8(prefix).unwrap()
+}
+
+/// Returns true if the proposal module at `module` can not create
+/// proposals.
+fn is_non_proposing_module(deps: Deps, module: &Addr) -> StdResult<bool> {
+    Ok(MODULE_VERSIONS
+        .may_load(deps.storage, module.clone())?
+        .is_some_and(|version| NON_PROPOSING_MODULE_NAMES.contains(&version.contract.as_str())))
+}
+
+/// Checks that a newly instantiated proposal module implements the
+/// proposal module interface and belongs to this DAO. Returns the
+/// module's cw2 version.
+fn validate_proposal_module(
+    deps: Deps,
+    env: &Env,
+    module: &Addr,
+) -> Result<ContractVersion, ContractError> {
+    let invalid = |reason: String| ContractError::InvalidProposalModule {
+        address: module.to_string(),
+        reason,
+    };
+    let info: voting::InfoResponse = deps
+        .querier
+        .query_wasm_smart(module, &govmod::Query::Info {})
+        .map_err(|e| invalid(e.to_string()))?;
+    let dao: Addr = deps
+        .querier
+        .query_wasm_smart(module, &govmod::Query::Dao {})
+        .map_err(|e| invalid(e.to_string()))?;
+    if dao != env.contract.address {
+        return Err(invalid(format!("module belongs to DAO ({})", dao)));
+    }
+    Ok(info.info)
+}
+
+/// Checks that a newly instantiated voting module implements the
+/// voting module interface. Returns the module's cw2 version.
+fn validate_voting_module(deps: Deps, module: &Addr) -> Result<ContractVersion, ContractError> {
+    let invalid = |e: cosmwasm_std::StdError| ContractError::InvalidVotingModule {
+        address: module.to_string(),
+        reason: e.to_string(),
+    };
+    let info: voting::InfoResponse = deps
+        .querier
+        .query_wasm_smart(module, &voting::Query::Info {})
+        .map_err(invalid)?;
+    let _: voting::TotalPowerAtHeightResponse = deps
+        .querier
+        .query_wasm_smart(module, &voting::Query::TotalPowerAtHeight { height: None })
+        .map_err(invalid)?;
+    Ok(info.info)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         PROPOSAL_MODULE_REPLY_ID => {
             let res = parse_reply_instantiate_data(msg)?;
             let gov_module_addr = deps.api.addr_validate(&res.contract_address)?;
-            PROPOSAL_MODULES.save(deps.storage, gov_module_addr, &Empty {})?;
+            let version = validate_proposal_module(deps.as_ref(), &env, &gov_module_addr)?;
+            MODULE_VERSIONS.save(deps.storage, gov_module_addr.clone(), &version)?;
+            if is_non_proposing_module(deps.as_ref(), &gov_module_addr)? {
+                let count = ACTIVE_NON_PROPOSING_MODULE_COUNT
+                    .may_load(deps.storage)?
+                    .unwrap_or_default();
+                ACTIVE_NON_PROPOSING_MODULE_COUNT.save(deps.storage, &(count + 1))?;
+            }
 
-            Ok(Response::default().add_attribute("gov_module".to_string(), res.contract_address))
+            let total_count = TOTAL_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
+            let prefix = derive_proposal_module_prefix(total_count);
+            PROPOSAL_MODULES.save(
+                deps.storage,
+                gov_module_addr.clone(),
+                &ProposalModule {
+                    address: gov_module_addr,
+                    prefix: prefix.clone(),
+                    status: ProposalModuleStatus::Enabled,
+                },
+            )?;
+            TOTAL_PROPOSAL_MODULE_COUNT.save(deps.storage, &(total_count + 1))?;
+            ACTIVE_PROPOSAL_MODULE_COUNT
+                .update(deps.storage, |count| -> StdResult<u32> { Ok(count + 1) })?;
+
+            Ok(Response::default()
+                .add_attribute("gov_module".to_string(), res.contract_address)
+                .add_attribute("prefix", prefix)
+                .add_attribute("version", version.version))
         }
         VOTE_MODULE_INSTANTIATE_REPLY_ID => {
             let res = parse_reply_instantiate_data(msg)?;
@@ -647,17 +1577,25 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
                 return Err(ContractError::MultipleVotingModules {});
             }
 
+            let version = validate_voting_module(deps.as_ref(), &vote_module_addr)?;
+            MODULE_VERSIONS.save(deps.storage, vote_module_addr.clone(), &version)?;
             VOTING_MODULE.save(deps.storage, &vote_module_addr)?;
 
-            Ok(Response::default().add_attribute("voting_module", vote_module_addr))
+            Ok(Response::default()
+                .add_attribute("voting_module", vote_module_addr)
+                .add_attribute("version", version.version))
         }
         VOTE_MODULE_UPDATE_REPLY_ID => {
             let res = parse_reply_instantiate_data(msg)?;
             let vote_module_addr = deps.api.addr_validate(&res.contract_address)?;
 
+            let version = validate_voting_module(deps.as_ref(), &vote_module_addr)?;
+            MODULE_VERSIONS.save(deps.storage, vote_module_addr.clone(), &version)?;
             VOTING_MODULE.save(deps.storage, &vote_module_addr)?;
 
-            Ok(Response::default().add_attribute("voting_module", vote_module_addr))
+            Ok(Response::default()
+                .add_attribute("voting_module", vote_module_addr)
+                .add_attribute("version", version.version))
         }
         reply_id if reply_id >= PENDING_ITEM_REPLY_ID_START => {
             // Retrieve the name using the ID. If it doesn't exist,
@@ -667,7 +1605,11 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             let res = parse_reply_instantiate_data(msg)?;
             let item_addr = deps.api.addr_validate(&res.contract_address)?;
 
-            ITEMS.save(deps.storage, item_name, &item_addr)?;
+            ITEMS.save(
+                deps.storage,
+                item_name,
+                &ItemEntry::from_addr(item_addr.clone()),
+            )?;
             // Remove from pending map since we now have the contract address.
             PENDING_ITEM_INSTANTIATION_NAMES.remove(deps.storage, reply_id);
 
@@ -1,5 +1,5 @@
-use cosmwasm_std::StdError;
-use cw_utils::ParseReplyError;
+use cosmwasm_std::{StdError, Uint128};
+use cw_utils::{Expiration, ParseReplyError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -10,15 +10,71 @@ pub enum ContractError {
     #[error("Unauthorized.")]
     Unauthorized {},
 
+    #[error("The admin's expiration has already passed.")]
+    AdminExpirationPassed {},
+
+    #[error("The admin must have an expiration.")]
+    AdminExpirationNever {},
+
+    #[error("There is no pending admin nomination.")]
+    NoAdminNomination {},
+
+    #[error("Allowances must have a non-zero amount.")]
+    ZeroAllowance {},
+
+    #[error("Allowance refill periods must be non-zero.")]
+    ZeroRefillPeriod {},
+
+    #[error("The allowance has expired.")]
+    AllowanceExpired {},
+
+    #[error("No allowance of the given asset exists for this address.")]
+    NoAllowance {},
+
+    #[error("Spend exceeds the remaining allowance ({remaining}).")]
+    AllowanceExceeded { remaining: Uint128 },
+
+    #[error("The given address is not a registered subDAO.")]
+    SubDaoNotRegistered {},
+
+    #[error("Only address items may have an expected contract.")]
+    ExpectedContractNotAddress {},
+
+    #[error("Expected item contract ({expected}), found ({actual}).")]
+    UnexpectedItemContract { expected: String, actual: String },
+
     #[error("The contract is paused.")]
     Paused {},
 
+    #[error("The pause guardian may not pause for longer than its maximum pause duration.")]
+    PauseDurationTooLong {},
+
+    #[error("The pause guardian may not pause the contract again until {until}.")]
+    PauseCooldown { until: Expiration },
+
     #[error("No voting module provided.")]
     NoVotingModule {},
 
     #[error("Execution would result in no governance modules being present.")]
     NoProposalModule {},
 
+    #[error(
+        "Proposal module ({address}) does not implement the proposal module interface: {reason}"
+    )]
+    InvalidProposalModule { address: String, reason: String },
+
+    #[error("Voting module ({address}) does not implement the voting module interface: {reason}")]
+    InvalidVotingModule { address: String, reason: String },
+
+    #[error("Proposal module with address ({address}) does not exist.")]
+    ProposalModuleDoesNotExist { address: String },
+
+    #[error("Proposal module with address ({address}) is already disabled.")]
+    ModuleAlreadyDisabled { address: String },
+
+    #[error("Proposal module with address ({address}) must be disabled before it is removed.")]
+    ModuleNotDisabled { address: String },
+
     #[error("An unknown reply ID was received.")]
     UnknownReplyID {},
 
@@ -1,6 +1,13 @@
-use cosmwasm_std::{Addr, WasmMsg};
+use cosmwasm_std::{Addr, BlockInfo, Deps, StdResult, Uint128, WasmMsg, WasmQuery};
+use cw2::{ContractVersion, CONTRACT};
+use cw_utils::{Duration, Expiration};
 
-use crate::msg::{Admin, ModuleInstantiateInfo};
+use crate::msg::{
+    Admin, AllowanceAssetInfo, AllowanceInfo, AllowanceKey, DaoAdminInfo, ItemInfo, ItemValueInfo,
+    ModuleInstantiateInfo, PauseGuardianInfo,
+};
+use crate::state::{Allowance, AllowanceAsset, DaoAdmin, ItemEntry, ItemValue, PauseGuardian};
+use crate::ContractError;
 
 impl ModuleInstantiateInfo {
     pub fn into_wasm_msg(self, contract_address: Addr) -> WasmMsg {
@@ -17,3 +24,124 @@ impl ModuleInstantiateInfo {
         }
     }
 }
+
+impl PauseGuardianInfo {
+    pub fn into_checked(self, deps: Deps) -> StdResult<PauseGuardian> {
+        Ok(PauseGuardian {
+            addr: deps.api.addr_validate(&self.addr)?,
+            max_pause_duration: self.max_pause_duration,
+        })
+    }
+}
+
+impl DaoAdminInfo {
+    /// Validates the admin's address and checks that its expiration
+    /// is set and has not already passed.
+    pub fn into_checked(self, deps: Deps, block: &BlockInfo) -> Result<DaoAdmin, ContractError> {
+        if matches!(self.expiration, Expiration::Never {}) {
+            return Err(ContractError::AdminExpirationNever {});
+        }
+        if self.expiration.is_expired(block) {
+            return Err(ContractError::AdminExpirationPassed {});
+        }
+        Ok(DaoAdmin {
+            addr: deps.api.addr_validate(&self.addr)?,
+            expiration: self.expiration,
+        })
+    }
+}
+
+impl AllowanceAssetInfo {
+    pub fn into_checked(self, deps: Deps) -> StdResult<AllowanceAsset> {
+        Ok(match self {
+            AllowanceAssetInfo::Native { denom } => AllowanceAsset::Native { denom },
+            AllowanceAssetInfo::Cw20 { addr } => AllowanceAsset::Cw20 {
+                addr: deps.api.addr_validate(&addr)?,
+            },
+        })
+    }
+}
+
+impl AllowanceInfo {
+    /// Validates the allowance and starts its first period.
+    pub fn into_checked(
+        self,
+        deps: Deps,
+        block: &BlockInfo,
+        grantee: Addr,
+    ) -> Result<Allowance, ContractError> {
+        if self.amount.is_zero() {
+            return Err(ContractError::ZeroAllowance {});
+        }
+        if matches!(
+            self.refill_period,
+            Some(Duration::Height(0) | Duration::Time(0))
+        ) {
+            return Err(ContractError::ZeroRefillPeriod {});
+        }
+        if self.expiration.is_expired(block) {
+            return Err(ContractError::AllowanceExpired {});
+        }
+        Ok(Allowance {
+            grantee,
+            asset: self.asset.into_checked(deps)?,
+            amount: self.amount,
+            spent: Uint128::zero(),
+            refill_period: self.refill_period,
+            next_refill: self.refill_period.map(|period| period.after(block)),
+            expiration: self.expiration,
+        })
+    }
+}
+
+impl AllowanceKey {
+    pub fn into_checked(self, deps: Deps) -> StdResult<(Addr, String)> {
+        Ok((
+            deps.api.addr_validate(&self.grantee)?,
+            self.asset.into_checked(deps)?.key(),
+        ))
+    }
+}
+
+impl ItemInfo {
+    /// Validates the item's address and, if an expected contract is
+    /// set, checks the cw2 contract name of the item's contract.
+    pub fn into_checked(self, deps: Deps) -> Result<ItemEntry, ContractError> {
+        let value = match self.value {
+            ItemValueInfo::Address { addr } => ItemValue::Address {
+                addr: deps.api.addr_validate(&addr)?,
+            },
+            ItemValueInfo::Text { text } => ItemValue::Text { text },
+        };
+
+        let contract_version = match (&self.expected_contract, &value) {
+            (None, _) => None,
+            (Some(expected), ItemValue::Address { addr }) => {
+                let version: ContractVersion = deps.querier.query(
+                    &WasmQuery::Raw {
+                        contract_addr: addr.to_string(),
+                        key: CONTRACT.as_slice().into(),
+                    }
+                    .into(),
+                )?;
+                if &version.contract != expected {
+                    return Err(ContractError::UnexpectedItemContract {
+                        expected: expected.clone(),
+                        actual: version.contract,
+                    });
+                }
+                Some(version)
+            }
+            (Some(_), ItemValue::Text { .. }) => {
+                return Err(ContractError::ExpectedContractNotAddress {})
+            }
+        };
+
+        Ok(ItemEntry {
+            value,
+            description: self.description,
+            expected_contract: self.expected_contract,
+            contract_version,
+        })
+    }
+}
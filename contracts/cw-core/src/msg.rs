@@ -1,5 +1,5 @@
-use cosmwasm_std::{Binary, CosmosMsg, Empty};
-use cw_utils::Duration;
+use cosmwasm_std::{Binary, CosmosMsg, Empty, Uint128};
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -49,6 +49,82 @@ pub struct InitialItem {
     pub info: InitialItemInfo,
 }
 
+/// Information about a pause guardian.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseGuardianInfo {
+    /// The address of the guardian.
+    pub addr: String,
+    /// The longest the guardian may pause the contract for. Must
+    /// use the same units as the duration passed to `Pause`. After a
+    /// pause ends the guardian must wait this long before pausing
+    /// again.
+    pub max_pause_duration: Duration,
+}
+
+/// Information about a time-bounded admin for the DAO.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DaoAdminInfo {
+    /// The address of the admin.
+    pub addr: String,
+    /// When the admin's privileges end. Must not be `Never` and must
+    /// not have already passed.
+    pub expiration: Expiration,
+}
+
+/// The value of an item to be added to the items map.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemValueInfo {
+    /// A contract or account address.
+    Address { addr: String },
+    /// An arbitrary string. For example, the DAO's website.
+    Text { text: String },
+}
+
+/// Information about an item to be added to the items map.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ItemInfo {
+    /// The item's value.
+    pub value: ItemValueInfo,
+    /// A description of what the item is used for.
+    pub description: Option<String>,
+    /// If set, the item must be an address of a contract whose cw2
+    /// contract name is `expected_contract`. The contract's cw2
+    /// version is recorded alongside the item.
+    pub expected_contract: Option<String>,
+}
+
+/// A token that may be spent from the treasury under an allowance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AllowanceAssetInfo {
+    /// A native token.
+    Native { denom: String },
+    /// A cw20 token.
+    Cw20 { addr: String },
+}
+
+/// Information about a spending allowance to grant.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllowanceInfo {
+    /// The token the allowance is denominated in.
+    pub asset: AllowanceAssetInfo,
+    /// The amount that may be spent each period.
+    pub amount: Uint128,
+    /// How often the allowance is refilled. Must be non-zero. If
+    /// `None` the allowance is a one-off budget.
+    pub refill_period: Option<Duration>,
+    /// When the allowance may no longer be spent.
+    pub expiration: Expiration,
+}
+
+/// Identifies an allowance. Used for pagination.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllowanceKey {
+    pub grantee: String,
+    pub asset: AllowanceAssetInfo,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     /// The name of the core contract.
@@ -77,6 +153,20 @@ pub struct InstantiateMsg {
     /// items map. The value is an enum that either uses an existing
     /// address or instantiates a new contract.
     pub initial_items: Option<Vec<InitialItem>>,
+
+    /// An optional address that may pause the contract for a bounded
+    /// duration and unpause it without going through a proposal.
+    pub pause_guardian: Option<PauseGuardianInfo>,
+
+    /// The core contract of the DAO this DAO is a subDAO of, if
+    /// any. The parent DAO may execute messages on this DAO.
+    pub parent_dao: Option<String>,
+
+    /// An optional address that may call `UpdateConfig`,
+    /// `UpdateProposalModules`, `SetItem`, and `RemoveItem` directly
+    /// until it expires or renounces. Useful while bootstrapping a
+    /// DAO.
+    pub admin: Option<DaoAdminInfo>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -98,22 +188,101 @@ pub enum ExecuteMsg {
     UpdateVotingModule {
         module: ModuleInstantiateInfo,
     },
-    /// Updates the governance contract's governance modules. Module
+    /// Updates the governance contract's governance modules. Callable
+    /// by the core contract or the admin. Module
     /// instantiate info in `to_add` is used to create new modules and
-    /// install them.
+    /// install them. Modules in `to_disable` may no longer create
+    /// proposals but may still execute proposals that have
+    /// passed. Modules in `to_remove` must be disabled, either
+    /// previously or by this message, and are removed entirely.
     UpdateProposalModules {
         to_add: Vec<ModuleInstantiateInfo>,
+        to_disable: Vec<String>,
         to_remove: Vec<String>,
     },
+    /// Pauses the contract for `duration`. Callable by the core
+    /// contract or the pause guardian. While paused only `Unpause`,
+    /// `UpdatePauseGuardian`, `Receive`, `ReceiveNft`, and proposal
+    /// hooks that exclusively unpause the contract or update its
+    /// guardian may be executed. `Unpause` is callable by the same
+    /// addresses.
     Pause {
         duration: Duration,
+        reason: Option<String>,
+    },
+    Unpause {},
+    /// Callable by the core contract. Sets or removes the pause
+    /// guardian.
+    UpdatePauseGuardian {
+        guardian: Option<PauseGuardianInfo>,
+    },
+    /// Callable by the core contract. Sets or removes the DAO's
+    /// admin and clears any pending admin nomination.
+    UpdateAdmin {
+        admin: Option<DaoAdminInfo>,
+    },
+    /// Callable by the admin. Nominates an address to take over as
+    /// admin, or withdraws the current nomination if `None`. The
+    /// nominee keeps the current admin's expiration.
+    NominateAdmin {
+        admin: Option<String>,
+    },
+    /// Callable by the nominated admin. Accepts the nomination,
+    /// replacing the current admin.
+    AcceptAdminNomination {},
+    /// Callable by the admin. Removes the admin and any pending
+    /// nomination.
+    RenounceAdmin {},
+    /// Callable by the core contract or the current parent DAO. Sets
+    /// or removes this DAO's parent DAO.
+    UpdateParentDao {
+        parent_dao: Option<String>,
+    },
+    /// Callable by the parent DAO. The DAO will execute the messages
+    /// in order, as if they had passed a proposal. Allowed while the
+    /// contract is paused.
+    ExecuteParentDaoMsgs {
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Callable by the core contract. Registers a subDAO with an
+    /// optional charter. If the subDAO is already registered its
+    /// charter is replaced.
+    RegisterSubDao {
+        addr: String,
+        charter: Option<String>,
+    },
+    /// Callable by the core contract. Unregisters a subDAO.
+    UnregisterSubDao {
+        addr: String,
+    },
+    /// Callable by the core contract. Grants `grantee` an allowance
+    /// to spend from the treasury without a proposal. Replaces any
+    /// existing allowance of the same asset and resets the amount
+    /// spent.
+    GrantAllowance {
+        grantee: String,
+        allowance: AllowanceInfo,
+    },
+    /// Callable by the core contract. Revokes `grantee`'s allowance
+    /// of `asset`.
+    RevokeAllowance {
+        grantee: String,
+        asset: AllowanceAssetInfo,
+    },
+    /// Callable by a grantee. Sends `amount` of `asset` from the
+    /// treasury to `recipient`, deducting it from the sender's
+    /// allowance.
+    SpendAllowance {
+        asset: AllowanceAssetInfo,
+        amount: Uint128,
+        recipient: String,
     },
     /// Adds an item to the governance contract's item map. If the
     /// item already exists the existing value is overriden. If the
     /// item does not exist a new item is added.
     SetItem {
         key: String,
-        addr: String,
+        item: ItemInfo,
     },
     /// Removes an item from the governance contract's item map.
     RemoveItem {
@@ -137,6 +306,18 @@ pub enum ExecuteMsg {
         to_add: Vec<String>,
         to_remove: Vec<String>,
     },
+    /// Forgets the received NFTs in `collection` with the given token
+    /// IDs that this contract no longer owns. Callable by anyone.
+    PruneCw721Tokens {
+        collection: String,
+        token_ids: Vec<String>,
+    },
+    /// Updates the list of native denoms this contract has
+    /// registered.
+    UpdateNativeList {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
 }
 
 #[voting_query]
@@ -145,26 +326,68 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     /// Gets the contract's config. Returns Config.
     Config {},
-    /// Returns information about if the contract is currently paused.
+    /// Returns information about if the contract is currently
+    /// paused, who paused it, and why.
     PauseInfo {},
+    /// Gets the contract's pause guardian. Returns
+    /// Option<PauseGuardian>.
+    PauseGuardian {},
+    /// Gets the contract's admin. Returns Option<DaoAdmin>, which is
+    /// `None` if there is no admin or it has expired.
+    Admin {},
+    /// Gets the address nominated to become admin. Returns
+    /// Option<Addr>.
+    AdminNomination {},
+    /// Gets the contract's parent DAO. Returns Option<Addr>.
+    ParentDao {},
+    /// Lists the subDAOs registered with this contract. Returns
+    /// Vec<SubDao>.
+    ListSubDaos {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    /// Gets `grantee`'s allowance of `asset`, refilled if its period
+    /// has ended. Returns Option<Allowance>.
+    Allowance {
+        grantee: String,
+        asset: AllowanceAssetInfo,
+    },
+    /// Lists all spending allowances ordered by grantee. Returns
+    /// Vec<Allowance>.
+    ListAllowances {
+        start_after: Option<AllowanceKey>,
+        limit: Option<u64>,
+    },
     /// Gets the contract's voting module. Returns Addr.
     VotingModule {},
-    /// Gets the proposal modules assocaited with the
-    /// contract. Returns Vec<Addr>.
+    /// Gets the proposal modules assocaited with the contract,
+    /// including disabled ones. Returns Vec<ProposalModule>.
     ProposalModules {
         start_at: Option<String>,
         limit: Option<u64>,
     },
+    /// Gets the enabled proposal modules associated with the
+    /// contract. Returns Vec<ProposalModule>.
+    ActiveProposalModules {
+        start_at: Option<String>,
+        limit: Option<u64>,
+    },
+    /// Gets a proposal module by address. Returns
+    /// Option<ProposalModule>.
+    ProposalModule { address: String },
+    /// Gets the cw2 version recorded for a voting or proposal module
+    /// when it was installed. Returns Option<ContractVersion>.
+    ModuleVersion { address: String },
     /// Dumps all of the core contract's state in a single
     /// query. Useful for frontends as performance for queries is more
     /// limited by network times than compute times. Returns
     /// `DumpStateResponse`.
     DumpState {},
-    /// Gets the address associated with an item key.
+    /// Gets the item associated with an item key. Returns
+    /// `GetItemResponse`.
     GetItem { key: String },
-    /// Lists all of the item keys associted with the contract. For
-    /// example, given the items `{ "group": "...", "subdao": "..."}`
-    /// this query would return `["group", "subdao"]`.
+    /// Lists all of the items associted with the contract along with
+    /// their keys. Returns Vec<ListItemsEntry>.
     ListItems {
         start_at: Option<String>,
         limit: Option<u64>,
@@ -181,10 +404,31 @@ pub enum QueryMsg {
         start_at: Option<String>,
         limit: Option<u64>,
     },
+    /// Lists the token IDs this contract owns in the cw721
+    /// `collection` by querying the collection. Returns
+    /// `cw721::TokensResponse`.
+    Cw721Holdings {
+        collection: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the token IDs of the NFTs in `collection` that this
+    /// contract has received. Returns Vec<String>.
+    Cw721ReceivedTokens {
+        collection: String,
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
     /// Gets the token balance for each cw20 registered with the
     /// contract.
     Cw20Balances {
         start_at: Option<String>,
         limit: Option<u64>,
     },
+    /// Gets the contract's balance of each native denom registered
+    /// with the contract. Returns Vec<Coin>.
+    NativeBalances {
+        start_at: Option<String>,
+        limit: Option<u64>,
+    },
 }
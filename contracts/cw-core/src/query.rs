@@ -1,10 +1,10 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, Uint128};
 use cw2::ContractVersion;
 use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::state::Config;
+use crate::state::{Config, DaoAdmin, ItemEntry, ProposalModule};
 
 /// Relevant state for the governance module. Returned by the
 /// `DumpState` query.
@@ -17,16 +17,34 @@ pub struct DumpStateResponse {
     /// The governance contract's version.
     pub version: ContractVersion,
     /// The governance modules associated with the governance
-    /// contract.
-    pub governance_modules: Vec<Addr>,
+    /// contract, including disabled ones.
+    pub governance_modules: Vec<ProposalModule>,
+    /// The number of enabled proposal modules.
+    pub active_proposal_module_count: u32,
+    /// The number of proposal modules that have ever been added.
+    pub total_proposal_module_count: u32,
     /// The voting module associated with the governance contract.
     pub voting_module: Addr,
+    /// The contract's balance of each registered native denom.
+    pub native_balances: Vec<Coin>,
+    /// The DAO this DAO is a subDAO of, if any.
+    pub parent_dao: Option<Addr>,
+    /// The DAO's admin, if any and not yet expired.
+    pub admin: Option<DaoAdmin>,
+    /// The address nominated to become admin, if any.
+    pub admin_nomination: Option<Addr>,
 }
 
 /// Information about if the contract is currently paused.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum PauseInfoResponse {
-    Paused { expiration: Expiration },
+    Paused {
+        expiration: Expiration,
+        /// The address that paused the contract.
+        paused_by: Addr,
+        /// The reason given when pausing, if any.
+        reason: Option<String>,
+    },
     Unpaused {},
 }
 
@@ -35,7 +53,16 @@ pub enum PauseInfoResponse {
 pub struct GetItemResponse {
     /// `None` if no item with the provided key was found, `Some`
     /// otherwise.
-    pub item: Option<Addr>,
+    pub item: Option<ItemEntry>,
+}
+
+/// Entry returned by the `ListItems` query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListItemsEntry {
+    /// The item's key.
+    pub key: String,
+    /// The item.
+    pub item: ItemEntry,
 }
 
 /// Returned by Cw20Balances query.
@@ -1,8 +1,9 @@
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Empty};
+use cosmwasm_std::{Addr, BlockInfo, Empty, Uint128};
+use cw2::ContractVersion;
 use cw_storage_plus::{Item, Map};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -26,11 +27,131 @@ pub const CONFIG: Item<Config> = Item::new("config");
 
 pub const PAUSED: Item<Expiration> = Item::new("paused");
 
+/// Who paused the contract and why.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseReason {
+    /// The address that paused the contract.
+    pub paused_by: Addr,
+    /// An optional explanation provided when pausing.
+    pub reason: Option<String>,
+}
+
+pub const PAUSE_REASON: Item<PauseReason> = Item::new("pause_reason");
+
+/// An address that may pause the contract without a proposal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseGuardian {
+    /// The guardian's address.
+    pub addr: Addr,
+    /// The longest the guardian may pause the contract for. After a
+    /// pause ends the guardian must wait this long before pausing
+    /// again.
+    pub max_pause_duration: Duration,
+}
+
+pub const PAUSE_GUARDIAN: Item<PauseGuardian> = Item::new("pause_guardian");
+/// When the pause guardian may next pause the contract. Guarantees
+/// the DAO time to remove a guardian that keeps pausing it.
+pub const GUARDIAN_PAUSE_COOLDOWN: Item<Expiration> = Item::new("guardian_pause_cooldown");
+
+/// An address that may update the config, proposal modules, and
+/// items without a proposal until `expiration`. Intended for
+/// bootstrapping a DAO.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DaoAdmin {
+    /// The admin's address.
+    pub addr: Addr,
+    /// When the admin's privileges end.
+    pub expiration: Expiration,
+}
+
+pub const DAO_ADMIN: Item<DaoAdmin> = Item::new("dao_admin");
+/// An address nominated by the admin to take over as admin. The
+/// nominee becomes admin once it accepts the nomination.
+pub const NOMINATED_DAO_ADMIN: Item<Addr> = Item::new("nominated_dao_admin");
+
 /// The voting module associated with this contract.
 pub const VOTING_MODULE: Item<Addr> = Item::new("voting_module");
-pub const PROPOSAL_MODULES: Map<Addr, Empty> = Map::new("governance_modules");
 
-pub const ITEMS: Map<String, Addr> = Map::new("items");
+/// The cw2 version of each voting and proposal module, recorded when
+/// the module was installed and checked to implement its interface.
+pub const MODULE_VERSIONS: Map<Addr, ContractVersion> = Map::new("module_versions");
+
+/// The status of a proposal module.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalModuleStatus {
+    /// The module may create and execute proposals.
+    Enabled,
+    /// The module may execute proposals that have already passed but
+    /// may not create new ones.
+    Disabled,
+}
+
+/// A proposal module registered with this contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalModule {
+    /// The address of the proposal module.
+    pub address: Addr,
+    /// A unique prefix for the module derived from the order in
+    /// which it was added. For example, `A`, `B`, ..., `Z`, `AA`.
+    /// Prefixes are never reused.
+    pub prefix: String,
+    /// The module's status.
+    pub status: ProposalModuleStatus,
+}
+
+pub const PROPOSAL_MODULES: Map<Addr, ProposalModule> = Map::new("proposal_modules");
+/// The number of proposal modules with an `Enabled` status.
+pub const ACTIVE_PROPOSAL_MODULE_COUNT: Item<u32> = Item::new("active_proposal_module_count");
+/// The number of enabled proposal modules that can not create
+/// proposals, for example ragequit. These are included in
+/// `ACTIVE_PROPOSAL_MODULE_COUNT` but do not keep the DAO governable.
+pub const ACTIVE_NON_PROPOSING_MODULE_COUNT: Item<u32> =
+    Item::new("active_non_proposing_module_count");
+/// The number of proposal modules that have ever been added. Used to
+/// derive module prefixes.
+pub const TOTAL_PROPOSAL_MODULE_COUNT: Item<u32> = Item::new("total_proposal_module_count");
+
+/// The value of an item in the items map.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemValue {
+    /// A contract or account address.
+    Address { addr: Addr },
+    /// An arbitrary string. For example, the DAO's website.
+    Text { text: String },
+}
+
+/// An item in the items map along with its metadata.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ItemEntry {
+    /// The item's value.
+    pub value: ItemValue,
+    /// A description of what the item is used for.
+    pub description: Option<String>,
+    /// The cw2 contract name the item is expected to have. If set
+    /// the item was verified to have this contract name when it was
+    /// registered.
+    pub expected_contract: Option<String>,
+    /// The cw2 version of the item's contract when it was registered.
+    /// Only set if `expected_contract` is set.
+    pub contract_version: Option<ContractVersion>,
+}
+
+impl ItemEntry {
+    /// An address item with no metadata.
+    pub fn from_addr(addr: Addr) -> Self {
+        Self {
+            value: ItemValue::Address { addr },
+            description: None,
+            expected_contract: None,
+            contract_version: None,
+        }
+    }
+}
+
+pub const ITEMS: Map<String, ItemEntry> = Map::new("items");
 pub const PENDING_ITEM_INSTANTIATION_NAMES: Map<u64, String> =
     Map::new("pending_item_instantiations");
 
@@ -40,3 +161,103 @@ pub const CW20_LIST: Map<Addr, Empty> = Map::new("cw20s");
 /// Set of cw721 tokens that have been registered with this contract's
 /// treasury.
 pub const CW721_LIST: Map<Addr, Empty> = Map::new("cw721s");
+/// Set of (collection, token ID) pairs for NFTs this contract has
+/// received.
+pub const CW721_TOKENS: Map<(Addr, String), Empty> = Map::new("cw721_tokens");
+/// Set of native denoms that have been registered with this
+/// contract's treasury.
+pub const NATIVE_LIST: Map<String, Empty> = Map::new("native_denoms");
+
+/// The DAO this DAO is a subDAO of, if any. The parent DAO may execute
+/// messages on this DAO as a governance override.
+pub const PARENT_DAO: Item<Addr> = Item::new("parent_dao");
+
+/// Information about a subDAO of this DAO.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SubDao {
+    /// The core contract address of the subDAO.
+    pub addr: Addr,
+    /// An optional description of the subDAO's purpose and mandate.
+    pub charter: Option<String>,
+}
+
+/// Map from subDAO core contract address to its charter.
+pub const SUB_DAOS: Map<Addr, Option<String>> = Map::new("sub_daos");
+
+/// A token that may be spent from the treasury under an allowance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AllowanceAsset {
+    /// A native token.
+    Native { denom: String },
+    /// A cw20 token.
+    Cw20 { addr: Addr },
+}
+
+impl AllowanceAsset {
+    /// The key under which allowances of this asset are stored for
+    /// a grantee. Prefixed so native denoms and cw20 addresses can
+    /// never collide.
+    pub fn key(&self) -> String {
+        match self {
+            AllowanceAsset::Native { denom } => format!("native:{}", denom),
+            AllowanceAsset::Cw20 { addr } => format!("cw20:{}", addr),
+        }
+    }
+}
+
+/// A budget that `grantee` may spend from the treasury without a
+/// proposal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Allowance {
+    /// The address that may spend the allowance.
+    pub grantee: Addr,
+    /// The token the allowance is denominated in.
+    pub asset: AllowanceAsset,
+    /// The amount that may be spent each period.
+    pub amount: Uint128,
+    /// The amount spent so far in the current period.
+    pub spent: Uint128,
+    /// How often the amount spent is reset to zero. If `None` the
+    /// allowance is never refilled.
+    pub refill_period: Option<Duration>,
+    /// When the current period ends. `None` if the allowance is
+    /// never refilled.
+    pub next_refill: Option<Expiration>,
+    /// When the allowance may no longer be spent.
+    pub expiration: Expiration,
+}
+
+impl Allowance {
+    /// Starts a new period if the current one has ended. Periods
+    /// are anchored to when the allowance was granted, so the next
+    /// period ends on the first period boundary after `block` rather
+    /// than a full period after the allowance is next used.
+    pub fn refill(&mut self, block: &BlockInfo) {
+        if let (Some(period), Some(next_refill)) = (self.refill_period, self.next_refill) {
+            if next_refill.is_expired(block) {
+                self.spent = Uint128::zero();
+                self.next_refill = Some(match (next_refill, period) {
+                    (Expiration::AtHeight(end), Duration::Height(period)) => {
+                        let elapsed = (block.height - end) / period + 1;
+                        Expiration::AtHeight(end + elapsed * period)
+                    }
+                    (Expiration::AtTime(end), Duration::Time(period)) => {
+                        let elapsed = (block.time.seconds() - end.seconds()) / period + 1;
+                        Expiration::AtTime(end.plus_seconds(elapsed * period))
+                    }
+                    _ => period.after(block),
+                });
+            }
+        }
+    }
+
+    /// The amount that may still be spent in the current period.
+    pub fn remaining(&self) -> Uint128 {
+        self.amount.saturating_sub(self.spent)
+    }
+}
+
+/// Map from (grantee, asset key) to the grantee's allowance of that
+/// asset.
+pub const ALLOWANCES: Map<(Addr, String), Allowance> = Map::new("allowances");
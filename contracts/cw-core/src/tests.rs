@@ -1,16 +1,23 @@
-use cosmwasm_std::{to_binary, Addr, Empty, Uint128, WasmMsg};
+use cosmwasm_std::{coin, to_binary, Addr, Coin, Empty, Uint128, WasmMsg};
 use cw2::ContractVersion;
 use cw_core_interface::voting::VotingPowerAtHeightResponse;
 use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 use cw_utils::{Duration, Expiration};
 
 use crate::{
+    contract::derive_proposal_module_prefix,
     msg::{
-        Admin, ExecuteMsg, InitialItem, InitialItemInfo, InstantiateMsg, ModuleInstantiateInfo,
-        QueryMsg,
+        Admin, AllowanceAssetInfo, AllowanceInfo, AllowanceKey, DaoAdminInfo, ExecuteMsg,
+        InitialItem, InitialItemInfo, InstantiateMsg, ItemInfo, ItemValueInfo,
+        ModuleInstantiateInfo, PauseGuardianInfo, QueryMsg,
+    },
+    query::{
+        Cw20BalanceResponse, DumpStateResponse, GetItemResponse, ListItemsEntry, PauseInfoResponse,
+    },
+    state::{
+        Allowance, AllowanceAsset, Config, DaoAdmin, ItemEntry, ItemValue, PauseGuardian,
+        ProposalModule, ProposalModuleStatus, SubDao,
     },
-    query::{Cw20BalanceResponse, DumpStateResponse, GetItemResponse, PauseInfoResponse},
-    state::Config,
     ContractError,
 };
 
@@ -63,6 +70,33 @@ fn cw_gov_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+/// Stores a cw20 balance voting module and returns the info needed to
+/// instantiate it as a DAO's voting module.
+fn voting_module_instantiate_info(app: &mut App) -> ModuleInstantiateInfo {
+    let cw20_id = app.store_code(cw20_contract());
+    let voting_id = app.store_code(cw20_balances_voting());
+    let voting_instantiate = cw20_balance_voting::msg::InstantiateMsg {
+        token_info: cw20_balance_voting::msg::TokenInfo::New {
+            code_id: cw20_id,
+            label: "DAO DAO voting".to_string(),
+            name: "DAO DAO".to_string(),
+            symbol: "DAO".to_string(),
+            decimals: 6,
+            initial_balances: vec![cw20::Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::from(2u64),
+            }],
+            marketing: None,
+        },
+    };
+    ModuleInstantiateInfo {
+        code_id: voting_id,
+        msg: to_binary(&voting_instantiate).unwrap(),
+        admin: Admin::CoreContract {},
+        label: "voting module".to_string(),
+    }
+}
+
 fn instantiate_gov(app: &mut App, code_id: u64, msg: InstantiateMsg) -> Addr {
     app.instantiate_contract(
         code_id,
@@ -77,16 +111,11 @@ fn instantiate_gov(app: &mut App, code_id: u64, msg: InstantiateMsg) -> Addr {
 
 fn test_instantiate_with_n_gov_modules(n: usize) {
     let mut app = App::default();
-    let cw20_id = app.store_code(cw20_contract());
+    let govmod_id = app.store_code(sudo_govmod_contract());
     let gov_id = app.store_code(cw_gov_contract());
 
-    let cw20_instantiate = cw20_base::msg::InstantiateMsg {
-        name: "DAO".to_string(),
-        symbol: "DAO".to_string(),
-        decimals: 6,
-        initial_balances: vec![],
-        mint: None,
-        marketing: None,
+    let govmod_instantiate = cw_proposal_sudo::msg::InstantiateMsg {
+        root: CREATOR_ADDR.to_string(),
     };
     let instantiate = InstantiateMsg {
         name: "DAO DAO".to_string(),
@@ -94,20 +123,18 @@ fn test_instantiate_with_n_gov_modules(n: usize) {
         image_url: None,
         automatically_add_cw20s: true,
         automatically_add_cw721s: true,
-        voting_module_instantiate_info: ModuleInstantiateInfo {
-            code_id: cw20_id,
-            msg: to_binary(&cw20_instantiate).unwrap(),
-            admin: Admin::CoreContract {},
-            label: "voting module".to_string(),
-        },
+        voting_module_instantiate_info: voting_module_instantiate_info(&mut app),
         proposal_modules_instantiate_info: (0..n)
             .map(|n| ModuleInstantiateInfo {
-                code_id: cw20_id,
-                msg: to_binary(&cw20_instantiate).unwrap(),
+                code_id: govmod_id,
+                msg: to_binary(&govmod_instantiate).unwrap(),
                 admin: Admin::CoreContract {},
                 label: format!("governance module {}", n),
             })
             .collect(),
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
     let gov_addr = instantiate_gov(&mut app, gov_id, instantiate);
@@ -154,38 +181,35 @@ fn test_valid_instantiate() {
 }
 
 #[test]
-#[should_panic(expected = "Error parsing into type cw20_base::msg::InstantiateMsg: Invalid type")]
+#[should_panic(
+    expected = "Error parsing into type cw_proposal_sudo::msg::InstantiateMsg: Invalid type"
+)]
 fn test_instantiate_with_submessage_failure() {
     let mut app = App::default();
-    let cw20_id = app.store_code(cw20_contract());
+    let govmod_id = app.store_code(sudo_govmod_contract());
     let gov_id = app.store_code(cw_gov_contract());
 
-    let cw20_instantiate = cw20_base::msg::InstantiateMsg {
-        name: "DAO".to_string(),
-        symbol: "DAO".to_string(),
-        decimals: 6,
-        initial_balances: vec![],
-        mint: None,
-        marketing: None,
+    let govmod_instantiate = cw_proposal_sudo::msg::InstantiateMsg {
+        root: CREATOR_ADDR.to_string(),
     };
 
     let mut governance_modules = (0..3)
         .map(|n| ModuleInstantiateInfo {
-            code_id: cw20_id,
-            msg: to_binary(&cw20_instantiate).unwrap(),
+            code_id: govmod_id,
+            msg: to_binary(&govmod_instantiate).unwrap(),
             admin: Admin::CoreContract {},
             label: format!("governance module {}", n),
         })
         .collect::<Vec<_>>();
     governance_modules.push(ModuleInstantiateInfo {
-        code_id: cw20_id,
+        code_id: govmod_id,
         msg: to_binary("bad").unwrap(),
         admin: Admin::CoreContract {},
         label: "I have a bad instantiate message".to_string(),
     });
     governance_modules.push(ModuleInstantiateInfo {
-        code_id: cw20_id,
-        msg: to_binary(&cw20_instantiate).unwrap(),
+        code_id: govmod_id,
+        msg: to_binary(&govmod_instantiate).unwrap(),
         admin: Admin::CoreContract {},
         label: "Everybody knowing
 that goodness is good
@@ -199,13 +223,11 @@ makes wickedness."
         image_url: None,
         automatically_add_cw20s: true,
         automatically_add_cw721s: true,
-        voting_module_instantiate_info: ModuleInstantiateInfo {
-            code_id: cw20_id,
-            msg: to_binary(&cw20_instantiate).unwrap(),
-            admin: Admin::CoreContract {},
-            label: "voting module".to_string(),
-        },
+        voting_module_instantiate_info: voting_module_instantiate_info(&mut app),
         proposal_modules_instantiate_info: governance_modules,
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
     instantiate_gov(&mut app, gov_id, instantiate);
@@ -227,18 +249,16 @@ fn test_update_config() {
         image_url: None,
         automatically_add_cw20s: true,
         automatically_add_cw721s: true,
-        voting_module_instantiate_info: ModuleInstantiateInfo {
-            code_id: govmod_id,
-            msg: to_binary(&govmod_instantiate).unwrap(),
-            admin: Admin::CoreContract {},
-            label: "voting module".to_string(),
-        },
+        voting_module_instantiate_info: voting_module_instantiate_info(&mut app),
         proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
             code_id: govmod_id,
             msg: to_binary(&govmod_instantiate).unwrap(),
             admin: Admin::CoreContract {},
             label: "voting module".to_string(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
 
@@ -253,7 +273,7 @@ fn test_update_config() {
         )
         .unwrap();
 
-    let modules: Vec<Addr> = app
+    let modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             gov_addr.clone(),
@@ -276,7 +296,7 @@ fn test_update_config() {
 
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        modules[0].clone(),
+        modules[0].address.clone(),
         &cw_proposal_sudo::msg::ExecuteMsg::Execute {
             msgs: vec![WasmMsg::Execute {
                 contract_addr: gov_addr.to_string(),
@@ -315,18 +335,16 @@ fn test_swap_governance(swaps: Vec<(u64, u64)>) {
         image_url: None,
         automatically_add_cw20s: true,
         automatically_add_cw721s: true,
-        voting_module_instantiate_info: ModuleInstantiateInfo {
-            code_id: govmod_id,
-            msg: to_binary(&govmod_instantiate).unwrap(),
-            admin: Admin::CoreContract {},
-            label: "voting module".to_string(),
-        },
+        voting_module_instantiate_info: voting_module_instantiate_info(&mut app),
         proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
             code_id: govmod_id,
             msg: to_binary(&govmod_instantiate).unwrap(),
             admin: Admin::CoreContract {},
             label: "governance module".to_string(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
 
@@ -341,7 +359,7 @@ fn test_swap_governance(swaps: Vec<(u64, u64)>) {
         )
         .unwrap();
 
-    let modules: Vec<Addr> = app
+    let modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             gov_addr.clone(),
@@ -354,12 +372,12 @@ fn test_swap_governance(swaps: Vec<(u64, u64)>) {
 
     assert_eq!(modules.len(), 1);
 
-    for (add, remove) in swaps {
-        let start_modules: Vec<Addr> = app
+    for (add, disable) in swaps {
+        let start_modules: Vec<ProposalModule> = app
             .wrap()
             .query_wasm_smart(
                 gov_addr.clone(),
-                &QueryMsg::ProposalModules {
+                &QueryMsg::ActiveProposalModules {
                     start_at: None,
                     limit: None,
                 },
@@ -375,21 +393,25 @@ fn test_swap_governance(swaps: Vec<(u64, u64)>) {
             })
             .collect();
 
-        let to_remove: Vec<_> = start_modules
+        let to_disable: Vec<_> = start_modules
             .iter()
-            .take(remove as usize)
-            .map(|a| a.to_string())
+            .take(disable as usize)
+            .map(|m| m.address.to_string())
             .collect();
 
         app.execute_contract(
             Addr::unchecked(CREATOR_ADDR),
-            start_modules[0].clone(),
+            start_modules[0].address.clone(),
             &cw_proposal_sudo::msg::ExecuteMsg::Execute {
                 msgs: vec![WasmMsg::Execute {
                     contract_addr: gov_addr.to_string(),
                     funds: vec![],
-                    msg: to_binary(&ExecuteMsg::UpdateProposalModules { to_add, to_remove })
-                        .unwrap(),
+                    msg: to_binary(&ExecuteMsg::UpdateProposalModules {
+                        to_add,
+                        to_disable,
+                        to_remove: vec![],
+                    })
+                    .unwrap(),
                 }
                 .into()],
             },
@@ -397,11 +419,11 @@ fn test_swap_governance(swaps: Vec<(u64, u64)>) {
         )
         .unwrap();
 
-        let finish_modules: Vec<Addr> = app
+        let finish_modules: Vec<ProposalModule> = app
             .wrap()
             .query_wasm_smart(
                 gov_addr.clone(),
-                &QueryMsg::ProposalModules {
+                &QueryMsg::ActiveProposalModules {
                     start_at: None,
                     limit: None,
                 },
@@ -410,10 +432,10 @@ fn test_swap_governance(swaps: Vec<(u64, u64)>) {
 
         assert_eq!(
             finish_modules.len() as u64,
-            start_modules.len() as u64 + add - remove
+            start_modules.len() as u64 + add - disable
         );
-        for module in start_modules.into_iter().take(remove as usize) {
-            assert!(!finish_modules.contains(&module))
+        for module in start_modules.into_iter().take(disable as usize) {
+            assert!(!finish_modules.iter().any(|m| m.address == module.address))
         }
     }
 }
@@ -430,7 +452,7 @@ fn test_swap_governance_bad() {
 }
 
 #[test]
-fn test_removed_modules_can_not_execute() {
+fn test_disabled_and_removed_modules() {
     let mut app = App::default();
     let govmod_id = app.store_code(sudo_govmod_contract());
     let gov_id = app.store_code(cw_gov_contract());
@@ -445,18 +467,16 @@ fn test_removed_modules_can_not_execute() {
         image_url: None,
         automatically_add_cw20s: true,
         automatically_add_cw721s: true,
-        voting_module_instantiate_info: ModuleInstantiateInfo {
-            code_id: govmod_id,
-            msg: to_binary(&govmod_instantiate).unwrap(),
-            admin: Admin::CoreContract {},
-            label: "voting module".to_string(),
-        },
+        voting_module_instantiate_info: voting_module_instantiate_info(&mut app),
         proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
             code_id: govmod_id,
             msg: to_binary(&govmod_instantiate).unwrap(),
             admin: Admin::CoreContract {},
             label: "governance module".to_string(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
 
@@ -471,7 +491,7 @@ fn test_removed_modules_can_not_execute() {
         )
         .unwrap();
 
-    let modules: Vec<Addr> = app
+    let modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             gov_addr.clone(),
@@ -485,6 +505,9 @@ fn test_removed_modules_can_not_execute() {
     assert_eq!(modules.len(), 1);
 
     let start_module = modules.into_iter().next().unwrap();
+    assert_eq!(start_module.prefix, "A");
+    assert_eq!(start_module.status, ProposalModuleStatus::Enabled);
+    let start_module = start_module.address;
 
     let to_add = vec![ModuleInstantiateInfo {
         code_id: govmod_id,
@@ -493,7 +516,35 @@ fn test_removed_modules_can_not_execute() {
         label: "new governance module".to_string(),
     }];
 
-    let to_remove = vec![start_module.to_string()];
+    // Modules must be disabled before they are removed.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            start_module.clone(),
+            &cw_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: gov_addr.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&ExecuteMsg::UpdateProposalModules {
+                        to_add: to_add.clone(),
+                        to_disable: vec![],
+                        to_remove: vec![start_module.to_string()],
+                    })
+                    .unwrap(),
+                }
+                .into()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ModuleNotDisabled {
+            address: start_module.to_string()
+        }
+    );
 
     // Swap ourselves out.
     app.execute_contract(
@@ -503,7 +554,12 @@ fn test_removed_modules_can_not_execute() {
             msgs: vec![WasmMsg::Execute {
                 contract_addr: gov_addr.to_string(),
                 funds: vec![],
-                msg: to_binary(&ExecuteMsg::UpdateProposalModules { to_add, to_remove }).unwrap(),
+                msg: to_binary(&ExecuteMsg::UpdateProposalModules {
+                    to_add: to_add.clone(),
+                    to_disable: vec![start_module.to_string()],
+                    to_remove: vec![],
+                })
+                .unwrap(),
             }
             .into()],
         },
@@ -511,40 +567,85 @@ fn test_removed_modules_can_not_execute() {
     )
     .unwrap();
 
-    let finish_modules: Vec<Addr> = app
+    let active_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             gov_addr.clone(),
-            &QueryMsg::ProposalModules {
+            &QueryMsg::ActiveProposalModules {
                 start_at: None,
                 limit: None,
             },
         )
         .unwrap();
+    assert_eq!(active_modules.len(), 1);
+    let new_proposal_module = active_modules.into_iter().next().unwrap();
+    assert_eq!(new_proposal_module.prefix, "B");
+    let new_proposal_module = new_proposal_module.address;
+
+    // The disabled module is still listed and may still execute
+    // messages.
+    let disabled: Option<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::ProposalModule {
+                address: start_module.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        disabled,
+        Some(ProposalModule {
+            address: start_module.clone(),
+            prefix: "A".to_string(),
+            status: ProposalModuleStatus::Disabled,
+        })
+    );
+    let state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(state.governance_modules.len(), 2);
+    assert_eq!(state.active_proposal_module_count, 1);
+    assert_eq!(state.total_proposal_module_count, 2);
 
-    let new_proposal_module = finish_modules.into_iter().next().unwrap();
-
-    // Try to add a new module and remove the one we added
-    // earlier. This should fail as we have been removed.
-    let to_add = vec![ModuleInstantiateInfo {
-        code_id: govmod_id,
-        msg: to_binary(&govmod_instantiate).unwrap(),
-        admin: Admin::CoreContract {},
-        label: "new governance module".to_string(),
-    }];
-    let to_remove = vec![new_proposal_module.to_string()];
+    let update_config = ExecuteMsg::UpdateConfig {
+        config: Config {
+            name: "Disabled but not forgotten".to_string(),
+            description: "A DAO that builds DAOs.".to_string(),
+            image_url: None,
+            automatically_add_cw20s: true,
+            automatically_add_cw721s: true,
+        },
+    };
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        start_module.clone(),
+        &cw_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: gov_addr.to_string(),
+                funds: vec![],
+                msg: to_binary(&update_config).unwrap(),
+            }
+            .into()],
+        },
+        &[],
+    )
+    .unwrap();
 
+    // Disabling a module twice fails.
     let err: ContractError = app
         .execute_contract(
             Addr::unchecked(CREATOR_ADDR),
-            start_module,
+            new_proposal_module.clone(),
             &cw_proposal_sudo::msg::ExecuteMsg::Execute {
                 msgs: vec![WasmMsg::Execute {
                     contract_addr: gov_addr.to_string(),
                     funds: vec![],
                     msg: to_binary(&ExecuteMsg::UpdateProposalModules {
-                        to_add: to_add.clone(),
-                        to_remove: to_remove.clone(),
+                        to_add: vec![],
+                        to_disable: vec![start_module.to_string()],
+                        to_remove: vec![],
                     })
                     .unwrap(),
                 }
@@ -555,9 +656,14 @@ fn test_removed_modules_can_not_execute() {
         .unwrap_err()
         .downcast()
         .unwrap();
-    assert!(matches!(err, ContractError::Unauthorized {}));
+    assert_eq!(
+        err,
+        ContractError::ModuleAlreadyDisabled {
+            address: start_module.to_string()
+        }
+    );
 
-    // The new proposal module should be able to perform actions.
+    // Remove the disabled module. It may no longer execute.
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
         new_proposal_module,
@@ -565,13 +671,64 @@ fn test_removed_modules_can_not_execute() {
             msgs: vec![WasmMsg::Execute {
                 contract_addr: gov_addr.to_string(),
                 funds: vec![],
-                msg: to_binary(&ExecuteMsg::UpdateProposalModules { to_add, to_remove }).unwrap(),
+                msg: to_binary(&ExecuteMsg::UpdateProposalModules {
+                    to_add,
+                    to_disable: vec![],
+                    to_remove: vec![start_module.to_string()],
+                })
+                .unwrap(),
             }
             .into()],
         },
         &[],
     )
     .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            start_module,
+            &cw_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: gov_addr.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&update_config).unwrap(),
+                }
+                .into()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Prefixes are not reused after a module is removed.
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr,
+            &QueryMsg::ProposalModules {
+                start_at: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let mut prefixes: Vec<String> = modules.into_iter().map(|m| m.prefix).collect();
+    prefixes.sort();
+    assert_eq!(prefixes, vec!["B".to_string(), "C".to_string()]);
+}
+
+#[test]
+fn test_derive_proposal_module_prefix() {
+    assert_eq!(derive_proposal_module_prefix(0), "A");
+    assert_eq!(derive_proposal_module_prefix(25), "Z");
+    assert_eq!(derive_proposal_module_prefix(26), "AA");
+    assert_eq!(derive_proposal_module_prefix(27), "AB");
+    assert_eq!(derive_proposal_module_prefix(51), "AZ");
+    assert_eq!(derive_proposal_module_prefix(52), "BA");
+    assert_eq!(derive_proposal_module_prefix(701), "ZZ");
+    assert_eq!(derive_proposal_module_prefix(702), "AAA");
 }
 
 #[test]
@@ -590,18 +747,16 @@ fn test_swap_voting_module() {
         image_url: None,
         automatically_add_cw20s: true,
         automatically_add_cw721s: true,
-        voting_module_instantiate_info: ModuleInstantiateInfo {
-            code_id: govmod_id,
-            msg: to_binary(&govmod_instantiate).unwrap(),
-            admin: Admin::CoreContract {},
-            label: "voting module".to_string(),
-        },
+        voting_module_instantiate_info: voting_module_instantiate_info(&mut app),
         proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
             code_id: govmod_id,
             msg: to_binary(&govmod_instantiate).unwrap(),
             admin: Admin::CoreContract {},
             label: "governance module".to_string(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
 
@@ -621,7 +776,7 @@ fn test_swap_voting_module() {
         .query_wasm_smart(gov_addr.clone(), &QueryMsg::VotingModule {})
         .unwrap();
 
-    let modules: Vec<Addr> = app
+    let modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             gov_addr.clone(),
@@ -634,20 +789,16 @@ fn test_swap_voting_module() {
 
     assert_eq!(modules.len(), 1);
 
+    let new_voting_module = voting_module_instantiate_info(&mut app);
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        modules[0].clone(),
+        modules[0].address.clone(),
         &cw_proposal_sudo::msg::ExecuteMsg::Execute {
             msgs: vec![WasmMsg::Execute {
                 contract_addr: gov_addr.to_string(),
                 funds: vec![],
                 msg: to_binary(&ExecuteMsg::UpdateVotingModule {
-                    module: ModuleInstantiateInfo {
-                        code_id: govmod_id,
-                        msg: to_binary(&govmod_instantiate).unwrap(),
-                        admin: Admin::CoreContract {},
-                        label: "voting module".to_string(),
-                    },
+                    module: new_voting_module,
                 })
                 .unwrap(),
             }
@@ -689,18 +840,16 @@ fn test_permissions() {
         name: "DAO DAO".to_string(),
         description: "A DAO that builds DAOs.".to_string(),
         image_url: None,
-        voting_module_instantiate_info: ModuleInstantiateInfo {
-            code_id: govmod_id,
-            msg: to_binary(&govmod_instantiate).unwrap(),
-            admin: Admin::CoreContract {},
-            label: "voting module".to_string(),
-        },
+        voting_module_instantiate_info: voting_module_instantiate_info(&mut app),
         proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
             code_id: govmod_id,
             msg: to_binary(&govmod_instantiate).unwrap(),
             admin: Admin::CoreContract {},
             label: "governance module".to_string(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
         automatically_add_cw20s: true,
         automatically_add_cw721s: true,
@@ -735,6 +884,7 @@ fn test_permissions() {
         gov_addr.clone(),
         ExecuteMsg::UpdateProposalModules {
             to_add: vec![],
+            to_disable: vec![],
             to_remove: vec![],
         },
     );
@@ -797,6 +947,9 @@ fn do_standard_instantiate(auto_add: bool) -> (Addr, App) {
             admin: Admin::CoreContract {},
             label: "governance module".to_string(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
 
@@ -842,7 +995,14 @@ fn set_item(app: &mut App, gov_addr: Addr, key: String, addr: String) {
     app.execute_contract(
         gov_addr.clone(),
         gov_addr,
-        &ExecuteMsg::SetItem { key, addr },
+        &ExecuteMsg::SetItem {
+            key,
+            item: ItemInfo {
+                value: ItemValueInfo::Address { addr },
+                description: None,
+                expected_contract: None,
+            },
+        },
         &[],
     )
     .unwrap();
@@ -869,7 +1029,7 @@ fn list_items(
     gov_addr: Addr,
     start_at: Option<String>,
     limit: Option<u64>,
-) -> Vec<String> {
+) -> Vec<ListItemsEntry> {
     app.wrap()
         .query_wasm_smart(gov_addr, &QueryMsg::ListItems { start_at, limit })
         .unwrap()
@@ -892,7 +1052,7 @@ fn test_add_remove_get() {
     assert_eq!(
         a,
         GetItemResponse {
-            item: Some(Addr::unchecked("aaaaaaddr"))
+            item: Some(ItemEntry::from_addr(Addr::unchecked("aaaaaaddr")))
         }
     );
 
@@ -904,51 +1064,201 @@ fn test_add_remove_get() {
 }
 
 #[test]
-fn test_list_items() {
-    let mut app = App::default();
-    let govmod_id = app.store_code(sudo_govmod_contract());
-    let voting_id = app.store_code(cw20_balances_voting());
-    let gov_id = app.store_code(cw_gov_contract());
-    let cw20_id = app.store_code(cw20_contract());
+fn test_item_metadata() {
+    let (gov_addr, mut app) = do_standard_instantiate(true);
 
-    let govmod_instantiate = cw_proposal_sudo::msg::InstantiateMsg {
-        root: CREATOR_ADDR.to_string(),
-    };
-    let voting_instantiate = cw20_balance_voting::msg::InstantiateMsg {
-        token_info: cw20_balance_voting::msg::TokenInfo::New {
-            code_id: cw20_id,
-            label: "DAO DAO voting".to_string(),
-            name: "DAO DAO".to_string(),
-            symbol: "DAO".to_string(),
-            decimals: 6,
-            initial_balances: vec![cw20::Cw20Coin {
-                address: CREATOR_ADDR.to_string(),
-                amount: Uint128::from(2u64),
-            }],
-            marketing: None,
+    app.execute_contract(
+        gov_addr.clone(),
+        gov_addr.clone(),
+        &ExecuteMsg::SetItem {
+            key: "website".to_string(),
+            item: ItemInfo {
+                value: ItemValueInfo::Text {
+                    text: "https://daodao.zone".to_string(),
+                },
+                description: Some("The DAO's website.".to_string()),
+                expected_contract: None,
+            },
         },
-    };
+        &[],
+    )
+    .unwrap();
+    let website = get_item(&mut app, gov_addr.clone(), "website".to_string());
+    assert_eq!(
+        website.item,
+        Some(ItemEntry {
+            value: ItemValue::Text {
+                text: "https://daodao.zone".to_string()
+            },
+            description: Some("The DAO's website.".to_string()),
+            expected_contract: None,
+            contract_version: None,
+        })
+    );
 
-    let gov_instantiate = InstantiateMsg {
-        name: "DAO DAO".to_string(),
-        description: "A DAO that builds DAOs.".to_string(),
-        image_url: None,
-        automatically_add_cw20s: true,
-        automatically_add_cw721s: true,
-        voting_module_instantiate_info: ModuleInstantiateInfo {
-            code_id: voting_id,
-            msg: to_binary(&voting_instantiate).unwrap(),
-            admin: Admin::CoreContract {},
-            label: "voting module".to_string(),
-        },
-        proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
-            code_id: govmod_id,
-            msg: to_binary(&govmod_instantiate).unwrap(),
-            admin: Admin::CoreContract {},
-            label: "governance module".to_string(),
-        }],
-        initial_items: None,
-    };
+    // Text items can not be verified.
+    let err: ContractError = app
+        .execute_contract(
+            gov_addr.clone(),
+            gov_addr.clone(),
+            &ExecuteMsg::SetItem {
+                key: "website".to_string(),
+                item: ItemInfo {
+                    value: ItemValueInfo::Text {
+                        text: "https://daodao.zone".to_string(),
+                    },
+                    description: None,
+                    expected_contract: Some("crates.io:cw-governance".to_string()),
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ExpectedContractNotAddress {});
+
+    // The core contract has cw2 info so it can be used to test
+    // verification.
+    let err: ContractError = app
+        .execute_contract(
+            gov_addr.clone(),
+            gov_addr.clone(),
+            &ExecuteMsg::SetItem {
+                key: "self".to_string(),
+                item: ItemInfo {
+                    value: ItemValueInfo::Address {
+                        addr: gov_addr.to_string(),
+                    },
+                    description: None,
+                    expected_contract: Some("crates.io:cw3-multisig".to_string()),
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::UnexpectedItemContract {
+            expected: "crates.io:cw3-multisig".to_string(),
+            actual: "crates.io:cw-governance".to_string(),
+        }
+    );
+
+    app.execute_contract(
+        gov_addr.clone(),
+        gov_addr.clone(),
+        &ExecuteMsg::SetItem {
+            key: "self".to_string(),
+            item: ItemInfo {
+                value: ItemValueInfo::Address {
+                    addr: gov_addr.to_string(),
+                },
+                description: Some("This DAO.".to_string()),
+                expected_contract: Some("crates.io:cw-governance".to_string()),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    let items = list_items(&mut app, gov_addr.clone(), None, None);
+    assert_eq!(
+        items,
+        vec![
+            ListItemsEntry {
+                key: "website".to_string(),
+                item: website.item.unwrap(),
+            },
+            ListItemsEntry {
+                key: "self".to_string(),
+                item: ItemEntry {
+                    value: ItemValue::Address {
+                        addr: gov_addr.clone()
+                    },
+                    description: Some("This DAO.".to_string()),
+                    expected_contract: Some("crates.io:cw-governance".to_string()),
+                    contract_version: Some(ContractVersion {
+                        contract: "crates.io:cw-governance".to_string(),
+                        version: env!("CARGO_PKG_VERSION").to_string(),
+                    }),
+                },
+            },
+        ]
+    );
+
+    // Addresses without cw2 info can not be verified.
+    let err = app
+        .execute_contract(
+            gov_addr.clone(),
+            gov_addr,
+            &ExecuteMsg::SetItem {
+                key: "wallet".to_string(),
+                item: ItemInfo {
+                    value: ItemValueInfo::Address {
+                        addr: CREATOR_ADDR.to_string(),
+                    },
+                    description: None,
+                    expected_contract: Some("crates.io:cw-governance".to_string()),
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(err.downcast::<ContractError>().is_ok());
+}
+
+#[test]
+fn test_list_items() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(sudo_govmod_contract());
+    let voting_id = app.store_code(cw20_balances_voting());
+    let gov_id = app.store_code(cw_gov_contract());
+    let cw20_id = app.store_code(cw20_contract());
+
+    let govmod_instantiate = cw_proposal_sudo::msg::InstantiateMsg {
+        root: CREATOR_ADDR.to_string(),
+    };
+    let voting_instantiate = cw20_balance_voting::msg::InstantiateMsg {
+        token_info: cw20_balance_voting::msg::TokenInfo::New {
+            code_id: cw20_id,
+            label: "DAO DAO voting".to_string(),
+            name: "DAO DAO".to_string(),
+            symbol: "DAO".to_string(),
+            decimals: 6,
+            initial_balances: vec![cw20::Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::from(2u64),
+            }],
+            marketing: None,
+        },
+    };
+
+    let gov_instantiate = InstantiateMsg {
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs.".to_string(),
+        image_url: None,
+        automatically_add_cw20s: true,
+        automatically_add_cw721s: true,
+        voting_module_instantiate_info: ModuleInstantiateInfo {
+            code_id: voting_id,
+            msg: to_binary(&voting_instantiate).unwrap(),
+            admin: Admin::CoreContract {},
+            label: "voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+            code_id: govmod_id,
+            msg: to_binary(&govmod_instantiate).unwrap(),
+            admin: Admin::CoreContract {},
+            label: "governance module".to_string(),
+        }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
+        initial_items: None,
+    };
 
     let gov_addr = app
         .instantiate_contract(
@@ -976,7 +1286,7 @@ fn test_list_items() {
 
     let first_item = list_items(&mut app, gov_addr.clone(), None, Some(1));
     assert_eq!(first_item.len(), 1);
-    assert_eq!(first_item[0], "fookey".to_string());
+    assert_eq!(first_item[0].key, "fookey".to_string());
 
     let no_items = list_items(&mut app, gov_addr.clone(), None, Some(0));
     assert_eq!(no_items.len(), 0);
@@ -985,7 +1295,7 @@ fn test_list_items() {
     // no limit ought to give us a single item.
     let second_item = list_items(&mut app, gov_addr, Some("foo".to_string()), None);
     assert_eq!(second_item.len(), 1);
-    assert_eq!(second_item[0], "fookey".to_string());
+    assert_eq!(second_item[0].key, "fookey".to_string());
 }
 
 #[test]
@@ -1032,6 +1342,9 @@ fn test_instantiate_with_items() {
             admin: Admin::CoreContract {},
             label: "governance module".to_string(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: Some(vec![
             InitialItem {
                 name: "item0".to_string(),
@@ -1080,16 +1393,16 @@ fn test_instantiate_with_items() {
     assert_eq!(items.len(), 3);
 
     // Descending order, so item2 is first.
-    assert_eq!(items[0], "item2".to_string());
+    assert_eq!(items[0].key, "item2".to_string());
     let get_item2 = get_item(&mut app, gov_addr.clone(), "item2".to_string());
     assert_eq!(
         get_item2,
         GetItemResponse {
-            item: Some(Addr::unchecked("item2_addr")),
+            item: Some(ItemEntry::from_addr(Addr::unchecked("item2_addr"))),
         }
     );
 
-    assert_eq!(items[1], "item1".to_string());
+    assert_eq!(items[1].key, "item1".to_string());
     get_item(&mut app, gov_addr, "item1".to_string())
         .item
         .unwrap();
@@ -1316,6 +1629,93 @@ fn test_cw721_receive() {
         .unwrap();
     assert_eq!(cw721_list, vec![cw721_addr.clone()]);
 
+    let holdings: cw721::TokensResponse = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::Cw721Holdings {
+                collection: cw721_addr.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(holdings.tokens, vec!["ekez".to_string()]);
+
+    let received: Vec<String> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::Cw721ReceivedTokens {
+                collection: cw721_addr.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(received, vec!["ekez".to_string()]);
+
+    // Pruning keeps NFTs the DAO still owns.
+    let prune = ExecuteMsg::PruneCw721Tokens {
+        collection: cw721_addr.to_string(),
+        token_ids: vec!["ekez".to_string()],
+    };
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), gov_addr.clone(), &prune, &[])
+        .unwrap();
+    let received: Vec<String> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::Cw721ReceivedTokens {
+                collection: cw721_addr.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(received, vec!["ekez".to_string()]);
+
+    // Holdings come from the collection so they reflect NFTs the DAO
+    // has since sent away.
+    app.execute_contract(
+        gov_addr.clone(),
+        cw721_addr.clone(),
+        &cw721_base::msg::ExecuteMsg::<Option<Empty>>::TransferNft {
+            recipient: CREATOR_ADDR.to_string(),
+            token_id: "ekez".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let holdings: cw721::TokensResponse = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::Cw721Holdings {
+                collection: cw721_addr.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(holdings.tokens.is_empty());
+
+    // Once pruned, NFTs the DAO has sent away are forgotten.
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), gov_addr.clone(), &prune, &[])
+        .unwrap();
+    let received: Vec<String> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::Cw721ReceivedTokens {
+                collection: cw721_addr.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(received.is_empty());
+
     // OK to add already added. Remove happens after add.
     app.execute_contract(
         Addr::unchecked(gov_addr.clone()),
@@ -1331,7 +1731,7 @@ fn test_cw721_receive() {
     let cw20_list: Vec<Addr> = app
         .wrap()
         .query_wasm_smart(
-            gov_addr,
+            gov_addr.clone(),
             &QueryMsg::Cw721TokenList {
                 start_at: None,
                 limit: None,
@@ -1434,7 +1834,7 @@ fn test_pause() {
 
     let start_height = app.block_info().height;
 
-    let proposal_modules: Vec<Addr> = app
+    let proposal_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             core_addr.clone(),
@@ -1446,7 +1846,7 @@ fn test_pause() {
         .unwrap();
 
     assert_eq!(proposal_modules.len(), 1);
-    let proposal_module = proposal_modules.into_iter().next().unwrap();
+    let proposal_module = proposal_modules.into_iter().next().unwrap().address;
 
     let paused: PauseInfoResponse = app
         .wrap()
@@ -1488,6 +1888,7 @@ fn test_pause() {
             core_addr.clone(),
             &ExecuteMsg::Pause {
                 duration: Duration::Height(10),
+                reason: None,
             },
             &[],
         )
@@ -1507,6 +1908,7 @@ fn test_pause() {
                 contract_addr: core_addr.to_string(),
                 msg: to_binary(&ExecuteMsg::Pause {
                     duration: Duration::Height(10),
+                    reason: None,
                 })
                 .unwrap(),
                 funds: vec![],
@@ -1524,7 +1926,9 @@ fn test_pause() {
     assert_eq!(
         paused,
         PauseInfoResponse::Paused {
-            expiration: Expiration::AtHeight(start_height + 10)
+            expiration: Expiration::AtHeight(start_height + 10),
+            paused_by: core_addr.clone(),
+            reason: None,
         }
     );
     let all_state: DumpStateResponse = app
@@ -1534,7 +1938,9 @@ fn test_pause() {
     assert_eq!(
         all_state.pause_info,
         PauseInfoResponse::Paused {
-            expiration: Expiration::AtHeight(start_height + 10)
+            expiration: Expiration::AtHeight(start_height + 10),
+            paused_by: core_addr.clone(),
+            reason: None,
         }
     );
 
@@ -1568,6 +1974,7 @@ fn test_pause() {
                     contract_addr: core_addr.to_string(),
                     msg: to_binary(&ExecuteMsg::Pause {
                         duration: Duration::Height(10),
+                        reason: None,
                     })
                     .unwrap(),
                     funds: vec![],
@@ -1582,7 +1989,7 @@ fn test_pause() {
 
     assert!(matches!(err, ContractError::Paused { .. }));
 
-    app.update_block(|mut block| block.height += 9);
+    app.update_block(|block| block.height += 9);
 
     // Still not unpaused.
     let err: ContractError = app
@@ -1594,6 +2001,7 @@ fn test_pause() {
                     contract_addr: core_addr.to_string(),
                     msg: to_binary(&ExecuteMsg::Pause {
                         duration: Duration::Height(10),
+                        reason: None,
                     })
                     .unwrap(),
                     funds: vec![],
@@ -1608,7 +2016,7 @@ fn test_pause() {
 
     assert!(matches!(err, ContractError::Paused { .. }));
 
-    app.update_block(|mut block| block.height += 1);
+    app.update_block(|block| block.height += 1);
 
     let paused: PauseInfoResponse = app
         .wrap()
@@ -1630,6 +2038,7 @@ fn test_pause() {
                 contract_addr: core_addr.to_string(),
                 msg: to_binary(&ExecuteMsg::Pause {
                     duration: Duration::Height(10),
+                    reason: None,
                 })
                 .unwrap(),
                 funds: vec![],
@@ -1647,17 +2056,1092 @@ fn test_pause() {
     assert_eq!(
         paused,
         PauseInfoResponse::Paused {
-            expiration: Expiration::AtHeight(start_height + 20)
+            expiration: Expiration::AtHeight(start_height + 20),
+            paused_by: core_addr.clone(),
+            reason: None,
         }
     );
     let all_state: DumpStateResponse = app
         .wrap()
-        .query_wasm_smart(core_addr, &QueryMsg::DumpState {})
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
         .unwrap();
     assert_eq!(
         all_state.pause_info,
         PauseInfoResponse::Paused {
-            expiration: Expiration::AtHeight(start_height + 20)
+            expiration: Expiration::AtHeight(start_height + 20),
+            paused_by: core_addr.clone(),
+            reason: None,
         }
     );
 }
+
+#[test]
+fn test_native_balances() {
+    let (gov_addr, mut app) = do_standard_instantiate(true);
+
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &gov_addr,
+                vec![coin(10, "ujuno"), coin(20, "uatom")],
+            )
+            .unwrap();
+    });
+
+    // Nothing is tracked until the DAO registers the denoms.
+    let native_balances: Vec<Coin> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::NativeBalances {
+                start_at: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(native_balances, vec![]);
+
+    test_unauthorized(
+        &mut app,
+        gov_addr.clone(),
+        ExecuteMsg::UpdateNativeList {
+            to_add: vec!["ujuno".to_string()],
+            to_remove: vec![],
+        },
+    );
+
+    app.execute_contract(
+        gov_addr.clone(),
+        gov_addr.clone(),
+        &ExecuteMsg::UpdateNativeList {
+            to_add: vec![
+                "ujuno".to_string(),
+                "uatom".to_string(),
+                "uosmo".to_string(),
+            ],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let native_balances: Vec<Coin> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::NativeBalances {
+                start_at: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        native_balances,
+        vec![coin(0, "uosmo"), coin(10, "ujuno"), coin(20, "uatom")]
+    );
+
+    let native_balances: Vec<Coin> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::NativeBalances {
+                start_at: Some("ujuno".to_string()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(native_balances, vec![coin(0, "uosmo"), coin(10, "ujuno")]);
+
+    app.execute_contract(
+        gov_addr.clone(),
+        gov_addr.clone(),
+        &ExecuteMsg::UpdateNativeList {
+            to_add: vec![],
+            to_remove: vec!["uosmo".to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let all_state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(gov_addr, &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(
+        all_state.native_balances,
+        vec![coin(10, "ujuno"), coin(20, "uatom")]
+    );
+}
+
+#[test]
+fn test_pause_guardian() {
+    let (core_addr, mut app) = do_standard_instantiate(false);
+    let guardian = Addr::unchecked("guardian");
+
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ProposalModules {
+                start_at: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = proposal_modules.into_iter().next().unwrap().address;
+
+    // No guardian is configured so the guardian may not pause.
+    let err: ContractError = app
+        .execute_contract(
+            guardian.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::Pause {
+                duration: Duration::Height(5),
+                reason: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::UpdatePauseGuardian {
+            guardian: Some(PauseGuardianInfo {
+                addr: CREATOR_ADDR.to_string(),
+                max_pause_duration: Duration::Height(10),
+            }),
+        },
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdatePauseGuardian {
+            guardian: Some(PauseGuardianInfo {
+                addr: guardian.to_string(),
+                max_pause_duration: Duration::Height(10),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let pause_guardian: Option<PauseGuardian> = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseGuardian {})
+        .unwrap();
+    assert_eq!(
+        pause_guardian,
+        Some(PauseGuardian {
+            addr: guardian.clone(),
+            max_pause_duration: Duration::Height(10),
+        })
+    );
+
+    // The guardian may not exceed its maximum pause duration or use
+    // different units.
+    for duration in [Duration::Height(11), Duration::Time(5)] {
+        let err: ContractError = app
+            .execute_contract(
+                guardian.clone(),
+                core_addr.clone(),
+                &ExecuteMsg::Pause {
+                    duration,
+                    reason: None,
+                },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::PauseDurationTooLong {});
+    }
+
+    let start_height = app.block_info().height;
+    app.execute_contract(
+        guardian.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::Pause {
+            duration: Duration::Height(10),
+            reason: Some("suspicious proposal".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let paused: PauseInfoResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseInfo {})
+        .unwrap();
+    assert_eq!(
+        paused,
+        PauseInfoResponse::Paused {
+            expiration: Expiration::AtHeight(start_height + 10),
+            paused_by: guardian.clone(),
+            reason: Some("suspicious proposal".to_string()),
+        }
+    );
+
+    // A proposal hook that does more than unpause is rejected.
+    let unpause = WasmMsg::Execute {
+        contract_addr: core_addr.to_string(),
+        msg: to_binary(&ExecuteMsg::Unpause {}).unwrap(),
+        funds: vec![],
+    };
+    let pause = WasmMsg::Execute {
+        contract_addr: core_addr.to_string(),
+        msg: to_binary(&ExecuteMsg::Pause {
+            duration: Duration::Height(10),
+            reason: None,
+        })
+        .unwrap(),
+        funds: vec![],
+    };
+    let err: ContractError = app
+        .execute_contract(
+            proposal_module.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteProposalHook {
+                msgs: vec![unpause.clone().into(), pause.into()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Paused {});
+
+    // The DAO may unpause itself via a proposal.
+    app.execute_contract(
+        proposal_module,
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![unpause.into()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let paused: PauseInfoResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseInfo {})
+        .unwrap();
+    assert_eq!(paused, PauseInfoResponse::Unpaused {});
+
+    // The guardian may not pause again until its maximum pause
+    // duration has passed since the end of its last pause.
+    let err: ContractError = app
+        .execute_contract(
+            guardian.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::Pause {
+                duration: Duration::Height(10),
+                reason: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::PauseCooldown {
+            until: Expiration::AtHeight(start_height + 20)
+        }
+    );
+    app.update_block(|block| block.height = start_height + 20);
+
+    // The guardian may unpause and nobody else.
+    app.execute_contract(
+        guardian.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::Pause {
+            duration: Duration::Height(10),
+            reason: None,
+        },
+        &[],
+    )
+    .unwrap();
+    test_unauthorized(&mut app, core_addr.clone(), ExecuteMsg::Unpause {});
+    app.execute_contract(guardian, core_addr.clone(), &ExecuteMsg::Unpause {}, &[])
+        .unwrap();
+
+    let paused: PauseInfoResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &QueryMsg::PauseInfo {})
+        .unwrap();
+    assert_eq!(paused, PauseInfoResponse::Unpaused {});
+}
+
+#[test]
+fn test_remove_pause_guardian_while_paused() {
+    let (core_addr, mut app) = do_standard_instantiate(false);
+    let guardian = Addr::unchecked("guardian");
+
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ProposalModules {
+                start_at: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = proposal_modules.into_iter().next().unwrap().address;
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdatePauseGuardian {
+            guardian: Some(PauseGuardianInfo {
+                addr: guardian.to_string(),
+                max_pause_duration: Duration::Height(10),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        guardian.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::Pause {
+            duration: Duration::Height(10),
+            reason: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // A proposal may remove the guardian and unpause the DAO while
+    // it is paused.
+    let remove_guardian = WasmMsg::Execute {
+        contract_addr: core_addr.to_string(),
+        msg: to_binary(&ExecuteMsg::UpdatePauseGuardian { guardian: None }).unwrap(),
+        funds: vec![],
+    };
+    let unpause = WasmMsg::Execute {
+        contract_addr: core_addr.to_string(),
+        msg: to_binary(&ExecuteMsg::Unpause {}).unwrap(),
+        funds: vec![],
+    };
+    app.execute_contract(
+        proposal_module,
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![remove_guardian.into(), unpause.into()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let pause_guardian: Option<PauseGuardian> = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseGuardian {})
+        .unwrap();
+    assert_eq!(pause_guardian, None);
+    let paused: PauseInfoResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseInfo {})
+        .unwrap();
+    assert_eq!(paused, PauseInfoResponse::Unpaused {});
+
+    // The former guardian may no longer pause.
+    let err: ContractError = app
+        .execute_contract(
+            guardian,
+            core_addr,
+            &ExecuteMsg::Pause {
+                duration: Duration::Height(10),
+                reason: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_sub_daos() {
+    let (core_addr, mut app) = do_standard_instantiate(false);
+    let parent = Addr::unchecked("parent");
+    let sub_dao = Addr::unchecked("sub_dao");
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::RegisterSubDao {
+            addr: sub_dao.to_string(),
+            charter: None,
+        },
+    );
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::UpdateParentDao {
+            parent_dao: Some(CREATOR_ADDR.to_string()),
+        },
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::RegisterSubDao {
+            addr: sub_dao.to_string(),
+            charter: Some("treasury management".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::RegisterSubDao {
+            addr: "another_sub_dao".to_string(),
+            charter: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let sub_daos: Vec<SubDao> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ListSubDaos {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        sub_daos,
+        vec![
+            SubDao {
+                addr: Addr::unchecked("another_sub_dao"),
+                charter: None,
+            },
+            SubDao {
+                addr: sub_dao.clone(),
+                charter: Some("treasury management".to_string()),
+            },
+        ]
+    );
+
+    let sub_daos: Vec<SubDao> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ListSubDaos {
+                start_after: Some("another_sub_dao".to_string()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(sub_daos.len(), 1);
+    assert_eq!(sub_daos[0].addr, sub_dao);
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UnregisterSubDao {
+            addr: sub_dao.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UnregisterSubDao {
+                addr: sub_dao.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::SubDaoNotRegistered {});
+
+    // Only the parent DAO may execute messages on behalf of the DAO.
+    let update_config = ExecuteMsg::ExecuteParentDaoMsgs {
+        msgs: vec![WasmMsg::Execute {
+            contract_addr: core_addr.to_string(),
+            msg: to_binary(&ExecuteMsg::UpdateConfig {
+                config: Config {
+                    name: "Updated by parent".to_string(),
+                    description: "A subDAO.".to_string(),
+                    image_url: None,
+                    automatically_add_cw20s: false,
+                    automatically_add_cw721s: false,
+                },
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()],
+    };
+    test_unauthorized(&mut app, core_addr.clone(), update_config.clone());
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateParentDao {
+            parent_dao: Some(parent.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    let parent_dao: Option<Addr> = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::ParentDao {})
+        .unwrap();
+    assert_eq!(parent_dao, Some(parent.clone()));
+
+    test_unauthorized(&mut app, core_addr.clone(), update_config.clone());
+    app.execute_contract(parent.clone(), core_addr.clone(), &update_config, &[])
+        .unwrap();
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.name, "Updated by parent");
+
+    // The parent may remove itself.
+    app.execute_contract(
+        parent.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateParentDao { parent_dao: None },
+        &[],
+    )
+    .unwrap();
+    let state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(state.parent_dao, None);
+
+    let err: ContractError = app
+        .execute_contract(parent, core_addr, &update_config, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_dao_admin() {
+    let (core_addr, mut app) = do_standard_instantiate(false);
+    let admin = Addr::unchecked("admin");
+    let nominee = Addr::unchecked("nominee");
+    let expiration = Expiration::AtHeight(app.block_info().height + 10);
+    let update_config = ExecuteMsg::UpdateConfig {
+        config: Config {
+            name: "Updated by admin".to_string(),
+            description: "A DAO being bootstrapped.".to_string(),
+            image_url: None,
+            automatically_add_cw20s: true,
+            automatically_add_cw721s: true,
+        },
+    };
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::UpdateAdmin {
+            admin: Some(DaoAdminInfo {
+                addr: CREATOR_ADDR.to_string(),
+                expiration,
+            }),
+        },
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpdateAdmin {
+                admin: Some(DaoAdminInfo {
+                    addr: admin.to_string(),
+                    expiration: Expiration::AtHeight(app.block_info().height),
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AdminExpirationPassed {});
+
+    // The admin may not be permanent.
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpdateAdmin {
+                admin: Some(DaoAdminInfo {
+                    addr: admin.to_string(),
+                    expiration: Expiration::Never {},
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AdminExpirationNever {});
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateAdmin {
+            admin: Some(DaoAdminInfo {
+                addr: admin.to_string(),
+                expiration,
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The admin may update the config and items directly.
+    app.execute_contract(admin.clone(), core_addr.clone(), &update_config, &[])
+        .unwrap();
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.name, "Updated by admin");
+
+    // Only the admin may nominate a new admin.
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::NominateAdmin {
+            admin: Some(CREATOR_ADDR.to_string()),
+        },
+    );
+    let err: ContractError = app
+        .execute_contract(
+            nominee.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::AcceptAdminNomination {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoAdminNomination {});
+
+    app.execute_contract(
+        admin.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::NominateAdmin {
+            admin: Some(nominee.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    let nomination: Option<Addr> = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::AdminNomination {})
+        .unwrap();
+    assert_eq!(nomination, Some(nominee.clone()));
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::AcceptAdminNomination {},
+    );
+    app.execute_contract(
+        nominee.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::AcceptAdminNomination {},
+        &[],
+    )
+    .unwrap();
+
+    let state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(
+        state.admin,
+        Some(DaoAdmin {
+            addr: nominee.clone(),
+            expiration,
+        })
+    );
+    assert_eq!(state.admin_nomination, None);
+
+    // The previous admin has lost its privileges.
+    let err: ContractError = app
+        .execute_contract(admin, core_addr.clone(), &update_config, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        nominee.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::SetItem {
+            key: "website".to_string(),
+            item: ItemInfo {
+                value: ItemValueInfo::Text {
+                    text: "https://daodao.zone".to_string(),
+                },
+                description: None,
+                expected_contract: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Once expired the admin may no longer act.
+    app.update_block(|block| block.height += 10);
+    let err: ContractError = app
+        .execute_contract(nominee.clone(), core_addr.clone(), &update_config, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let current: Option<DaoAdmin> = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::Admin {})
+        .unwrap();
+    assert_eq!(current, None);
+
+    // A renounced admin may no longer act.
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateAdmin {
+            admin: Some(DaoAdminInfo {
+                addr: nominee.to_string(),
+                expiration: Expiration::AtHeight(app.block_info().height + 100),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        nominee.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::RenounceAdmin {},
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(nominee, core_addr.clone(), &update_config, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(state.admin, None);
+}
+
+#[test]
+fn test_allowances() {
+    let (core_addr, mut app) = do_standard_instantiate(false);
+    let ops = Addr::unchecked("ops");
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &core_addr, vec![coin(100, "ujuno")])
+            .unwrap();
+    });
+
+    let ujuno = AllowanceAssetInfo::Native {
+        denom: "ujuno".to_string(),
+    };
+    let grant = ExecuteMsg::GrantAllowance {
+        grantee: ops.to_string(),
+        allowance: AllowanceInfo {
+            asset: ujuno.clone(),
+            amount: Uint128::new(30),
+            refill_period: Some(Duration::Height(10)),
+            expiration: Expiration::AtHeight(app.block_info().height + 25),
+        },
+    };
+    test_unauthorized(&mut app, core_addr.clone(), grant.clone());
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::GrantAllowance {
+                grantee: ops.to_string(),
+                allowance: AllowanceInfo {
+                    asset: ujuno.clone(),
+                    amount: Uint128::new(30),
+                    refill_period: Some(Duration::Height(0)),
+                    expiration: Expiration::Never {},
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroRefillPeriod {});
+
+    let grant_height = app.block_info().height;
+    app.execute_contract(core_addr.clone(), core_addr.clone(), &grant, &[])
+        .unwrap();
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::GrantAllowance {
+            grantee: ops.to_string(),
+            allowance: AllowanceInfo {
+                asset: AllowanceAssetInfo::Cw20 {
+                    addr: "token".to_string(),
+                },
+                amount: Uint128::new(5),
+                refill_period: None,
+                expiration: Expiration::Never {},
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    let spend = |amount: u128| ExecuteMsg::SpendAllowance {
+        asset: ujuno.clone(),
+        amount: Uint128::new(amount),
+        recipient: "vendor".to_string(),
+    };
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            core_addr.clone(),
+            &spend(1),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoAllowance {});
+
+    app.execute_contract(ops.clone(), core_addr.clone(), &spend(20), &[])
+        .unwrap();
+    let err: ContractError = app
+        .execute_contract(ops.clone(), core_addr.clone(), &spend(20), &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::AllowanceExceeded {
+            remaining: Uint128::new(10)
+        }
+    );
+
+    // The allowance refills once the period ends. The next period
+    // ends a whole number of periods after the allowance was
+    // granted.
+    app.update_block(|block| block.height += 13);
+    let allowance: Option<Allowance> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::Allowance {
+                grantee: ops.to_string(),
+                asset: ujuno.clone(),
+            },
+        )
+        .unwrap();
+    let allowance = allowance.unwrap();
+    assert_eq!(allowance.remaining(), Uint128::new(30));
+    assert_eq!(
+        allowance.next_refill,
+        Some(Expiration::AtHeight(grant_height + 20))
+    );
+    app.execute_contract(ops.clone(), core_addr.clone(), &spend(30), &[])
+        .unwrap();
+
+    let balance = app.wrap().query_balance("vendor", "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(50));
+
+    let allowances: Vec<Allowance> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ListAllowances {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(allowances.len(), 2);
+    let allowances: Vec<Allowance> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ListAllowances {
+                start_after: Some(AllowanceKey {
+                    grantee: ops.to_string(),
+                    asset: AllowanceAssetInfo::Cw20 {
+                        addr: "token".to_string(),
+                    },
+                }),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(allowances.len(), 1);
+    assert_eq!(
+        allowances[0].asset,
+        AllowanceAsset::Native {
+            denom: "ujuno".to_string()
+        }
+    );
+
+    // Expired allowances may not be spent.
+    app.update_block(|block| block.height += 15);
+    let err: ContractError = app
+        .execute_contract(ops.clone(), core_addr.clone(), &spend(1), &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AllowanceExpired {});
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::RevokeAllowance {
+            grantee: ops.to_string(),
+            asset: ujuno.clone(),
+        },
+    );
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::RevokeAllowance {
+            grantee: ops.to_string(),
+            asset: ujuno.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    let allowance: Option<Allowance> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &QueryMsg::Allowance {
+                grantee: ops.to_string(),
+                asset: ujuno,
+            },
+        )
+        .unwrap();
+    assert_eq!(allowance, None);
+}
+
+#[test]
+fn test_module_interface_checks() {
+    let (gov_addr, mut app) = do_standard_instantiate(true);
+    let cw20_id = app.store_code(cw20_contract());
+    let cw20_instantiate = cw20_base::msg::InstantiateMsg {
+        name: "DAO".to_string(),
+        symbol: "DAO".to_string(),
+        decimals: 6,
+        initial_balances: vec![],
+        mint: None,
+        marketing: None,
+    };
+
+    // Versions of the installed modules are recorded.
+    let state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    let version: Option<ContractVersion> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::ModuleVersion {
+                address: state.voting_module.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        version.unwrap().contract,
+        "crates.io:cw20-balance-voting".to_string()
+    );
+    let version: Option<ContractVersion> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::ModuleVersion {
+                address: state.governance_modules[0].address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        version.unwrap().contract,
+        "crates.io:cw-govmod-sudo".to_string()
+    );
+
+    // A cw20 token is neither a proposal module nor a voting module.
+    let err: ContractError = app
+        .execute_contract(
+            gov_addr.clone(),
+            gov_addr.clone(),
+            &ExecuteMsg::UpdateProposalModules {
+                to_add: vec![ModuleInstantiateInfo {
+                    code_id: cw20_id,
+                    msg: to_binary(&cw20_instantiate).unwrap(),
+                    admin: Admin::CoreContract {},
+                    label: "not a proposal module".to_string(),
+                }],
+                to_disable: vec![],
+                to_remove: vec![],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidProposalModule { .. }));
+
+    let err: ContractError = app
+        .execute_contract(
+            gov_addr.clone(),
+            gov_addr.clone(),
+            &ExecuteMsg::UpdateVotingModule {
+                module: ModuleInstantiateInfo {
+                    code_id: cw20_id,
+                    msg: to_binary(&cw20_instantiate).unwrap(),
+                    admin: Admin::CoreContract {},
+                    label: "not a voting module".to_string(),
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidVotingModule { .. }));
+
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(gov_addr, &QueryMsg::VotingModule {})
+        .unwrap();
+    assert_eq!(voting_module, state.voting_module);
+}
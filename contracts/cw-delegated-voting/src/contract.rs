@@ -0,0 +1,392 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
+    StdResult, Storage, Uint128,
+};
+use cw2::set_contract_version;
+use cw_core_interface::voting::{
+    self, IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::msg::{
+    Delegation, DelegationsResponse, ExecuteMsg, InstantiateMsg, PendingDelegation,
+    PendingDelegationsResponse, QueryMsg,
+};
+use crate::state::{
+    DAO, DELEGATED, DELEGATIONS, DELEGATORS, MAX_DELEGATORS, PENDING_DELEGATIONS, VOTING_MODULE,
+};
+
+const CONTRACT_NAME: &str = "crates.io:cw-delegated-voting";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let voting_module = deps.api.addr_validate(&msg.voting_module)?;
+    // Make sure the module is a voting module. If it is not every
+    // voting power query would fail.
+    let _: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        voting_module.clone(),
+        &voting::Query::TotalPowerAtHeight { height: None },
+    )?;
+    VOTING_MODULE.save(deps.storage, &voting_module)?;
+    DAO.save(deps.storage, &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", info.sender)
+        .add_attribute("voting_module", voting_module))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Delegate { delegate, percent } => {
+            execute_delegate(deps, env, info, delegate, percent)
+        }
+        ExecuteMsg::Undelegate { delegate } => execute_undelegate(deps, env, info, delegate),
+        ExecuteMsg::AcceptDelegation { delegator } => {
+            execute_accept_delegation(deps, env, info, delegator)
+        }
+        ExecuteMsg::RemoveDelegator { delegator } => {
+            execute_remove_delegator(deps, env, info, delegator)
+        }
+    }
+}
+
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+    percent: Decimal,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if delegate == info.sender {
+        return Err(ContractError::SelfDelegation {});
+    }
+    if percent.is_zero() || percent > Decimal::one() {
+        return Err(ContractError::InvalidPercent {});
+    }
+
+    let old = DELEGATIONS.may_load(deps.storage, (&info.sender, &delegate))?;
+    let delegated = DELEGATED
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default()
+        - old.unwrap_or_default()
+        + percent;
+    if delegated > Decimal::one() {
+        return Err(ContractError::OverDelegated {});
+    }
+
+    // Delegations only need to be accepted once so that addresses
+    // can not be made to carry delegators they did not agree to.
+    let status = if old.is_some() {
+        DELEGATIONS.save(
+            deps.storage,
+            (&info.sender, &delegate),
+            &percent,
+            env.block.height,
+        )?;
+        DELEGATED.save(deps.storage, &info.sender, &delegated, env.block.height)?;
+        "accepted"
+    } else {
+        PENDING_DELEGATIONS.save(deps.storage, (&delegate, &info.sender), &percent)?;
+        "pending"
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "delegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("delegate", delegate)
+        .add_attribute("percent", percent.to_string())
+        .add_attribute("status", status))
+}
+
+pub fn execute_accept_delegation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegator: String,
+) -> Result<Response, ContractError> {
+    let delegator = deps.api.addr_validate(&delegator)?;
+    let percent = PENDING_DELEGATIONS
+        .may_load(deps.storage, (&info.sender, &delegator))?
+        .ok_or_else(|| ContractError::DelegatorNotFound {
+            delegator: delegator.to_string(),
+        })?;
+
+    let mut delegators = DELEGATORS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if delegators.len() >= MAX_DELEGATORS {
+        return Err(ContractError::TooManyDelegators {
+            max: MAX_DELEGATORS,
+        });
+    }
+    // The delegator may have delegated elsewhere since making the
+    // offer.
+    let delegated = DELEGATED
+        .may_load(deps.storage, &delegator)?
+        .unwrap_or_default()
+        + percent;
+    if delegated > Decimal::one() {
+        return Err(ContractError::OverDelegated {});
+    }
+
+    PENDING_DELEGATIONS.remove(deps.storage, (&info.sender, &delegator));
+    DELEGATIONS.save(
+        deps.storage,
+        (&delegator, &info.sender),
+        &percent,
+        env.block.height,
+    )?;
+    DELEGATED.save(deps.storage, &delegator, &delegated, env.block.height)?;
+    delegators.push(delegator.clone());
+    DELEGATORS.save(deps.storage, &info.sender, &delegators, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_delegation")
+        .add_attribute("delegator", delegator)
+        .add_attribute("delegate", info.sender)
+        .add_attribute("percent", percent.to_string()))
+}
+
+/// Removes `delegator`'s pending or accepted delegation to
+/// `delegate`, returning false if there is neither.
+fn remove_delegation(
+    storage: &mut dyn Storage,
+    height: u64,
+    delegator: &Addr,
+    delegate: &Addr,
+) -> StdResult<bool> {
+    if PENDING_DELEGATIONS.has(storage, (delegate, delegator)) {
+        PENDING_DELEGATIONS.remove(storage, (delegate, delegator));
+        return Ok(true);
+    }
+    let percent = match DELEGATIONS.may_load(storage, (delegator, delegate))? {
+        Some(percent) => percent,
+        None => return Ok(false),
+    };
+
+    DELEGATIONS.remove(storage, (delegator, delegate), height)?;
+    DELEGATED.update(
+        storage,
+        delegator,
+        height,
+        |delegated| -> StdResult<Decimal> { Ok(delegated.unwrap_or_default() - percent) },
+    )?;
+    DELEGATORS.update(storage, delegate, height, |delegators| -> StdResult<_> {
+        let mut delegators = delegators.unwrap_or_default();
+        delegators.retain(|address| address != delegator);
+        Ok(delegators)
+    })?;
+    Ok(true)
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if !remove_delegation(deps.storage, env.block.height, &info.sender, &delegate)? {
+        return Err(ContractError::DelegationNotFound {
+            delegate: delegate.to_string(),
+        });
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "undelegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("delegate", delegate))
+}
+
+pub fn execute_remove_delegator(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegator: String,
+) -> Result<Response, ContractError> {
+    let delegator = deps.api.addr_validate(&delegator)?;
+    if !remove_delegation(deps.storage, env.block.height, &delegator, &info.sender)? {
+        return Err(ContractError::DelegatorNotFound {
+            delegator: delegator.to_string(),
+        });
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_delegator")
+        .add_attribute("delegator", delegator)
+        .add_attribute("delegate", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            query_voting_power_at_height(deps, env, address, height)
+        }
+        QueryMsg::TotalPowerAtHeight { height } => query_total_power_at_height(deps, env, height),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::IsActive {} => query_is_active(deps),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::VotingModule {} => to_binary(&VOTING_MODULE.load(deps.storage)?),
+        QueryMsg::Delegations {
+            delegator,
+            start_after,
+            limit,
+        } => query_delegations(deps, delegator, start_after, limit),
+        QueryMsg::PendingDelegations {
+            delegate,
+            start_after,
+            limit,
+        } => query_pending_delegations(deps, delegate, start_after, limit),
+    }
+}
+
+fn query_underlying_power(deps: Deps, address: &Addr, height: u64) -> StdResult<Uint128> {
+    let res: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        VOTING_MODULE.load(deps.storage)?,
+        &voting::Query::VotingPowerAtHeight {
+            address: address.to_string(),
+            height: Some(height),
+        },
+    )?;
+    Ok(res.power)
+}
+
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+
+    // The power the address has not delegated away.
+    let own = query_underlying_power(deps, &address, height)?;
+    let delegated = DELEGATED
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+    let mut power = own - own * delegated;
+
+    // Only an address' own power may be delegated, so power
+    // delegated to a delegate is not passed on by them.
+    for delegator in DELEGATORS
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default()
+    {
+        if let Some(percent) =
+            DELEGATIONS.may_load_at_height(deps.storage, (&delegator, &address), height)?
+        {
+            let delegated = query_underlying_power(deps, &delegator, height)? * percent;
+            power = power.checked_add(delegated).map_err(StdError::overflow)?;
+        }
+    }
+
+    to_binary(&VotingPowerAtHeightResponse { power, height })
+}
+
+pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    // Delegation moves voting power between addresses without
+    // changing the total.
+    let height = height.unwrap_or(env.block.height);
+    let res: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        VOTING_MODULE.load(deps.storage)?,
+        &voting::Query::TotalPowerAtHeight {
+            height: Some(height),
+        },
+    )?;
+    to_binary(&res)
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&voting::InfoResponse { info })
+}
+
+pub fn query_is_active(deps: Deps) -> StdResult<Binary> {
+    // Voting modules are not required to implement this
+    // query. Lacking an implementation they are active by default.
+    let active = deps
+        .querier
+        .query_wasm_smart(
+            VOTING_MODULE.load(deps.storage)?,
+            &voting::Query::IsActive {},
+        )
+        .unwrap_or(IsActiveResponse { active: true })
+        .active;
+    to_binary(&IsActiveResponse { active })
+}
+
+pub fn query_delegations(
+    deps: Deps,
+    delegator: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let delegator = deps.api.addr_validate(&delegator)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let min = start_after.as_ref().map(Bound::exclusive);
+
+    let delegations = DELEGATIONS
+        .prefix(&delegator)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (delegate, percent) = item?;
+            Ok(Delegation { delegate, percent })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&DelegationsResponse { delegations })
+}
+
+pub fn query_pending_delegations(
+    deps: Deps,
+    delegate: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let min = start_after.as_ref().map(Bound::exclusive);
+
+    let delegations = PENDING_DELEGATIONS
+        .prefix(&delegate)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (delegator, percent) = item?;
+            Ok(PendingDelegation { delegator, percent })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&PendingDelegationsResponse { delegations })
+}
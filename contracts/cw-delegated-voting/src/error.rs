@@ -0,0 +1,26 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Delegation percentages must be greater than zero and at most one")]
+    InvalidPercent {},
+
+    #[error("An address can not delegate to itself")]
+    SelfDelegation {},
+
+    #[error("Can not delegate more than all of an address' voting power")]
+    OverDelegated {},
+
+    #[error("No delegation to ({delegate})")]
+    DelegationNotFound { delegate: String },
+
+    #[error("No delegation from ({delegator})")]
+    DelegatorNotFound { delegator: String },
+
+    #[error("A delegate may accept at most ({max}) delegators")]
+    TooManyDelegators { max: usize },
+}
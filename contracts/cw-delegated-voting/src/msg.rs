@@ -0,0 +1,74 @@
+use cosmwasm_std::{Addr, Decimal};
+use cw_core_macros::{active_query, voting_query};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The address of an existing voting module whose voting power
+    /// may be delegated.
+    pub voting_module: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Offers `percent` of the sender's voting power in the
+    /// underlying module to `delegate`. The delegation takes effect
+    /// once `delegate` accepts it. If `delegate` has already accepted
+    /// a delegation from the sender its percentage is replaced
+    /// immediately. The sender may delegate at most all of their
+    /// voting power across all delegates.
+    Delegate { delegate: String, percent: Decimal },
+    /// Revokes the sender's pending or accepted delegation to
+    /// `delegate`.
+    Undelegate { delegate: String },
+    /// Accepts `delegator`'s pending delegation to the sender.
+    AcceptDelegation { delegator: String },
+    /// Declines or revokes `delegator`'s pending or accepted
+    /// delegation to the sender.
+    RemoveDelegator { delegator: String },
+}
+
+#[voting_query]
+#[active_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Dao {},
+    VotingModule {},
+    /// Lists the current delegations made by `delegator`.
+    Delegations {
+        delegator: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the delegations to `delegate` awaiting its acceptance.
+    PendingDelegations {
+        delegate: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Delegation {
+    pub delegate: Addr,
+    pub percent: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelegationsResponse {
+    pub delegations: Vec<Delegation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingDelegation {
+    pub delegator: Addr,
+    pub percent: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingDelegationsResponse {
+    pub delegations: Vec<PendingDelegation>,
+}
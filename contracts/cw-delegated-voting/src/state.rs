@@ -0,0 +1,40 @@
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
+
+pub const DAO: Item<Addr> = Item::new("dao");
+pub const VOTING_MODULE: Item<Addr> = Item::new("voting_module");
+
+/// The percentage of a delegator's voting power delegated to a
+/// delegate, keyed by (delegator, delegate).
+pub const DELEGATIONS: SnapshotMap<(&Addr, &Addr), Decimal> = SnapshotMap::new(
+    "delegations",
+    "delegations__checkpoints",
+    "delegations__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The total percentage of each delegator's voting power that is
+/// delegated.
+pub const DELEGATED: SnapshotMap<&Addr, Decimal> = SnapshotMap::new(
+    "delegated",
+    "delegated__checkpoints",
+    "delegated__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The addresses whose delegations each delegate has accepted.
+pub const DELEGATORS: SnapshotMap<&Addr, Vec<Addr>> = SnapshotMap::new(
+    "delegators",
+    "delegators__checkpoints",
+    "delegators__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Delegations awaiting acceptance by their delegate, keyed by
+/// (delegate, delegator).
+pub const PENDING_DELEGATIONS: Map<(&Addr, &Addr), Decimal> = Map::new("pending_delegations");
+
+/// The maximum number of delegators a delegate may accept. Computing
+/// a delegate's voting power queries the underlying module once per
+/// delegator.
+pub const MAX_DELEGATORS: usize = 20;
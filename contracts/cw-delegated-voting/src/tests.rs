@@ -0,0 +1,415 @@
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
+use cw_core_interface::voting::{TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
+use cw_multi_test::{next_block, App, AppResponse, Contract, ContractWrapper, Executor};
+
+use crate::{
+    msg::{
+        Delegation, DelegationsResponse, ExecuteMsg, InstantiateMsg, PendingDelegation,
+        PendingDelegationsResponse, QueryMsg,
+    },
+    state::MAX_DELEGATORS,
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+const ADDR3: &str = "addr3";
+
+fn cw4_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw4_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_voting::contract::execute,
+        cw4_voting::contract::instantiate,
+        cw4_voting::contract::query,
+    )
+    .with_reply(cw4_voting::contract::reply);
+    Box::new(contract)
+}
+
+fn delegated_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// Instantiates a cw4 voting module where ADDR1, ADDR2, and ADDR3
+/// have weights of 10, 2, and 4 and a delegated voting module
+/// wrapping it.
+fn setup_test_case(app: &mut App) -> Addr {
+    let cw4_id = app.store_code(cw4_contract());
+    let cw4_voting_id = app.store_code(cw4_voting_contract());
+    let delegated_id = app.store_code(delegated_voting_contract());
+
+    let cw4_voting = app
+        .instantiate_contract(
+            cw4_voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw4_voting::msg::InstantiateMsg {
+                cw4_group_code_id: cw4_id,
+                initial_members: vec![
+                    cw4::Member {
+                        addr: ADDR1.to_string(),
+                        weight: 10,
+                    },
+                    cw4::Member {
+                        addr: ADDR2.to_string(),
+                        weight: 2,
+                    },
+                    cw4::Member {
+                        addr: ADDR3.to_string(),
+                        weight: 4,
+                    },
+                ],
+                active_threshold: None,
+            },
+            &[],
+            "cw4 voting",
+            None,
+        )
+        .unwrap();
+    let delegated = app
+        .instantiate_contract(
+            delegated_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                voting_module: cw4_voting.to_string(),
+            },
+            &[],
+            "delegated voting",
+            None,
+        )
+        .unwrap();
+    app.update_block(next_block);
+    delegated
+}
+
+fn delegate(
+    app: &mut App,
+    voting: &Addr,
+    sender: &str,
+    delegate: &str,
+    percent: u64,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        voting.clone(),
+        &ExecuteMsg::Delegate {
+            delegate: delegate.to_string(),
+            percent: Decimal::percent(percent),
+        },
+        &[],
+    )
+}
+
+fn accept(
+    app: &mut App,
+    voting: &Addr,
+    sender: &str,
+    delegator: &str,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        voting.clone(),
+        &ExecuteMsg::AcceptDelegation {
+            delegator: delegator.to_string(),
+        },
+        &[],
+    )
+}
+
+fn voting_power(app: &App, voting: &Addr, address: &str, height: Option<u64>) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+#[test]
+fn test_delegate_and_undelegate() {
+    let mut app = App::default();
+    let voting = setup_test_case(&mut app);
+    let start = app.block_info().height;
+
+    delegate(&mut app, &voting, ADDR1, ADDR2, 50).unwrap();
+    delegate(&mut app, &voting, ADDR3, ADDR2, 100).unwrap();
+    accept(&mut app, &voting, ADDR2, ADDR1).unwrap();
+    accept(&mut app, &voting, ADDR2, ADDR3).unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(5));
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(11));
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::zero());
+    // Delegations take effect from the next block.
+    assert_eq!(
+        voting_power(&app, &voting, ADDR2, Some(start)),
+        Uint128::new(2)
+    );
+    let total: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(&voting, &QueryMsg::TotalPowerAtHeight { height: None })
+        .unwrap();
+    assert_eq!(total.power, Uint128::new(16));
+
+    // Delegating again to the same delegate replaces the delegation
+    // without it being accepted again.
+    delegate(&mut app, &voting, ADDR1, ADDR2, 100).unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::zero());
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(16));
+
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting.clone(),
+        &ExecuteMsg::Undelegate {
+            delegate: ADDR2.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(10));
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(6));
+    assert_eq!(
+        voting_power(&app, &voting, ADDR2, Some(start + 2)),
+        Uint128::new(16)
+    );
+
+    let delegations: DelegationsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting,
+            &QueryMsg::Delegations {
+                delegator: ADDR3.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        delegations.delegations,
+        vec![Delegation {
+            delegate: Addr::unchecked(ADDR2),
+            percent: Decimal::percent(100),
+        }]
+    );
+}
+
+#[test]
+fn test_delegated_power_is_not_passed_on() {
+    let mut app = App::default();
+    let voting = setup_test_case(&mut app);
+
+    delegate(&mut app, &voting, ADDR3, ADDR2, 100).unwrap();
+    delegate(&mut app, &voting, ADDR2, ADDR1, 100).unwrap();
+    accept(&mut app, &voting, ADDR2, ADDR3).unwrap();
+    accept(&mut app, &voting, ADDR1, ADDR2).unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(12));
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(4));
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::zero());
+}
+
+#[test]
+fn test_invalid_delegations() {
+    let mut app = App::default();
+    let voting = setup_test_case(&mut app);
+
+    let err: ContractError = delegate(&mut app, &voting, ADDR1, ADDR1, 50)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::SelfDelegation {});
+
+    let err: ContractError = delegate(&mut app, &voting, ADDR1, ADDR2, 0)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidPercent {});
+
+    // Offers may add up to more than all of an address' voting
+    // power, but accepting one may not take it over.
+    delegate(&mut app, &voting, ADDR1, ADDR2, 50).unwrap();
+    delegate(&mut app, &voting, ADDR1, ADDR3, 60).unwrap();
+    accept(&mut app, &voting, ADDR2, ADDR1).unwrap();
+    let err: ContractError = accept(&mut app, &voting, ADDR3, ADDR1)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::OverDelegated {});
+    let err: ContractError = delegate(&mut app, &voting, ADDR1, ADDR3, 60)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::OverDelegated {});
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting.clone(),
+        &ExecuteMsg::Undelegate {
+            delegate: ADDR3.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting,
+            &ExecuteMsg::Undelegate {
+                delegate: ADDR3.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::DelegationNotFound {
+            delegate: ADDR3.to_string()
+        }
+    );
+}
+
+#[test]
+fn test_delegations_require_acceptance() {
+    let mut app = App::default();
+    let voting = setup_test_case(&mut app);
+
+    delegate(&mut app, &voting, ADDR1, ADDR2, 50).unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(10));
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(2));
+
+    let pending: PendingDelegationsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting,
+            &QueryMsg::PendingDelegations {
+                delegate: ADDR2.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        pending.delegations,
+        vec![PendingDelegation {
+            delegator: Addr::unchecked(ADDR1),
+            percent: Decimal::percent(50),
+        }]
+    );
+
+    // Only the delegate may accept.
+    let err: ContractError = accept(&mut app, &voting, ADDR3, ADDR1)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::DelegatorNotFound {
+            delegator: ADDR1.to_string()
+        }
+    );
+
+    // Delegates may decline offers.
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        voting.clone(),
+        &ExecuteMsg::RemoveDelegator {
+            delegator: ADDR1.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = accept(&mut app, &voting, ADDR2, ADDR1)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::DelegatorNotFound {
+            delegator: ADDR1.to_string()
+        }
+    );
+
+    // And remove delegators they have accepted.
+    delegate(&mut app, &voting, ADDR1, ADDR2, 50).unwrap();
+    accept(&mut app, &voting, ADDR2, ADDR1).unwrap();
+    app.update_block(next_block);
+    let accepted = app.block_info().height;
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(7));
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        voting.clone(),
+        &ExecuteMsg::RemoveDelegator {
+            delegator: ADDR1.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(10));
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(2));
+    assert_eq!(
+        voting_power(&app, &voting, ADDR2, Some(accepted)),
+        Uint128::new(7)
+    );
+}
+
+#[test]
+fn test_max_delegators() {
+    let mut app = App::default();
+    let voting = setup_test_case(&mut app);
+
+    for i in 0..MAX_DELEGATORS {
+        let delegator = format!("delegator{}", i);
+        delegate(&mut app, &voting, &delegator, ADDR1, 100).unwrap();
+        accept(&mut app, &voting, ADDR1, &delegator).unwrap();
+    }
+    delegate(&mut app, &voting, ADDR3, ADDR1, 100).unwrap();
+    let err: ContractError = accept(&mut app, &voting, ADDR1, ADDR3)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::TooManyDelegators {
+            max: MAX_DELEGATORS
+        }
+    );
+
+    // Removing a delegator makes room for another.
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting.clone(),
+        &ExecuteMsg::RemoveDelegator {
+            delegator: "delegator0".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    accept(&mut app, &voting, ADDR1, ADDR3).unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(14));
+}
@@ -0,0 +1,342 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+    Storage, SubMsg, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_named_groups::msg::{ListAddressesResponse, ListGroupsResponse};
+use cw_utils::parse_reply_instantiate_data;
+use std::collections::BTreeMap;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, Weighting};
+use crate::state::{DAO, GROUP_CONTRACT, TOTAL_POWER, USER_POWERS, WEIGHTING};
+
+const CONTRACT_NAME: &str = "crates.io:cw-named-groups-voting";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const INSTANTIATE_GROUP_REPLY_ID: u64 = 0;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    validate_weighting(&msg.weighting)?;
+
+    // Collect the groups each address is in.
+    let mut memberships: BTreeMap<Addr, Vec<String>> = BTreeMap::new();
+    for group in msg.initial_groups.iter() {
+        for address in group.addresses.iter() {
+            let groups = memberships
+                .entry(deps.api.addr_validate(address)?)
+                .or_default();
+            if !groups.contains(&group.name) {
+                groups.push(group.name.clone());
+            }
+        }
+    }
+
+    let mut total_power = Uint128::zero();
+    for (address, groups) in memberships {
+        let power = power_for_groups(&msg.weighting, &groups)?;
+        if !power.is_zero() {
+            USER_POWERS.save(deps.storage, &address, &power, env.block.height)?;
+            total_power = total_power.checked_add(power).map_err(StdError::overflow)?;
+        }
+    }
+    if total_power.is_zero() {
+        return Err(ContractError::NoVotingPower {});
+    }
+    TOTAL_POWER.save(deps.storage, &total_power, env.block.height)?;
+    WEIGHTING.save(deps.storage, &msg.weighting)?;
+    DAO.save(deps.storage, &info.sender)?;
+
+    // We remain the owner of the group contract so that voting power
+    // stays in sync with group membership.
+    let msg = WasmMsg::Instantiate {
+        admin: Some(info.sender.to_string()),
+        code_id: msg.cw_named_groups_code_id,
+        msg: to_binary(&cw_named_groups::msg::InstantiateMsg {
+            groups: Some(msg.initial_groups),
+        })?,
+        funds: vec![],
+        label: env.contract.address.to_string(),
+    };
+    let msg = SubMsg::reply_on_success(msg, INSTANTIATE_GROUP_REPLY_ID);
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("total_power", total_power)
+        .add_submessage(msg))
+}
+
+fn validate_weighting(weighting: &Weighting) -> Result<(), ContractError> {
+    let groups: Vec<&String> = match weighting {
+        Weighting::OnePerMember { groups } => groups.iter().collect(),
+        Weighting::PerGroup { weights } => {
+            if weights.iter().any(|w| w.weight.is_zero()) {
+                return Err(ContractError::ZeroWeight {});
+            }
+            weights.iter().map(|w| &w.group).collect()
+        }
+    };
+    if groups.is_empty() {
+        return Err(ContractError::NoGroups {});
+    }
+    for (i, group) in groups.iter().enumerate() {
+        if groups[..i].contains(group) {
+            return Err(ContractError::DuplicateGroup {
+                group: group.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Computes the voting power of an address that is a member of
+/// `groups`.
+fn power_for_groups(weighting: &Weighting, groups: &[String]) -> StdResult<Uint128> {
+    match weighting {
+        Weighting::OnePerMember { groups: voting } => {
+            if groups.iter().any(|group| voting.contains(group)) {
+                Ok(Uint128::new(1))
+            } else {
+                Ok(Uint128::zero())
+            }
+        }
+        Weighting::PerGroup { weights } => weights
+            .iter()
+            .filter(|w| groups.contains(&w.group))
+            .try_fold(Uint128::zero(), |acc, w| {
+                acc.checked_add(w.weight).map_err(StdError::overflow)
+            }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Update {
+            group,
+            addresses_to_add,
+            addresses_to_remove,
+        } => execute_update(
+            deps,
+            env,
+            info,
+            group,
+            addresses_to_add,
+            addresses_to_remove,
+        ),
+        ExecuteMsg::RemoveGroup { group } => execute_remove_group(deps, env, info, group),
+    }
+}
+
+pub fn execute_update(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    group: String,
+    addresses_to_add: Option<Vec<String>>,
+    addresses_to_remove: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let to_add = addresses_to_add
+        .iter()
+        .flatten()
+        .map(|address| deps.api.addr_validate(address))
+        .collect::<StdResult<Vec<_>>>()?;
+    let to_remove = addresses_to_remove
+        .iter()
+        .flatten()
+        .map(|address| deps.api.addr_validate(address))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // The group contract has not been updated yet so we work out the
+    // groups each address will be in once it has. The group contract
+    // adds addresses before removing them.
+    let group_contract = GROUP_CONTRACT.load(deps.storage)?;
+    let weighting = WEIGHTING.load(deps.storage)?;
+    let mut total_power = TOTAL_POWER.load(deps.storage)?;
+    for address in to_add.iter().chain(to_remove.iter()) {
+        let mut groups = query_groups(deps.as_ref(), &group_contract, address)?;
+        groups.retain(|g| *g != group);
+        if !to_remove.contains(address) {
+            groups.push(group.clone());
+        }
+        let power = power_for_groups(&weighting, &groups)?;
+        total_power = update_power(deps.storage, env.block.height, address, power, total_power)?;
+    }
+    TOTAL_POWER.save(deps.storage, &total_power, env.block.height)?;
+
+    let msg = WasmMsg::Execute {
+        contract_addr: group_contract.to_string(),
+        msg: to_binary(&cw_named_groups::msg::ExecuteMsg::Update {
+            group: group.clone(),
+            addresses_to_add,
+            addresses_to_remove,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "update")
+        .add_attribute("group", group)
+        .add_attribute("total_power", total_power)
+        .add_message(msg))
+}
+
+pub fn execute_remove_group(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    group: String,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let group_contract = GROUP_CONTRACT.load(deps.storage)?;
+    let weighting = WEIGHTING.load(deps.storage)?;
+    let members: ListAddressesResponse = deps.querier.query_wasm_smart(
+        group_contract.clone(),
+        &cw_named_groups::msg::QueryMsg::ListAddresses {
+            group: group.clone(),
+            offset: None,
+            limit: None,
+        },
+    )?;
+    let mut total_power = TOTAL_POWER.load(deps.storage)?;
+    for address in members.addresses {
+        let mut groups = query_groups(deps.as_ref(), &group_contract, &address)?;
+        groups.retain(|g| *g != group);
+        let power = power_for_groups(&weighting, &groups)?;
+        total_power = update_power(deps.storage, env.block.height, &address, power, total_power)?;
+    }
+    TOTAL_POWER.save(deps.storage, &total_power, env.block.height)?;
+
+    let msg = WasmMsg::Execute {
+        contract_addr: group_contract.to_string(),
+        msg: to_binary(&cw_named_groups::msg::ExecuteMsg::RemoveGroup {
+            group: group.clone(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_group")
+        .add_attribute("group", group)
+        .add_attribute("total_power", total_power)
+        .add_message(msg))
+}
+
+fn query_groups(deps: Deps, group_contract: &Addr, address: &Addr) -> StdResult<Vec<String>> {
+    let res: ListGroupsResponse = deps.querier.query_wasm_smart(
+        group_contract,
+        &cw_named_groups::msg::QueryMsg::ListGroups {
+            address: address.to_string(),
+            offset: None,
+            limit: None,
+        },
+    )?;
+    Ok(res.groups)
+}
+
+/// Sets the voting power of `address` and returns the total power
+/// adjusted for the change.
+fn update_power(
+    storage: &mut dyn Storage,
+    height: u64,
+    address: &Addr,
+    power: Uint128,
+    total_power: Uint128,
+) -> StdResult<Uint128> {
+    let old = USER_POWERS.may_load(storage, address)?.unwrap_or_default();
+    if old == power {
+        return Ok(total_power);
+    }
+    USER_POWERS.save(storage, address, &power, height)?;
+    total_power
+        .checked_sub(old)
+        .map_err(StdError::overflow)?
+        .checked_add(power)
+        .map_err(StdError::overflow)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            query_voting_power_at_height(deps, env, address, height)
+        }
+        QueryMsg::TotalPowerAtHeight { height } => query_total_power_at_height(deps, env, height),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::GroupContract {} => to_binary(&GROUP_CONTRACT.load(deps.storage)?),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Weighting {} => to_binary(&WEIGHTING.load(deps.storage)?),
+    }
+}
+
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+    let power = USER_POWERS
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+    to_binary(&cw_core_interface::voting::VotingPowerAtHeightResponse { power, height })
+}
+
+pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    let height = height.unwrap_or(env.block.height);
+    let power = TOTAL_POWER
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+    to_binary(&cw_core_interface::voting::TotalPowerAtHeightResponse { power, height })
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&cw_core_interface::voting::InfoResponse { info })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        INSTANTIATE_GROUP_REPLY_ID => {
+            let res = parse_reply_instantiate_data(msg);
+            match res {
+                Ok(res) => {
+                    let group_contract = GROUP_CONTRACT.may_load(deps.storage)?;
+                    if group_contract.is_some() {
+                        return Err(ContractError::DuplicateGroupContract {});
+                    }
+                    let group_contract = deps.api.addr_validate(&res.contract_address)?;
+                    GROUP_CONTRACT.save(deps.storage, &group_contract)?;
+                    Ok(Response::default().add_attribute("group_contract_address", group_contract))
+                }
+                Err(_) => Err(ContractError::GroupContractInstantiateError {}),
+            }
+        }
+        _ => Err(ContractError::UnknownReplyId { id: msg.id }),
+    }
+}
@@ -0,0 +1,32 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("At least one group must be given voting power")]
+    NoGroups {},
+
+    #[error("Group weights must be greater than zero")]
+    ZeroWeight {},
+
+    #[error("Group ({group}) was listed more than once")]
+    DuplicateGroup { group: String },
+
+    #[error("Cannot instantiate with no voting power as it would lock the DAO")]
+    NoVotingPower {},
+
+    #[error("Can not change the contract's group contract after it has been set")]
+    DuplicateGroupContract {},
+
+    #[error("Error occured whilst instantiating group contract")]
+    GroupContractInstantiateError {},
+
+    #[error("Got a submessage reply with unknown id: {id}")]
+    UnknownReplyId { id: u64 },
+}
@@ -0,0 +1,56 @@
+use cosmwasm_std::Uint128;
+use cw_core_macros::voting_query;
+use cw_named_groups::msg::Group;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GroupWeight {
+    pub group: String,
+    pub weight: Uint128,
+}
+
+/// How voting power is derived from group membership. Groups not
+/// listed here may still be used as role lists but carry no voting
+/// power.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Weighting {
+    /// Every address in at least one of `groups` has one vote.
+    OnePerMember { groups: Vec<String> },
+    /// An address' voting power is the sum of the weights of the
+    /// groups it is in.
+    PerGroup { weights: Vec<GroupWeight> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub cw_named_groups_code_id: u64,
+    pub initial_groups: Vec<Group>,
+    pub weighting: Weighting,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Adds and removes addresses from a group in the group
+    /// contract, creating the group if it does not exist. Only
+    /// callable by the DAO.
+    Update {
+        group: String,
+        addresses_to_add: Option<Vec<String>>,
+        addresses_to_remove: Option<Vec<String>>,
+    },
+    /// Removes a group from the group contract. Only callable by the
+    /// DAO.
+    RemoveGroup { group: String },
+}
+
+#[voting_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    GroupContract {},
+    Dao {},
+    Weighting {},
+}
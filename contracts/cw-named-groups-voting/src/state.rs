@@ -0,0 +1,22 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+
+use crate::msg::Weighting;
+
+pub const USER_POWERS: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "user_powers",
+    "user_powers__checkpoints",
+    "user_powers__changelog",
+    Strategy::EveryBlock,
+);
+
+pub const TOTAL_POWER: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_power",
+    "total_power__checkpoints",
+    "total_power__changelog",
+    Strategy::EveryBlock,
+);
+
+pub const GROUP_CONTRACT: Item<Addr> = Item::new("group_contract");
+pub const DAO: Item<Addr> = Item::new("dao");
+pub const WEIGHTING: Item<Weighting> = Item::new("weighting");
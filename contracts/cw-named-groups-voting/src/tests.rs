@@ -0,0 +1,350 @@
+use cosmwasm_std::{Addr, Empty, StdError, Uint128};
+use cw_core_interface::voting::{TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
+use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+use cw_named_groups::msg::{Group, IsAddressInGroupResponse};
+
+use crate::{
+    msg::{ExecuteMsg, GroupWeight, InstantiateMsg, QueryMsg, Weighting},
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+const ADDR3: &str = "addr3";
+const ADDR4: &str = "addr4";
+
+fn named_groups_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_named_groups::contract::execute,
+        cw_named_groups::contract::instantiate,
+        cw_named_groups::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn named_groups_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply);
+    Box::new(contract)
+}
+
+fn initial_groups() -> Vec<Group> {
+    vec![
+        Group {
+            name: "council".to_string(),
+            addresses: vec![ADDR1.to_string(), ADDR2.to_string()],
+        },
+        Group {
+            name: "members".to_string(),
+            addresses: vec![ADDR1.to_string(), ADDR2.to_string(), ADDR3.to_string()],
+        },
+        Group {
+            name: "treasurer".to_string(),
+            addresses: vec![ADDR3.to_string()],
+        },
+    ]
+}
+
+fn per_group_weighting() -> Weighting {
+    Weighting::PerGroup {
+        weights: vec![
+            GroupWeight {
+                group: "council".to_string(),
+                weight: Uint128::new(3),
+            },
+            GroupWeight {
+                group: "members".to_string(),
+                weight: Uint128::new(1),
+            },
+        ],
+    }
+}
+
+fn instantiate_voting(app: &mut App, weighting: Weighting) -> anyhow::Result<Addr> {
+    let groups_id = app.store_code(named_groups_contract());
+    let voting_id = app.store_code(named_groups_voting_contract());
+    app.instantiate_contract(
+        voting_id,
+        Addr::unchecked(DAO_ADDR),
+        &InstantiateMsg {
+            cw_named_groups_code_id: groups_id,
+            initial_groups: initial_groups(),
+            weighting,
+        },
+        &[],
+        "named groups voting",
+        None,
+    )
+}
+
+fn voting_power(app: &App, voting: &Addr, address: &str, height: Option<u64>) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+fn total_power(app: &App, voting: &Addr, height: Option<u64>) -> Uint128 {
+    let res: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(voting, &QueryMsg::TotalPowerAtHeight { height })
+        .unwrap();
+    res.power
+}
+
+#[test]
+fn test_instantiate() {
+    let mut app = App::default();
+    let voting = instantiate_voting(&mut app, per_group_weighting()).unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(4));
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(4));
+    // The treasurer group carries no voting power.
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::new(1));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(9));
+
+    // The voting module owns the group contract.
+    let group_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(voting.clone(), &QueryMsg::GroupContract {})
+        .unwrap();
+    let err: cw_named_groups::ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            group_contract,
+            &cw_named_groups::msg::ExecuteMsg::Update {
+                group: "council".to_string(),
+                addresses_to_add: Some(vec![ADDR4.to_string()]),
+                addresses_to_remove: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, cw_named_groups::ContractError::Unauthorized {});
+
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(voting, &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(dao, Addr::unchecked(DAO_ADDR));
+}
+
+#[test]
+fn test_one_per_member() {
+    let mut app = App::default();
+    let voting = instantiate_voting(
+        &mut app,
+        Weighting::OnePerMember {
+            groups: vec!["council".to_string(), "members".to_string()],
+        },
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(1));
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(1));
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::new(1));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(3));
+}
+
+#[test]
+fn test_update() {
+    let mut app = App::default();
+    let voting = instantiate_voting(&mut app, per_group_weighting()).unwrap();
+    app.update_block(next_block);
+    let before = app.block_info().height;
+
+    let update = ExecuteMsg::Update {
+        group: "council".to_string(),
+        addresses_to_add: Some(vec![ADDR3.to_string()]),
+        addresses_to_remove: Some(vec![ADDR2.to_string()]),
+    };
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked(ADDR1), voting.clone(), &update, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(Addr::unchecked(DAO_ADDR), voting.clone(), &update, &[])
+        .unwrap();
+    // Groups without a weight may be used as role lists.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting.clone(),
+        &ExecuteMsg::Update {
+            group: "observers".to_string(),
+            addresses_to_add: Some(vec![ADDR4.to_string()]),
+            addresses_to_remove: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(1));
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::new(4));
+    assert_eq!(voting_power(&app, &voting, ADDR4, None), Uint128::zero());
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(9));
+
+    // Power at past heights is unchanged.
+    assert_eq!(
+        voting_power(&app, &voting, ADDR2, Some(before)),
+        Uint128::new(4)
+    );
+    assert_eq!(
+        voting_power(&app, &voting, ADDR3, Some(before)),
+        Uint128::new(1)
+    );
+
+    // The group contract reflects the update.
+    let group_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(voting, &QueryMsg::GroupContract {})
+        .unwrap();
+    let res: IsAddressInGroupResponse = app
+        .wrap()
+        .query_wasm_smart(
+            group_contract,
+            &cw_named_groups::msg::QueryMsg::IsAddressInGroup {
+                address: ADDR3.to_string(),
+                group: "council".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(res.is_in_group);
+}
+
+#[test]
+fn test_remove_group() {
+    let mut app = App::default();
+    let voting = instantiate_voting(&mut app, per_group_weighting()).unwrap();
+    app.update_block(next_block);
+    let before = app.block_info().height;
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting.clone(),
+        &ExecuteMsg::RemoveGroup {
+            group: "council".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(1));
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(1));
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::new(1));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(3));
+    assert_eq!(total_power(&app, &voting, Some(before)), Uint128::new(9));
+}
+
+#[test]
+fn test_invalid_weighting() {
+    let mut app = App::default();
+
+    let err: ContractError = instantiate_voting(&mut app, Weighting::PerGroup { weights: vec![] })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoGroups {});
+
+    let err: ContractError = instantiate_voting(
+        &mut app,
+        Weighting::PerGroup {
+            weights: vec![GroupWeight {
+                group: "council".to_string(),
+                weight: Uint128::zero(),
+            }],
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::ZeroWeight {});
+
+    let err: ContractError = instantiate_voting(
+        &mut app,
+        Weighting::OnePerMember {
+            groups: vec!["council".to_string(), "council".to_string()],
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::DuplicateGroup {
+            group: "council".to_string()
+        }
+    );
+
+    let err: ContractError = instantiate_voting(
+        &mut app,
+        Weighting::OnePerMember {
+            groups: vec!["observers".to_string()],
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::NoVotingPower {});
+}
+
+#[test]
+fn test_weight_overflow() {
+    let mut app = App::default();
+
+    // addr1 is in both groups so its power does not fit in a
+    // Uint128. Instantiation errors instead of panicking.
+    let err: ContractError = instantiate_voting(
+        &mut app,
+        Weighting::PerGroup {
+            weights: vec![
+                GroupWeight {
+                    group: "council".to_string(),
+                    weight: Uint128::MAX,
+                },
+                GroupWeight {
+                    group: "members".to_string(),
+                    weight: Uint128::new(1),
+                },
+            ],
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+
+    // Each address fits but the total does not.
+    let err: ContractError = instantiate_voting(
+        &mut app,
+        Weighting::PerGroup {
+            weights: vec![GroupWeight {
+                group: "council".to_string(),
+                weight: Uint128::MAX,
+            }],
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+}
@@ -208,7 +208,6 @@ fn query_dump(deps: Deps) -> StdResult<DumpResponse> {
             .groups_to_addresses
             .prefix(&name)
             .keys(deps.storage, None, None, Order::Ascending)
-            .into_iter()
             .map(|addr| match addr {
                 Ok(_) => Ok(addr.unwrap().to_string()),
                 Err(_) => Err(addr.unwrap_err()),
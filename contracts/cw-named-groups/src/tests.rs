@@ -121,7 +121,7 @@ mod instantiate {
         let groups = list_groups(
             &app,
             &contract_addr,
-            group1.addresses.get(0).unwrap().clone(),
+            group1.addresses.first().unwrap().clone(),
             None,
             None,
         )
@@ -735,7 +735,7 @@ mod list_groups {
         let groups = list_groups(
             &app,
             &contract_addr,
-            group1.addresses.get(0).unwrap().to_string(),
+            group1.addresses.first().unwrap().to_string(),
             None,
             None,
         )
@@ -757,7 +757,7 @@ mod list_groups {
 
         let all_groups = vec![group1.clone(), group2, group3];
         let groups_count = all_groups.len();
-        let address = group1.addresses.get(0).unwrap();
+        let address = group1.addresses.first().unwrap();
 
         let (app, contract_addr) = instantiate(Some(all_groups)).unwrap();
 
@@ -905,7 +905,7 @@ mod is_address_in_group {
         let is_in_group = is_address_in_group(
             &app,
             &contract_addr,
-            group1.addresses.get(0).unwrap().to_string(),
+            group1.addresses.first().unwrap().to_string(),
             group1.name,
         )
         .unwrap()
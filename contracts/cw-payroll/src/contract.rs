@@ -0,0 +1,272 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, StreamResponse};
+use crate::state::{Schedule, Stream, DAO, RECIPIENT_STREAMS, STREAMS, STREAM_COUNT};
+
+const CONTRACT_NAME: &str = "crates.io:cw-payroll";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    DAO.save(deps.storage, &info.sender)?;
+    STREAM_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, info, wrapper),
+        ExecuteMsg::CreateStream {
+            recipient,
+            schedule,
+        } => execute_create_native_stream(deps, info, recipient, schedule),
+        ExecuteMsg::Claim { id } => execute_claim(deps, env, info, id),
+        ExecuteMsg::Cancel { id } => execute_cancel(deps, env, info, id),
+    }
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    match msg {
+        ReceiveMsg::CreateStream {
+            recipient,
+            schedule,
+        } => create_stream(
+            deps,
+            sender,
+            recipient,
+            Denom::Cw20(info.sender),
+            wrapper.amount,
+            schedule,
+        ),
+    }
+}
+
+pub fn execute_create_native_stream(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: String,
+    schedule: Schedule,
+) -> Result<Response, ContractError> {
+    let coin = match info.funds.as_slice() {
+        [coin] if !coin.amount.is_zero() => coin.clone(),
+        _ => return Err(ContractError::InvalidFunds {}),
+    };
+    create_stream(
+        deps,
+        info.sender,
+        recipient,
+        Denom::Native(coin.denom),
+        coin.amount,
+        schedule,
+    )
+}
+
+fn create_stream(
+    deps: DepsMut,
+    sender: Addr,
+    recipient: String,
+    denom: Denom,
+    amount: Uint128,
+    schedule: Schedule,
+) -> Result<Response, ContractError> {
+    if sender != DAO.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    if let Schedule::Linear {
+        start_time,
+        end_time,
+    } = schedule
+    {
+        if end_time <= start_time {
+            return Err(ContractError::InvalidSchedule {});
+        }
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let id = STREAM_COUNT.load(deps.storage)? + 1;
+    STREAM_COUNT.save(deps.storage, &id)?;
+    STREAMS.save(
+        deps.storage,
+        id,
+        &Stream {
+            id,
+            recipient: recipient.clone(),
+            denom,
+            amount,
+            claimed: Uint128::zero(),
+            schedule,
+        },
+    )?;
+    RECIPIENT_STREAMS.save(deps.storage, (recipient.clone(), id), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_stream")
+        .add_attribute("id", id.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let mut stream = load_stream(deps.as_ref(), id)?;
+    if info.sender != stream.recipient {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let claimable = stream.schedule.vested(stream.amount, env.block.time) - stream.claimed;
+    if claimable.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    stream.claimed += claimable;
+    STREAMS.save(deps.storage, id, &stream)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "claim")
+        .add_attribute("id", id.to_string())
+        .add_attribute("amount", claimable)
+        .add_message(transfer_msg(&stream.denom, &stream.recipient, claimable)?))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+    let stream = load_stream(deps.as_ref(), id)?;
+    STREAMS.remove(deps.storage, id);
+    RECIPIENT_STREAMS.remove(deps.storage, (stream.recipient.clone(), id));
+
+    let vested = stream.schedule.vested(stream.amount, env.block.time);
+    let claimable = vested - stream.claimed;
+    let unvested = stream.amount - vested;
+    let mut msgs = vec![];
+    if !claimable.is_zero() {
+        msgs.push(transfer_msg(&stream.denom, &stream.recipient, claimable)?);
+    }
+    if !unvested.is_zero() {
+        msgs.push(transfer_msg(&stream.denom, &dao, unvested)?);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel")
+        .add_attribute("id", id.to_string())
+        .add_attribute("paid", claimable)
+        .add_attribute("returned", unvested)
+        .add_messages(msgs))
+}
+
+fn load_stream(deps: Deps, id: u64) -> Result<Stream, ContractError> {
+    STREAMS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::StreamNotFound { id })
+}
+
+fn transfer_msg(denom: &Denom, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), denom),
+        }
+        .into(),
+        Denom::Cw20(token) => WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Stream { id } => query_stream(deps, env, id),
+        QueryMsg::ListStreams {
+            recipient,
+            start_after,
+            limit,
+        } => query_list_streams(deps, env, recipient, start_after, limit),
+    }
+}
+
+fn stream_response(env: &Env, stream: Stream) -> StreamResponse {
+    let vested = stream.schedule.vested(stream.amount, env.block.time);
+    StreamResponse {
+        claimable: vested - stream.claimed,
+        vested,
+        stream,
+    }
+}
+
+pub fn query_stream(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let stream = STREAMS.load(deps.storage, id)?;
+    to_binary(&stream_response(&env, stream))
+}
+
+pub fn query_list_streams(
+    deps: Deps,
+    env: Env,
+    recipient: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let ids = RECIPIENT_STREAMS.prefix(recipient).keys(
+        deps.storage,
+        start_after.map(Bound::exclusive),
+        None,
+        Order::Ascending,
+    );
+    let ids = match limit {
+        Some(limit) => ids.take(limit as usize).collect::<StdResult<Vec<u64>>>()?,
+        None => ids.collect::<StdResult<Vec<u64>>>()?,
+    };
+    let streams = ids
+        .into_iter()
+        .map(|id| Ok(stream_response(&env, STREAMS.load(deps.storage, id)?)))
+        .collect::<StdResult<Vec<StreamResponse>>>()?;
+    to_binary(&streams)
+}
@@ -0,0 +1,23 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Streams must be funded with exactly one non-zero native token")]
+    InvalidFunds {},
+
+    #[error("Linear schedules must end after they start")]
+    InvalidSchedule {},
+
+    #[error("Stream ({id}) does not exist")]
+    StreamNotFound { id: u64 },
+
+    #[error("Nothing has vested since the last claim")]
+    NothingToClaim {},
+}
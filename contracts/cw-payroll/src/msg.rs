@@ -0,0 +1,64 @@
+use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{Schedule, Stream};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Executed when the DAO sends cw20 tokens to fund a stream.
+    Receive(Cw20ReceiveMsg),
+    /// Callable by the DAO. Creates a stream paying the native
+    /// tokens sent with this message to `recipient`.
+    CreateStream {
+        recipient: String,
+        schedule: Schedule,
+    },
+    /// Callable by a stream's recipient. Sends the recipient the
+    /// stream's vested but unclaimed tokens.
+    Claim { id: u64 },
+    /// Callable by the DAO. Pays the recipient any vested but
+    /// unclaimed tokens, returns the rest to the DAO, and removes the
+    /// stream.
+    Cancel { id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    /// Creates a stream paying the sent tokens to `recipient`.
+    CreateStream {
+        recipient: String,
+        schedule: Schedule,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the DAO that manages this contract. Returns Addr.
+    Dao {},
+    /// Returns a stream. Returns StreamResponse.
+    Stream { id: u64 },
+    /// Lists the streams paying `recipient` in order of
+    /// creation. Returns Vec<StreamResponse>.
+    ListStreams {
+        recipient: String,
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StreamResponse {
+    pub stream: Stream,
+    /// The amount that has vested so far.
+    pub vested: Uint128,
+    /// The amount the recipient may claim now.
+    pub claimable: Uint128,
+}
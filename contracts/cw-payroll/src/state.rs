@@ -0,0 +1,70 @@
+use cosmwasm_std::{Addr, Empty, Timestamp, Uint128};
+use cw20::Denom;
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// How a stream's tokens vest over time.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Schedule {
+    /// Tokens vest continuously between `start_time` and `end_time`.
+    Linear {
+        start_time: Timestamp,
+        end_time: Timestamp,
+    },
+    /// All tokens vest at `release_time`.
+    Cliff { release_time: Timestamp },
+}
+
+impl Schedule {
+    /// The amount of `total` that has vested at `now`.
+    pub fn vested(&self, total: Uint128, now: Timestamp) -> Uint128 {
+        match self {
+            Schedule::Linear {
+                start_time,
+                end_time,
+            } => {
+                if now <= *start_time {
+                    Uint128::zero()
+                } else if now >= *end_time {
+                    total
+                } else {
+                    total.multiply_ratio(
+                        now.seconds() - start_time.seconds(),
+                        end_time.seconds() - start_time.seconds(),
+                    )
+                }
+            }
+            Schedule::Cliff { release_time } => {
+                if now >= *release_time {
+                    total
+                } else {
+                    Uint128::zero()
+                }
+            }
+        }
+    }
+}
+
+/// Tokens being paid to a recipient on a schedule.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Stream {
+    pub id: u64,
+    /// The address that may claim vested tokens.
+    pub recipient: Addr,
+    /// The token being paid.
+    pub denom: Denom,
+    /// The total amount paid over the course of the stream.
+    pub amount: Uint128,
+    /// The amount the recipient has claimed so far.
+    pub claimed: Uint128,
+    pub schedule: Schedule,
+}
+
+/// The DAO that may create and cancel streams.
+pub const DAO: Item<Addr> = Item::new("dao");
+pub const STREAM_COUNT: Item<u64> = Item::new("stream_count");
+pub const STREAMS: Map<u64, Stream> = Map::new("streams");
+/// Set of (recipient, stream ID) pairs.
+pub const RECIPIENT_STREAMS: Map<(Addr, u64), Empty> = Map::new("recipient_streams");
@@ -0,0 +1,328 @@
+use cosmwasm_std::{coin, coins, to_binary, Addr, Empty, Timestamp, Uint128};
+use cw20::Cw20Coin;
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, StreamResponse},
+    state::Schedule,
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const CONTRIBUTOR: &str = "contributor";
+
+fn payroll_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn setup() -> (App, Addr) {
+    let mut app = App::default();
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(DAO_ADDR), coins(1000, "ujuno"))
+            .unwrap();
+    });
+    let payroll_id = app.store_code(payroll_contract());
+    let payroll = app
+        .instantiate_contract(
+            payroll_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {},
+            &[],
+            "payroll",
+            None,
+        )
+        .unwrap();
+    (app, payroll)
+}
+
+fn linear(app: &App, duration: u64) -> Schedule {
+    let now = app.block_info().time;
+    Schedule::Linear {
+        start_time: now,
+        end_time: now.plus_seconds(duration),
+    }
+}
+
+fn query_stream(app: &App, payroll: &Addr, id: u64) -> StreamResponse {
+    app.wrap()
+        .query_wasm_smart(payroll, &QueryMsg::Stream { id })
+        .unwrap()
+}
+
+#[test]
+fn test_native_linear_stream() {
+    let (mut app, payroll) = setup();
+    let schedule = linear(&app, 100);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CONTRIBUTOR),
+            payroll.clone(),
+            &ExecuteMsg::CreateStream {
+                recipient: CONTRIBUTOR.to_string(),
+                schedule: schedule.clone(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidFunds {});
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        payroll.clone(),
+        &ExecuteMsg::CreateStream {
+            recipient: CONTRIBUTOR.to_string(),
+            schedule,
+        },
+        &coins(100, "ujuno"),
+    )
+    .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(50));
+    let stream = query_stream(&app, &payroll, 1);
+    assert_eq!(stream.vested, Uint128::new(50));
+    assert_eq!(stream.claimable, Uint128::new(50));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            payroll.clone(),
+            &ExecuteMsg::Claim { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        Addr::unchecked(CONTRIBUTOR),
+        payroll.clone(),
+        &ExecuteMsg::Claim { id: 1 },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CONTRIBUTOR),
+            payroll.clone(),
+            &ExecuteMsg::Claim { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    // Cancelling pays out what has vested and returns the rest.
+    app.update_block(|block| block.time = block.time.plus_seconds(25));
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CONTRIBUTOR),
+            payroll.clone(),
+            &ExecuteMsg::Cancel { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        payroll.clone(),
+        &ExecuteMsg::Cancel { id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.wrap().query_balance(CONTRIBUTOR, "ujuno").unwrap(),
+        coin(75, "ujuno")
+    );
+    assert_eq!(
+        app.wrap().query_balance(DAO_ADDR, "ujuno").unwrap(),
+        coin(925, "ujuno")
+    );
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CONTRIBUTOR),
+            payroll,
+            &ExecuteMsg::Claim { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::StreamNotFound { id: 1 });
+}
+
+#[test]
+fn test_cw20_cliff_stream() {
+    let (mut app, payroll) = setup();
+    let cw20_id = app.store_code(cw20_contract());
+    let token = app
+        .instantiate_contract(
+            cw20_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw20_base::msg::InstantiateMsg {
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![
+                    Cw20Coin {
+                        address: DAO_ADDR.to_string(),
+                        amount: Uint128::new(100),
+                    },
+                    Cw20Coin {
+                        address: CONTRIBUTOR.to_string(),
+                        amount: Uint128::new(100),
+                    },
+                ],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "token",
+            None,
+        )
+        .unwrap();
+
+    let release_time = app.block_info().time.plus_seconds(60);
+    let create = |amount: u128| cw20::Cw20ExecuteMsg::Send {
+        contract: payroll.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&ReceiveMsg::CreateStream {
+            recipient: CONTRIBUTOR.to_string(),
+            schedule: Schedule::Cliff { release_time },
+        })
+        .unwrap(),
+    };
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CONTRIBUTOR),
+            token.clone(),
+            &create(10),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(Addr::unchecked(DAO_ADDR), token.clone(), &create(100), &[])
+        .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CONTRIBUTOR),
+            payroll.clone(),
+            &ExecuteMsg::Claim { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    app.update_block(|block| block.time = release_time);
+    app.execute_contract(
+        Addr::unchecked(CONTRIBUTOR),
+        payroll,
+        &ExecuteMsg::Claim { id: 1 },
+        &[],
+    )
+    .unwrap();
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: CONTRIBUTOR.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(200));
+}
+
+#[test]
+fn test_list_streams() {
+    let (mut app, payroll) = setup();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            payroll.clone(),
+            &ExecuteMsg::CreateStream {
+                recipient: CONTRIBUTOR.to_string(),
+                schedule: Schedule::Linear {
+                    start_time: Timestamp::from_seconds(10),
+                    end_time: Timestamp::from_seconds(10),
+                },
+            },
+            &coins(10, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidSchedule {});
+
+    for recipient in [CONTRIBUTOR, "other", CONTRIBUTOR, CONTRIBUTOR] {
+        app.execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            payroll.clone(),
+            &ExecuteMsg::CreateStream {
+                recipient: recipient.to_string(),
+                schedule: linear(&app, 100),
+            },
+            &coins(10, "ujuno"),
+        )
+        .unwrap();
+    }
+
+    let streams: Vec<StreamResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            payroll.clone(),
+            &QueryMsg::ListStreams {
+                recipient: CONTRIBUTOR.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let ids: Vec<u64> = streams.into_iter().map(|s| s.stream.id).collect();
+    assert_eq!(ids, vec![1, 3, 4]);
+
+    let streams: Vec<StreamResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            payroll,
+            &QueryMsg::ListStreams {
+                recipient: CONTRIBUTOR.to_string(),
+                start_after: Some(1),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(streams.len(), 1);
+    assert_eq!(streams[0].stream.id, 3);
+}
@@ -9,7 +9,7 @@ use cw_proposal_single::{
     query::{ProposalListResponse, ProposalResponse, VoteListResponse, VoteResponse},
     state::Config,
 };
-use indexable_hooks::HooksResponse;
+use indexable_hooks::{HookGasLimitsResponse, HooksResponse};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -55,4 +55,14 @@ fn main() {
         "ProposalHooksResponse",
     );
     export_schema_with_title(&schema_for!(HooksResponse), &out_dir, "VoteHooksResponse");
+    export_schema_with_title(
+        &schema_for!(HookGasLimitsResponse),
+        &out_dir,
+        "ProposalHookGasLimitsResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(HookGasLimitsResponse),
+        &out_dir,
+        "VoteHookGasLimitsResponse",
+    );
 }
@@ -5,6 +5,7 @@ use cosmwasm_std::{
     StdResult, Storage, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw_core::state::{ProposalModule, ProposalModuleStatus};
 use cw_core_interface::voting::IsActiveResponse;
 use cw_storage_plus::Bound;
 use cw_utils::Duration;
@@ -97,13 +98,15 @@ pub fn execute(
             dao,
             deposit_info,
         ),
-        ExecuteMsg::AddProposalHook { address } => {
-            execute_add_proposal_hook(deps, env, info, address)
+        ExecuteMsg::AddProposalHook { address, gas_limit } => {
+            execute_add_proposal_hook(deps, env, info, address, gas_limit)
         }
         ExecuteMsg::RemoveProposalHook { address } => {
             execute_remove_proposal_hook(deps, env, info, address)
         }
-        ExecuteMsg::AddVoteHook { address } => execute_add_vote_hook(deps, env, info, address),
+        ExecuteMsg::AddVoteHook { address, gas_limit } => {
+            execute_add_vote_hook(deps, env, info, address, gas_limit)
+        }
         ExecuteMsg::RemoveVoteHook { address } => {
             execute_remove_vote_hook(deps, env, info, address)
         }
@@ -120,6 +123,24 @@ pub fn execute_propose(
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
+    // Disabled modules may still execute passed proposals but may
+    // not create new ones.
+    let module: Option<ProposalModule> = deps.querier.query_wasm_smart(
+        config.dao.clone(),
+        &cw_core::msg::QueryMsg::ProposalModule {
+            address: env.contract.address.to_string(),
+        },
+    )?;
+    if !matches!(
+        module,
+        Some(ProposalModule {
+            status: ProposalModuleStatus::Enabled,
+            ..
+        })
+    ) {
+        return Err(ContractError::ModuleDisabled {});
+    }
+
     let voting_module: Addr = deps
         .querier
         .query_wasm_smart(config.dao.clone(), &cw_core::msg::QueryMsg::VotingModule {})?;
@@ -425,9 +446,10 @@ pub fn add_hook(
     hooks: Hooks,
     storage: &mut dyn Storage,
     validated_address: Addr,
+    gas_limit: Option<u64>,
 ) -> Result<(), ContractError> {
     hooks
-        .add_hook(storage, validated_address)
+        .add_hook(storage, validated_address, gas_limit)
         .map_err(ContractError::HookError)?;
     Ok(())
 }
@@ -448,6 +470,7 @@ pub fn execute_add_proposal_hook(
     _env: Env,
     info: MessageInfo,
     address: String,
+    gas_limit: Option<u64>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -457,11 +480,17 @@ pub fn execute_add_proposal_hook(
 
     let validated_address = deps.api.addr_validate(&address)?;
 
-    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address, gas_limit)?;
 
     Ok(Response::default()
         .add_attribute("action", "add_proposal_hook")
-        .add_attribute("address", address))
+        .add_attribute("address", address)
+        .add_attribute(
+            "gas_limit",
+            gas_limit
+                .map(|g| g.to_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ))
 }
 
 pub fn execute_remove_proposal_hook(
@@ -490,6 +519,7 @@ pub fn execute_add_vote_hook(
     _env: Env,
     info: MessageInfo,
     address: String,
+    gas_limit: Option<u64>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -499,11 +529,17 @@ pub fn execute_add_vote_hook(
 
     let validated_address = deps.api.addr_validate(&address)?;
 
-    add_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+    add_hook(VOTE_HOOKS, deps.storage, validated_address, gas_limit)?;
 
     Ok(Response::default()
         .add_attribute("action", "add_vote_hook")
-        .add_attribute("address", address))
+        .add_attribute("address", address)
+        .add_attribute(
+            "gas_limit",
+            gas_limit
+                .map(|g| g.to_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ))
 }
 
 pub fn execute_remove_vote_hook(
@@ -543,12 +579,17 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             limit,
         } => query_list_votes(deps, proposal_id, start_after, limit),
         QueryMsg::Info {} => query_info(deps),
+        QueryMsg::Dao {} => query_dao(deps),
         QueryMsg::ReverseProposals {
             start_before,
             limit,
         } => query_reverse_proposals(deps, env, start_before, limit),
         QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::ProposalHookGasLimits {} => {
+            to_binary(&PROPOSAL_HOOKS.query_hook_gas_limits(deps)?)
+        }
+        QueryMsg::VoteHookGasLimits {} => to_binary(&VOTE_HOOKS.query_hook_gas_limits(deps)?),
     }
 }
 
@@ -645,6 +686,11 @@ pub fn query_list_votes(
     to_binary(&VoteListResponse { votes })
 }
 
+pub fn query_dao(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    to_binary(&config.dao)
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&cw_core_interface::voting::InfoResponse { info })
@@ -652,7 +698,7 @@ pub fn query_info(deps: Deps) -> StdResult<Binary> {
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
-    if msg.id % 2 == 0 {
+    if msg.id.is_multiple_of(2) {
         // Proposal hook so we can just divide by two for index
         let idx = msg.id / 2;
         PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
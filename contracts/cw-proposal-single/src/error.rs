@@ -1,5 +1,3 @@
-use std::u64;
-
 use cosmwasm_std::StdError;
 use indexable_hooks::HookError;
 use thiserror::Error;
@@ -53,4 +51,7 @@ pub enum ContractError {
 
     #[error("The DAO is currently inactive, you cannot create proposals")]
     InactiveDao {},
+
+    #[error("This proposal module has been disabled by the DAO, you cannot create proposals")]
+    ModuleDisabled {},
 }
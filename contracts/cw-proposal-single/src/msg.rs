@@ -105,14 +105,22 @@ pub enum ExecuteMsg {
         /// proposal. None if no deposit, Some otherwise.
         deposit_info: Option<DepositInfo>,
     },
+    /// Adds a hook that is called when proposals are created and
+    /// change status. If `gas_limit` is set the hook may use at most
+    /// that much gas, otherwise it may use all remaining gas.
     AddProposalHook {
         address: String,
+        gas_limit: Option<u64>,
     },
     RemoveProposalHook {
         address: String,
     },
+    /// Adds a hook that is called when votes are cast. If
+    /// `gas_limit` is set the hook may use at most that much gas,
+    /// otherwise it may use all remaining gas.
     AddVoteHook {
         address: String,
+        gas_limit: Option<u64>,
     },
     RemoveVoteHook {
         address: String,
@@ -150,4 +158,10 @@ pub enum QueryMsg {
     },
     ProposalHooks {},
     VoteHooks {},
+    /// Gets the gas limit of each proposal hook. Returns
+    /// `indexable_hooks::HookGasLimitsResponse`.
+    ProposalHookGasLimits {},
+    /// Gets the gas limit of each vote hook. Returns
+    /// `indexable_hooks::HookGasLimitsResponse`.
+    VoteHookGasLimits {},
 }
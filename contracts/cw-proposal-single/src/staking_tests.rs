@@ -113,6 +113,9 @@ fn instantiate_with_staked_balances_voting() {
             })
             .unwrap(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
 
@@ -1,12 +1,12 @@
-use cosmwasm_std::{to_binary, Addr, Decimal, Empty, Uint128};
+use cosmwasm_std::{to_binary, Addr, Decimal, Empty, Uint128, WasmMsg};
 use cw20::Cw20Coin;
 use cw20_staked_balance_voting::msg::ActiveThreshold;
 use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
 
-use cw_core::msg::ModuleInstantiateInfo;
+use cw_core::{msg::ModuleInstantiateInfo, state::ProposalModule};
 use cw_utils::Duration;
 
-use indexable_hooks::HooksResponse;
+use indexable_hooks::{HookError, HookGasLimit, HookGasLimitsResponse, HooksResponse};
 
 use testing::{ShouldExecute, TestVote};
 use voting::{PercentageThreshold, Status, Threshold, Vote, Votes};
@@ -16,6 +16,7 @@ use crate::{
     proposal::Proposal,
     query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteResponse},
     state::{CheckedDepositInfo, Config},
+    ContractError,
 };
 
 const CREATOR_ADDR: &str = "creator";
@@ -183,6 +184,9 @@ fn instantiate_with_staked_balances_governance(
             admin: cw_core::msg::Admin::CoreContract {},
             msg: to_binary(&governance_instantiate).unwrap(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
 
@@ -291,6 +295,9 @@ fn instantiate_with_staking_active_threshold(
             admin: cw_core::msg::Admin::CoreContract {},
             label: "DAO DAO governance module".to_string(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
 
@@ -348,6 +355,7 @@ fn instantiate_with_cw4_groups_governance(
             msg: to_binary(&cw4_voting::msg::InstantiateMsg {
                 cw4_group_code_id: cw4_id,
                 initial_members: initial_weights,
+                active_threshold: None,
             })
             .unwrap(),
             admin: cw_core::msg::Admin::CoreContract {},
@@ -359,6 +367,9 @@ fn instantiate_with_cw4_groups_governance(
             admin: cw_core::msg::Admin::CoreContract {},
             label: "DAO DAO governance module".to_string(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
 
@@ -441,6 +452,9 @@ fn instantiate_with_cw20_balances_governance(
             admin: cw_core::msg::Admin::CoreContract {},
             label: "DAO DAO governance module".to_string(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
 
@@ -549,7 +563,7 @@ where
     let governance_addr =
         setup_governance(&mut app, govmod_id, instantiate, Some(initial_balances));
 
-    let governance_modules: Vec<Addr> = app
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             governance_addr.clone(),
@@ -561,7 +575,7 @@ where
         .unwrap();
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     // Allow a proposal deposit as needed.
     let config: Config = app
@@ -702,7 +716,7 @@ fn test_propose() {
 
     let governance_addr =
         instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
-    let governance_modules: Vec<Addr> = app
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             governance_addr.clone(),
@@ -714,7 +728,7 @@ fn test_propose() {
         .unwrap();
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     // Check that the governance module has been configured correctly.
     let config: Config = app
@@ -766,6 +780,130 @@ fn test_propose() {
     assert_eq!(created.id, 1u64);
 }
 
+#[test]
+fn test_disabled_module() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(single_govmod_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: cw_utils::Duration::Height(6),
+        only_members_execute: false,
+        deposit_info: None,
+    };
+
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate.clone(), None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_at: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    // Proposal 1 disables this module and adds a new one. Proposal 2
+    // updates the DAO's config.
+    let disable = WasmMsg::Execute {
+        contract_addr: governance_addr.to_string(),
+        msg: to_binary(&cw_core::msg::ExecuteMsg::UpdateProposalModules {
+            to_add: vec![ModuleInstantiateInfo {
+                code_id: govmod_id,
+                msg: to_binary(&instantiate).unwrap(),
+                admin: cw_core::msg::Admin::CoreContract {},
+                label: "new governance module".to_string(),
+            }],
+            to_disable: vec![govmod_single.to_string()],
+            to_remove: vec![],
+        })
+        .unwrap(),
+        funds: vec![],
+    };
+    let update_config = WasmMsg::Execute {
+        contract_addr: governance_addr.to_string(),
+        msg: to_binary(&cw_core::msg::ExecuteMsg::UpdateConfig {
+            config: cw_core::state::Config {
+                name: "Disabled DAO".to_string(),
+                description: "A DAO that builds DAOs".to_string(),
+                image_url: None,
+                automatically_add_cw20s: true,
+                automatically_add_cw721s: true,
+            },
+        })
+        .unwrap(),
+        funds: vec![],
+    };
+    for msg in [disable, update_config] {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A proposal".to_string(),
+                description: "A proposal".to_string(),
+                msgs: vec![msg.into()],
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    for proposal_id in [1, 2] {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // Disabled modules may not create proposals.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ModuleDisabled {}));
+
+    // They may still execute proposals that have passed.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single,
+        &ExecuteMsg::Execute { proposal_id: 2 },
+        &[],
+    )
+    .unwrap();
+    let config: cw_core::state::Config = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.name, "Disabled DAO");
+}
+
 #[test]
 fn test_vote_simple() {
     testing::test_simple_votes(do_votes_cw20_balances);
@@ -889,7 +1027,7 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
     let voting_module = gov_state.voting_module;
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     let config: Config = app
         .wrap()
@@ -1050,7 +1188,7 @@ fn test_take_proposal_deposit() {
     let governance_modules = gov_state.governance_modules;
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     let govmod_config: Config = app
         .wrap()
@@ -1147,7 +1285,7 @@ fn test_deposit_return_on_execute() {
     let governance_modules = gov_state.governance_modules;
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     let govmod_config: Config = app
         .wrap()
@@ -1220,7 +1358,7 @@ fn test_close_open_proposal() {
     let governance_modules = gov_state.governance_modules;
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     // Close the proposal, this should error as the proposal is still
     // open and not expired.
@@ -1315,7 +1453,7 @@ fn test_deposit_return_on_close() {
     let governance_modules = gov_state.governance_modules;
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     let govmod_config: Config = app
         .wrap()
@@ -1395,7 +1533,7 @@ fn test_execute_expired_proposal() {
     let proposal_modules = gov_state.governance_modules;
 
     assert_eq!(proposal_modules.len(), 1);
-    let proposal_single = proposal_modules.into_iter().next().unwrap();
+    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
 
     app.execute_contract(
         Addr::unchecked("ekez"),
@@ -1506,7 +1644,7 @@ fn test_update_config() {
     let governance_modules = gov_state.governance_modules;
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     let govmod_config: Config = app
         .wrap()
@@ -1619,7 +1757,7 @@ fn test_no_return_if_no_refunds() {
     let governance_modules = gov_state.governance_modules;
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     let govmod_config: Config = app
         .wrap()
@@ -1673,7 +1811,7 @@ fn test_query_list_proposals() {
         }]),
     );
 
-    let gov_modules: Vec<Addr> = app
+    let gov_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             gov_addr,
@@ -1685,7 +1823,7 @@ fn test_query_list_proposals() {
         .unwrap();
     assert_eq!(gov_modules.len(), 1);
 
-    let govmod = gov_modules.into_iter().next().unwrap();
+    let govmod = gov_modules.into_iter().next().unwrap().address;
 
     for i in 1..10 {
         app.execute_contract(
@@ -1813,7 +1951,7 @@ fn test_hooks() {
 
     let governance_addr =
         instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
-    let governance_modules: Vec<Addr> = app
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             governance_addr,
@@ -1825,7 +1963,7 @@ fn test_hooks() {
         .unwrap();
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     let govmod_config: Config = app
         .wrap()
@@ -1848,6 +1986,7 @@ fn test_hooks() {
 
     let msg = ExecuteMsg::AddProposalHook {
         address: "some_addr".to_string(),
+        gas_limit: None,
     };
 
     // Expect error as sender is not DAO
@@ -1871,6 +2010,12 @@ fn test_hooks() {
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
 
+    let gas_limits: HookGasLimitsResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalHookGasLimits {})
+        .unwrap();
+    assert_eq!(gas_limits.hooks[0].gas_limit, None);
+
     // Expect error as hook is already set
     let _err = app
         .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
@@ -1907,8 +2052,28 @@ fn test_hooks() {
         .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
         .unwrap();
 
+    // Expect error as a hook may not have a zero gas limit
+    let err: ContractError = app
+        .execute_contract(
+            dao.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::AddVoteHook {
+                address: "some_addr".to_string(),
+                gas_limit: Some(0),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::HookError(HookError::ZeroGasLimit {})
+    ));
+
     let msg = ExecuteMsg::AddVoteHook {
         address: "some_addr".to_string(),
+        gas_limit: Some(100_000),
     };
 
     // Expect error as sender is not DAO
@@ -1932,6 +2097,18 @@ fn test_hooks() {
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
 
+    let gas_limits: HookGasLimitsResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::VoteHookGasLimits {})
+        .unwrap();
+    assert_eq!(
+        gas_limits.hooks,
+        vec![HookGasLimit {
+            addr: "some_addr".to_string(),
+            gas_limit: Some(100_000),
+        }]
+    );
+
     // Expect error as hook is already set
     let _err = app
         .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
@@ -1992,7 +2169,7 @@ fn test_active_threshold_absolute() {
             count: Uint128::new(100),
         }),
     );
-    let governance_modules: Vec<Addr> = app
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             governance_addr,
@@ -2004,7 +2181,7 @@ fn test_active_threshold_absolute() {
         .unwrap();
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     let govmod_config: Config = app
         .wrap()
@@ -2117,7 +2294,7 @@ fn test_active_threshold_percent() {
             percent: Decimal::percent(20),
         }),
     );
-    let governance_modules: Vec<Addr> = app
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             governance_addr,
@@ -2129,7 +2306,7 @@ fn test_active_threshold_percent() {
         .unwrap();
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     let govmod_config: Config = app
         .wrap()
@@ -2234,7 +2411,7 @@ fn test_active_threshold_none() {
 
     let governance_addr =
         instantiate_with_staking_active_threshold(&mut app, govmod_id, instantiate, None, None);
-    let governance_modules: Vec<Addr> = app
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             governance_addr,
@@ -2246,7 +2423,7 @@ fn test_active_threshold_none() {
         .unwrap();
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     let govmod_config: Config = app
         .wrap()
@@ -2311,7 +2488,7 @@ fn test_active_threshold_none() {
 
     let governance_addr =
         instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
-    let governance_modules: Vec<Addr> = app
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             governance_addr,
@@ -2323,7 +2500,7 @@ fn test_active_threshold_none() {
         .unwrap();
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     // Try and create a proposal, will succeed as IsActive is not implemented
     let _res = app
@@ -0,0 +1,38 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
+use cw_ragequit::{
+    msg::{ExecuteMsg, ExitShareResponse, InstantiateMsg, QueryMsg},
+    state::{Config, ExitWindow, Timelocked},
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+
+    // Auto TS code generation expects the query return type as QueryNameResponse
+    // Here we map query resonses to the correct name
+    export_schema_with_title(&schema_for!(Config), &out_dir, "ConfigResponse");
+    export_schema_with_title(
+        &schema_for!(Option<ExitWindow>),
+        &out_dir,
+        "ExitWindowResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(Option<Timelocked>),
+        &out_dir,
+        "TimelockedResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(ExitShareResponse),
+        &out_dir,
+        "ExitPreviewResponse",
+    );
+}
@@ -0,0 +1,422 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty,
+    Env, MessageInfo, Response, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_core::query::Cw20BalanceResponse;
+use cw_utils::Duration;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, ExitShareResponse, InstantiateMsg, QueryMsg, ReceiveMsg};
+use crate::state::{
+    Config, ExitWindow, ForfeitPolicy, Timelocked, CONFIG, EXITED, EXIT_WINDOW, TIMELOCKED,
+    TIMELOCK_COUNT,
+};
+
+const CONTRACT_NAME: &str = "crates.io:cw-ragequit";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let staking_contract = deps.api.addr_validate(&msg.staking_contract)?;
+    let staking_config: stake_cw20::msg::GetConfigResponse = deps
+        .querier
+        .query_wasm_smart(&staking_contract, &stake_cw20::msg::QueryMsg::GetConfig {})?;
+    let token = deps.api.addr_validate(&staking_config.token_address)?;
+
+    let config = Config {
+        dao: info.sender.clone(),
+        staking_contract,
+        token,
+        exit_window: msg.exit_window,
+        forfeit_policy: msg.forfeit_policy,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", info.sender)
+        .add_attribute("token", config.token))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, env, info, wrapper),
+        ExecuteMsg::Timelock { msgs } => execute_timelock(deps, env, info, msgs),
+        ExecuteMsg::ExecuteTimelocked { id } => execute_execute_timelocked(deps, env, id),
+        ExecuteMsg::UpdateConfig {
+            exit_window,
+            forfeit_policy,
+        } => execute_update_config(deps, info, exit_window, forfeit_policy),
+    }
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.token {
+        return Err(ContractError::InvalidToken {
+            received: info.sender,
+            expected: config.token,
+        });
+    }
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    match msg {
+        ReceiveMsg::Ragequit {} => execute_ragequit(deps, env, config, sender, wrapper.amount),
+    }
+}
+
+pub fn execute_ragequit(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    sender: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let window = EXIT_WINDOW
+        .may_load(deps.storage)?
+        .filter(|window| !window.closes.is_expired(&env.block))
+        .ok_or(ContractError::ExitWindowClosed {})?;
+
+    // Exits are limited to the value of the member's stake when the
+    // window opened so that tokens acquired or staked after the
+    // timelocked messages became known can not be used to exit.
+    let allowance = exit_allowance(deps.as_ref(), &config, &window, &sender)?;
+    if amount > allowance {
+        return Err(ContractError::ExceedsStake { allowance });
+    }
+    EXITED.update(
+        deps.storage,
+        (window.opened_at, &sender),
+        |exited| -> StdResult<_> { Ok(exited.unwrap_or_default().checked_add(amount)?) },
+    )?;
+
+    let share = compute_exit_share(deps.as_ref(), &config, amount)?;
+    if share.native.is_empty() && share.cw20.is_empty() {
+        return Err(ContractError::EmptyShare {});
+    }
+
+    let forfeit: CosmosMsg = WasmMsg::Execute {
+        contract_addr: config.token.to_string(),
+        msg: to_binary(&match config.forfeit_policy {
+            ForfeitPolicy::Burn {} => Cw20ExecuteMsg::Burn { amount },
+            ForfeitPolicy::SendToDao {} => Cw20ExecuteMsg::Transfer {
+                recipient: config.dao.to_string(),
+                amount,
+            },
+        })?,
+        funds: vec![],
+    }
+    .into();
+
+    let mut payouts: Vec<CosmosMsg> = share
+        .native
+        .into_iter()
+        .map(|coin| {
+            BankMsg::Send {
+                to_address: sender.to_string(),
+                amount: vec![coin],
+            }
+            .into()
+        })
+        .collect();
+    for coin in share.cw20 {
+        payouts.push(
+            WasmMsg::Execute {
+                contract_addr: coin.address,
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: sender.to_string(),
+                    amount: coin.amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        );
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "ragequit")
+        .add_attribute("member", sender)
+        .add_attribute("amount", amount)
+        .add_message(forfeit)
+        .add_message(execute_by_dao(&config, payouts)?))
+}
+
+pub fn execute_timelock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+    if msgs.is_empty() {
+        return Err(ContractError::NoMessages {});
+    }
+
+    let closes = config.exit_window.after(&env.block);
+    let window = match EXIT_WINDOW.may_load(deps.storage)? {
+        // Extending an open window keeps the stake snapshot it was
+        // opened with so members can not exit twice with the same
+        // stake.
+        Some(window) if !window.closes.is_expired(&env.block) => ExitWindow {
+            closes: if closes > window.closes {
+                closes
+            } else {
+                window.closes
+            },
+            ..window
+        },
+        _ => {
+            let total_staked: stake_cw20::msg::TotalStakedAtHeightResponse =
+                deps.querier.query_wasm_smart(
+                    &config.staking_contract,
+                    &stake_cw20::msg::QueryMsg::TotalStakedAtHeight {
+                        height: Some(env.block.height),
+                    },
+                )?;
+            let total_value: stake_cw20::msg::TotalValueResponse = deps.querier.query_wasm_smart(
+                &config.staking_contract,
+                &stake_cw20::msg::QueryMsg::TotalValue {},
+            )?;
+            ExitWindow {
+                opened_at: env.block.height,
+                closes,
+                total_staked: total_staked.total,
+                total_value: total_value.total,
+            }
+        }
+    };
+    EXIT_WINDOW.save(deps.storage, &window)?;
+
+    let id = TIMELOCK_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    TIMELOCK_COUNT.save(deps.storage, &id)?;
+    TIMELOCKED.save(
+        deps.storage,
+        id,
+        &Timelocked {
+            msgs,
+            executable_after: closes,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "timelock")
+        .add_attribute("id", id.to_string())
+        .add_attribute("executable_after", closes.to_string()))
+}
+
+pub fn execute_execute_timelocked(
+    deps: DepsMut,
+    env: Env,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let timelocked = TIMELOCKED.load(deps.storage, id)?;
+    if !timelocked.executable_after.is_expired(&env.block) {
+        return Err(ContractError::Timelocked {
+            id,
+            executable_after: timelocked.executable_after,
+        });
+    }
+    TIMELOCKED.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_timelocked")
+        .add_attribute("id", id.to_string())
+        .add_message(execute_by_dao(&config, timelocked.msgs)?))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    exit_window: Duration,
+    forfeit_policy: ForfeitPolicy,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+    config.exit_window = exit_window;
+    config.forfeit_policy = forfeit_policy;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// As a proposal module of the DAO this contract may have the DAO
+/// execute messages on its behalf.
+fn execute_by_dao(config: &Config, msgs: Vec<CosmosMsg<Empty>>) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: config.dao.to_string(),
+        msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// Returns how many more governance tokens `address` may exit with in
+/// `window`.
+fn exit_allowance(
+    deps: Deps,
+    config: &Config,
+    window: &ExitWindow,
+    address: &Addr,
+) -> StdResult<Uint128> {
+    let staked: stake_cw20::msg::StakedBalanceAtHeightResponse = deps.querier.query_wasm_smart(
+        &config.staking_contract,
+        &stake_cw20::msg::QueryMsg::StakedBalanceAtHeight {
+            address: address.to_string(),
+            height: Some(window.opened_at),
+        },
+    )?;
+    let value = if window.total_staked.is_zero() {
+        Uint128::zero()
+    } else {
+        staked
+            .balance
+            .multiply_ratio(window.total_value, window.total_staked)
+    };
+    let exited = EXITED
+        .may_load(deps.storage, (window.opened_at, address))?
+        .unwrap_or_default();
+    Ok(value.saturating_sub(exited))
+}
+
+/// Computes the share of the DAO's treasury a member forfeiting
+/// `amount` governance tokens is owed. Tokens held by the DAO are not
+/// counted towards the supply.
+fn compute_exit_share(
+    deps: Deps,
+    config: &Config,
+    amount: Uint128,
+) -> StdResult<ExitShareResponse> {
+    let token_info: cw20::TokenInfoResponse = deps
+        .querier
+        .query_wasm_smart(&config.token, &cw20::Cw20QueryMsg::TokenInfo {})?;
+    let dao_balance: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+        &config.token,
+        &cw20::Cw20QueryMsg::Balance {
+            address: config.dao.to_string(),
+        },
+    )?;
+    let supply = token_info.total_supply.checked_sub(dao_balance.balance)?;
+    if supply.is_zero() {
+        return Ok(ExitShareResponse {
+            native: vec![],
+            cw20: vec![],
+        });
+    }
+
+    let native: Vec<Coin> = deps.querier.query_wasm_smart(
+        &config.dao,
+        &cw_core::msg::QueryMsg::NativeBalances {
+            start_at: None,
+            limit: None,
+        },
+    )?;
+    let native = native
+        .into_iter()
+        .map(|coin| (coin.amount.multiply_ratio(amount, supply), coin.denom))
+        .filter(|(share, _)| !share.is_zero())
+        .flat_map(|(share, denom)| coins(share.u128(), denom))
+        .collect();
+
+    let cw20: Vec<Cw20BalanceResponse> = deps.querier.query_wasm_smart(
+        &config.dao,
+        &cw_core::msg::QueryMsg::Cw20Balances {
+            start_at: None,
+            limit: None,
+        },
+    )?;
+    let cw20 = cw20
+        .into_iter()
+        .filter(|balance| balance.addr != config.token)
+        .map(|balance| Cw20Coin {
+            address: balance.addr.into_string(),
+            amount: balance.balance.multiply_ratio(amount, supply),
+        })
+        .filter(|coin| !coin.amount.is_zero())
+        .collect();
+
+    Ok(ExitShareResponse { native, cw20 })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => query_config(deps),
+        QueryMsg::ExitWindow {} => query_exit_window(deps),
+        QueryMsg::Timelocked { id } => query_timelocked(deps, id),
+        QueryMsg::ExitPreview { address } => query_exit_preview(deps, env, address),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::Dao {} => query_dao(deps),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<Binary> {
+    to_binary(&CONFIG.load(deps.storage)?)
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&cw_core_interface::voting::InfoResponse { info })
+}
+
+pub fn query_dao(deps: Deps) -> StdResult<Binary> {
+    to_binary(&CONFIG.load(deps.storage)?.dao)
+}
+
+pub fn query_exit_window(deps: Deps) -> StdResult<Binary> {
+    let window: Option<ExitWindow> = EXIT_WINDOW.may_load(deps.storage)?;
+    to_binary(&window)
+}
+
+pub fn query_timelocked(deps: Deps, id: u64) -> StdResult<Binary> {
+    let timelocked: Option<Timelocked> = TIMELOCKED.may_load(deps.storage, id)?;
+    to_binary(&timelocked)
+}
+
+pub fn query_exit_preview(deps: Deps, env: Env, address: String) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let window = EXIT_WINDOW
+        .may_load(deps.storage)?
+        .filter(|window| !window.closes.is_expired(&env.block));
+    let amount = match window {
+        Some(window) => {
+            let address = deps.api.addr_validate(&address)?;
+            exit_allowance(deps, &config, &window, &address)?
+        }
+        None => {
+            let staked: stake_cw20::msg::StakedValueResponse = deps.querier.query_wasm_smart(
+                &config.staking_contract,
+                &stake_cw20::msg::QueryMsg::StakedValue { address },
+            )?;
+            staked.value
+        }
+    };
+    to_binary(&compute_exit_share(deps, &config, amount)?)
+}
@@ -0,0 +1,33 @@
+use cosmwasm_std::{Addr, StdError, Uint128};
+use cw_utils::Expiration;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid token. Got ({received}), expected ({expected})")]
+    InvalidToken { received: Addr, expected: Addr },
+
+    #[error("Members may only exit while timelocked messages are pending")]
+    ExitWindowClosed {},
+
+    #[error("Exiting with this amount would not return any treasury funds")]
+    EmptyShare {},
+
+    #[error("Members may only exit with up to the value of their stake when the exit window opened ({allowance} remaining)")]
+    ExceedsStake { allowance: Uint128 },
+
+    #[error("Can not timelock an empty list of messages")]
+    NoMessages {},
+
+    #[error("Timelocked messages ({id}) may not be executed until ({executable_after})")]
+    Timelocked {
+        id: u64,
+        executable_after: Expiration,
+    },
+}
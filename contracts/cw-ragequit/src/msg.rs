@@ -0,0 +1,76 @@
+use cosmwasm_std::{Coin, CosmosMsg, Empty};
+use cw20::{Cw20Coin, Cw20ReceiveMsg};
+use cw_core_macros::govmod_query;
+use cw_utils::Duration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::ForfeitPolicy;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The stake-cw20 contract for the DAO's governance token.
+    pub staking_contract: String,
+    /// How long members have to exit before timelocked messages may
+    /// be executed.
+    pub exit_window: Duration,
+    /// What happens to forfeited governance tokens.
+    pub forfeit_policy: ForfeitPolicy,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Executed when governance tokens are sent to this contract.
+    Receive(Cw20ReceiveMsg),
+    /// Callable by the DAO. Schedules `msgs` to be executed by the
+    /// DAO once the exit window has passed and opens an exit window
+    /// if one is not already open.
+    Timelock { msgs: Vec<CosmosMsg<Empty>> },
+    /// Executes timelocked messages whose exit window has passed.
+    /// Callable by anyone.
+    ExecuteTimelocked { id: u64 },
+    /// Callable by the DAO. Updates the exit window and forfeit
+    /// policy.
+    UpdateConfig {
+        exit_window: Duration,
+        forfeit_policy: ForfeitPolicy,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    /// Forfeits the sent tokens in exchange for a share of the
+    /// treasury. Only allowed while an exit window is open and for up
+    /// to the value of the sender's stake when it opened.
+    Ragequit {},
+}
+
+#[govmod_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract's config. Returns Config.
+    Config {},
+    /// Returns the current or most recent exit window, if one has
+    /// ever been opened. Returns Option<ExitWindow>.
+    ExitWindow {},
+    /// Returns timelocked messages that have not yet been executed.
+    /// Returns Option<Timelocked>.
+    Timelocked { id: u64 },
+    /// Returns the share of the treasury `address` would receive if
+    /// it exited with all it may. While an exit window is open this
+    /// is its remaining allowance, otherwise its currently staked
+    /// value. Returns ExitShareResponse.
+    ExitPreview { address: String },
+}
+
+/// A share of the DAO's treasury.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExitShareResponse {
+    /// Native tokens in the share.
+    pub native: Vec<Coin>,
+    /// Cw20 tokens in the share.
+    pub cw20: Vec<Cw20Coin>,
+}
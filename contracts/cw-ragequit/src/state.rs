@@ -0,0 +1,64 @@
+use cosmwasm_std::{Addr, CosmosMsg, Empty, Uint128};
+use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// What happens to governance tokens forfeited by exiting members.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ForfeitPolicy {
+    /// The tokens are burned.
+    Burn {},
+    /// The tokens are sent to the DAO's treasury.
+    SendToDao {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The DAO whose treasury exiting members receive a share of.
+    pub dao: Addr,
+    /// The staking contract for the DAO's governance token.
+    pub staking_contract: Addr,
+    /// The DAO's governance token.
+    pub token: Addr,
+    /// How long members have to exit before timelocked messages may
+    /// be executed.
+    pub exit_window: Duration,
+    /// What happens to forfeited governance tokens.
+    pub forfeit_policy: ForfeitPolicy,
+}
+
+/// A period in which members may exit.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExitWindow {
+    /// The height the window opened at. Members may exit with up to
+    /// the value of their stake at the start of this block.
+    pub opened_at: u64,
+    /// When the window closes.
+    pub closes: Expiration,
+    /// The staking contract's total staked balance when the window
+    /// opened.
+    pub total_staked: Uint128,
+    /// The staking contract's total value when the window opened.
+    pub total_value: Uint128,
+}
+
+/// Messages the DAO has scheduled for execution once members have had
+/// a chance to exit.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Timelocked {
+    /// The messages, executed by the DAO.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// When the messages may be executed.
+    pub executable_after: Expiration,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The current or most recent exit window.
+pub const EXIT_WINDOW: Item<ExitWindow> = Item::new("exit_window");
+/// The amount each member has exited with, keyed by the height the
+/// exit window they exited in opened at.
+pub const EXITED: Map<(u64, &Addr), Uint128> = Map::new("exited");
+pub const TIMELOCKED: Map<u64, Timelocked> = Map::new("timelocked");
+pub const TIMELOCK_COUNT: Item<u64> = Item::new("timelock_count");
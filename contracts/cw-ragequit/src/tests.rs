@@ -0,0 +1,801 @@
+use cosmwasm_std::{coin, coins, to_binary, Addr, BankMsg, CosmosMsg, Empty, Uint128};
+use cw20::Cw20Coin;
+use cw_core::msg::{Admin, ModuleInstantiateInfo};
+use cw_core::state::ProposalModule;
+use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+use cw_utils::{Duration, Expiration};
+
+use crate::{
+    msg::{ExecuteMsg, ExitShareResponse, InstantiateMsg, QueryMsg, ReceiveMsg},
+    state::{ExitWindow, ForfeitPolicy, Timelocked},
+    ContractError,
+};
+
+const LEAVER: &str = "leaver";
+const STAYER: &str = "stayer";
+
+fn ragequit_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw_core_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_core::contract::execute,
+        cw_core::contract::instantiate,
+        cw_core::contract::query,
+    )
+    .with_reply(cw_core::contract::reply);
+    Box::new(contract)
+}
+
+fn sudo_proposal_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_proposal_sudo::contract::execute,
+        cw_proposal_sudo::contract::instantiate,
+        cw_proposal_sudo::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw20_balances_voting() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_balance_voting::contract::execute,
+        cw20_balance_voting::contract::instantiate,
+        cw20_balance_voting::contract::query,
+    )
+    .with_reply(cw20_balance_voting::contract::reply);
+    Box::new(contract)
+}
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn stake_cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        stake_cw20::contract::execute,
+        stake_cw20::contract::instantiate,
+        stake_cw20::contract::query,
+    );
+    Box::new(contract)
+}
+
+struct Setup {
+    app: App,
+    dao: Addr,
+    staking: Addr,
+    ragequit: Addr,
+    gov_token: Addr,
+    treasury_token: Addr,
+}
+
+/// Creates a DAO whose governance token is split 60/40 between
+/// `STAYER` and `LEAVER`. The DAO's treasury holds 1000 ujuno and
+/// 500 of another cw20 token.
+fn setup(forfeit_policy: ForfeitPolicy) -> Setup {
+    let mut app = App::default();
+    let core_id = app.store_code(cw_core_contract());
+    let sudo_id = app.store_code(sudo_proposal_contract());
+    let voting_id = app.store_code(cw20_balances_voting());
+    let cw20_id = app.store_code(cw20_contract());
+    let stake_id = app.store_code(stake_cw20_contract());
+    let ragequit_id = app.store_code(ragequit_contract());
+
+    let voting_instantiate = cw20_balance_voting::msg::InstantiateMsg {
+        token_info: cw20_balance_voting::msg::TokenInfo::New {
+            code_id: cw20_id,
+            label: "DAO DAO voting".to_string(),
+            name: "DAO DAO".to_string(),
+            symbol: "DAO".to_string(),
+            decimals: 6,
+            initial_balances: vec![
+                Cw20Coin {
+                    address: STAYER.to_string(),
+                    amount: Uint128::new(60),
+                },
+                Cw20Coin {
+                    address: LEAVER.to_string(),
+                    amount: Uint128::new(40),
+                },
+            ],
+            marketing: None,
+        },
+    };
+    let dao = app
+        .instantiate_contract(
+            core_id,
+            Addr::unchecked(STAYER),
+            &cw_core::msg::InstantiateMsg {
+                name: "DAO DAO".to_string(),
+                description: "A DAO that builds DAOs.".to_string(),
+                image_url: None,
+                automatically_add_cw20s: true,
+                automatically_add_cw721s: true,
+                voting_module_instantiate_info: ModuleInstantiateInfo {
+                    code_id: voting_id,
+                    msg: to_binary(&voting_instantiate).unwrap(),
+                    admin: Admin::CoreContract {},
+                    label: "voting module".to_string(),
+                },
+                proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+                    code_id: sudo_id,
+                    msg: to_binary(&cw_proposal_sudo::msg::InstantiateMsg {
+                        root: STAYER.to_string(),
+                    })
+                    .unwrap(),
+                    admin: Admin::CoreContract {},
+                    label: "proposal module".to_string(),
+                }],
+                initial_items: None,
+                pause_guardian: None,
+                parent_dao: None,
+                admin: None,
+            },
+            &[],
+            "DAO DAO",
+            None,
+        )
+        .unwrap();
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            dao.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_at: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = proposal_modules[0].address.clone();
+
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(dao.clone(), &cw_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let gov_token: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &cw_core_interface::voting::Query::TokenContract {},
+        )
+        .unwrap();
+    let staking = app
+        .instantiate_contract(
+            stake_id,
+            Addr::unchecked(STAYER),
+            &stake_cw20::msg::InstantiateMsg {
+                owner: Some(dao.to_string()),
+                manager: None,
+                token_address: gov_token.to_string(),
+                unstaking_duration: None,
+            },
+            &[],
+            "staking",
+            None,
+        )
+        .unwrap();
+
+    // Fund the treasury. The cw20 is registered automatically when
+    // it is sent to the DAO.
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &dao, vec![coin(1000, "ujuno")])
+            .unwrap();
+    });
+    let treasury_token = app
+        .instantiate_contract(
+            cw20_id,
+            Addr::unchecked(STAYER),
+            &cw20_base::msg::InstantiateMsg {
+                name: "Treasury".to_string(),
+                symbol: "TRES".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: STAYER.to_string(),
+                    amount: Uint128::new(500),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "treasury token",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(STAYER),
+        treasury_token.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: dao.to_string(),
+            amount: Uint128::new(500),
+            msg: to_binary("").unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        dao.clone(),
+        dao.clone(),
+        &cw_core::msg::ExecuteMsg::UpdateNativeList {
+            to_add: vec!["ujuno".to_string()],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The DAO opts in by adding the ragequit contract as a proposal
+    // module.
+    app.execute_contract(
+        dao.clone(),
+        dao.clone(),
+        &cw_core::msg::ExecuteMsg::UpdateProposalModules {
+            to_add: vec![ModuleInstantiateInfo {
+                code_id: ragequit_id,
+                msg: to_binary(&InstantiateMsg {
+                    staking_contract: staking.to_string(),
+                    exit_window: Duration::Height(10),
+                    forfeit_policy,
+                })
+                .unwrap(),
+                admin: Admin::CoreContract {},
+                label: "ragequit".to_string(),
+            }],
+            to_disable: vec![],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let ragequit = app
+        .wrap()
+        .query_wasm_smart::<Vec<ProposalModule>>(
+            dao.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_at: None,
+                limit: None,
+            },
+        )
+        .unwrap()
+        .into_iter()
+        .find(|module| module.address != proposal_module)
+        .unwrap()
+        .address;
+
+    Setup {
+        app,
+        dao,
+        staking,
+        ragequit,
+        gov_token,
+        treasury_token,
+    }
+}
+
+fn stake(app: &mut App, gov_token: &Addr, staking: &Addr, sender: &str, amount: u128) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        gov_token.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: staking.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_binary(&stake_cw20::msg::ReceiveMsg::Stake {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn unstake(app: &mut App, staking: &Addr, sender: &str, amount: u128) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        staking.clone(),
+        &stake_cw20::msg::ExecuteMsg::Unstake {
+            amount: Uint128::new(amount),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+/// Timelocks a payment of `amount` ujuno from the DAO to `STAYER`.
+fn timelock(app: &mut App, dao: &Addr, ragequit: &Addr, amount: u128) -> anyhow::Result<()> {
+    app.execute_contract(
+        dao.clone(),
+        ragequit.clone(),
+        &ExecuteMsg::Timelock {
+            msgs: vec![BankMsg::Send {
+                to_address: STAYER.to_string(),
+                amount: coins(amount, "ujuno"),
+            }
+            .into()],
+        },
+        &[],
+    )
+    .map(|_| ())
+}
+
+fn ragequit(
+    app: &mut App,
+    gov_token: &Addr,
+    ragequit: &Addr,
+    sender: &str,
+    amount: u128,
+) -> anyhow::Result<()> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        gov_token.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: ragequit.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_binary(&ReceiveMsg::Ragequit {}).unwrap(),
+        },
+        &[],
+    )
+    .map(|_| ())
+}
+
+fn exit_window(app: &App, ragequit: &Addr) -> Option<ExitWindow> {
+    app.wrap()
+        .query_wasm_smart(ragequit, &QueryMsg::ExitWindow {})
+        .unwrap()
+}
+
+fn exit_preview(app: &App, ragequit: &Addr, address: &str) -> ExitShareResponse {
+    app.wrap()
+        .query_wasm_smart(
+            ragequit,
+            &QueryMsg::ExitPreview {
+                address: address.to_string(),
+            },
+        )
+        .unwrap()
+}
+
+fn cw20_balance(app: &App, token: &Addr, address: &str) -> Uint128 {
+    let res: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    res.balance
+}
+
+#[test]
+fn test_ragequit() {
+    let Setup {
+        mut app,
+        dao,
+        staking,
+        ragequit: ragequit_addr,
+        gov_token,
+        treasury_token,
+    } = setup(ForfeitPolicy::Burn {});
+
+    // Nothing is timelocked so the exit window is closed.
+    let err: ContractError = ragequit(&mut app, &gov_token, &ragequit_addr, LEAVER, 40)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ExitWindowClosed {});
+
+    stake(&mut app, &gov_token, &staking, LEAVER, 40);
+    app.update_block(next_block);
+
+    // Only the DAO may timelock messages.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(STAYER),
+            ragequit_addr.clone(),
+            &ExecuteMsg::Timelock { msgs: vec![] },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    timelock(&mut app, &dao, &ragequit_addr, 100).unwrap();
+    let height = app.block_info().height;
+    assert_eq!(
+        exit_window(&app, &ragequit_addr),
+        Some(ExitWindow {
+            opened_at: height,
+            closes: Expiration::AtHeight(height + 10),
+            total_staked: Uint128::new(40),
+            total_value: Uint128::new(40),
+        })
+    );
+    let timelocked: Option<Timelocked> = app
+        .wrap()
+        .query_wasm_smart(ragequit_addr.clone(), &QueryMsg::Timelocked { id: 1 })
+        .unwrap();
+    assert_eq!(
+        timelocked,
+        Some(Timelocked {
+            msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: STAYER.to_string(),
+                amount: coins(100, "ujuno"),
+            })],
+            executable_after: Expiration::AtHeight(height + 10),
+        })
+    );
+
+    // The timelocked messages can not be executed before members have
+    // had a chance to exit.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(STAYER),
+            ragequit_addr.clone(),
+            &ExecuteMsg::ExecuteTimelocked { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::Timelocked {
+            id: 1,
+            executable_after: Expiration::AtHeight(height + 10)
+        }
+    );
+
+    unstake(&mut app, &staking, LEAVER, 40);
+    ragequit(&mut app, &gov_token, &ragequit_addr, LEAVER, 40).unwrap();
+
+    assert_eq!(
+        app.wrap().query_balance(LEAVER, "ujuno").unwrap().amount,
+        Uint128::new(400)
+    );
+    assert_eq!(
+        cw20_balance(&app, &treasury_token, LEAVER),
+        Uint128::new(200)
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(dao.clone(), "ujuno")
+            .unwrap()
+            .amount,
+        Uint128::new(600)
+    );
+    let token_info: cw20::TokenInfoResponse = app
+        .wrap()
+        .query_wasm_smart(gov_token, &cw20::Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(token_info.total_supply, Uint128::new(60));
+
+    app.update_block(|block| block.height += 10);
+    app.execute_contract(
+        Addr::unchecked(LEAVER),
+        ragequit_addr.clone(),
+        &ExecuteMsg::ExecuteTimelocked { id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(STAYER, "ujuno").unwrap().amount,
+        Uint128::new(100)
+    );
+    assert_eq!(
+        app.wrap().query_balance(dao, "ujuno").unwrap().amount,
+        Uint128::new(500)
+    );
+
+    // Timelocked messages are only executed once.
+    let timelocked: Option<Timelocked> = app
+        .wrap()
+        .query_wasm_smart(ragequit_addr.clone(), &QueryMsg::Timelocked { id: 1 })
+        .unwrap();
+    assert_eq!(timelocked, None);
+    app.execute_contract(
+        Addr::unchecked(LEAVER),
+        ragequit_addr,
+        &ExecuteMsg::ExecuteTimelocked { id: 1 },
+        &[],
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_unstaked_tokens_can_not_exit() {
+    let Setup {
+        mut app,
+        dao,
+        staking,
+        ragequit: ragequit_addr,
+        gov_token,
+        ..
+    } = setup(ForfeitPolicy::Burn {});
+
+    // Tokens staked in the block the window opens do not count
+    // towards a member's allowance.
+    stake(&mut app, &gov_token, &staking, LEAVER, 40);
+    timelock(&mut app, &dao, &ragequit_addr, 100).unwrap();
+
+    let err: ContractError = ragequit(&mut app, &gov_token, &ragequit_addr, STAYER, 60)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ExceedsStake {
+            allowance: Uint128::zero()
+        }
+    );
+
+    assert_eq!(
+        exit_preview(&app, &ragequit_addr, LEAVER),
+        ExitShareResponse {
+            native: vec![],
+            cw20: vec![],
+        }
+    );
+    unstake(&mut app, &staking, LEAVER, 40);
+    let err: ContractError = ragequit(&mut app, &gov_token, &ragequit_addr, LEAVER, 40)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ExceedsStake {
+            allowance: Uint128::zero()
+        }
+    );
+}
+
+#[test]
+fn test_exit_allowance() {
+    let Setup {
+        mut app,
+        dao,
+        staking,
+        ragequit: ragequit_addr,
+        gov_token,
+        ..
+    } = setup(ForfeitPolicy::Burn {});
+
+    // The leaver keeps 10 tokens liquid. These do not count towards
+    // their allowance.
+    stake(&mut app, &gov_token, &staking, LEAVER, 30);
+    app.update_block(next_block);
+    timelock(&mut app, &dao, &ragequit_addr, 100).unwrap();
+    let opened_at = app.block_info().height;
+
+    unstake(&mut app, &staking, LEAVER, 30);
+    ragequit(&mut app, &gov_token, &ragequit_addr, LEAVER, 20).unwrap();
+    let err: ContractError = ragequit(&mut app, &gov_token, &ragequit_addr, LEAVER, 20)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ExceedsStake {
+            allowance: Uint128::new(10)
+        }
+    );
+
+    // Timelocking more messages while the window is open extends it
+    // without resetting members' allowances.
+    app.update_block(|block| block.height += 5);
+    timelock(&mut app, &dao, &ragequit_addr, 100).unwrap();
+    let window = exit_window(&app, &ragequit_addr).unwrap();
+    assert_eq!(window.opened_at, opened_at);
+    assert_eq!(window.closes, Expiration::AtHeight(opened_at + 15));
+    let timelocked: Option<Timelocked> = app
+        .wrap()
+        .query_wasm_smart(ragequit_addr.clone(), &QueryMsg::Timelocked { id: 2 })
+        .unwrap();
+    assert_eq!(
+        timelocked.unwrap().executable_after,
+        Expiration::AtHeight(opened_at + 15)
+    );
+
+    let err: ContractError = ragequit(&mut app, &gov_token, &ragequit_addr, LEAVER, 20)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ExceedsStake {
+            allowance: Uint128::new(10)
+        }
+    );
+    ragequit(&mut app, &gov_token, &ragequit_addr, LEAVER, 10).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(LEAVER, "ujuno").unwrap().amount,
+        Uint128::new(300)
+    );
+
+    // The first timelock's window has passed even though the second
+    // one's has not.
+    app.update_block(|block| block.height += 5);
+    app.execute_contract(
+        Addr::unchecked(STAYER),
+        ragequit_addr,
+        &ExecuteMsg::ExecuteTimelocked { id: 1 },
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_exit_window_closes() {
+    let Setup {
+        mut app,
+        dao,
+        staking,
+        ragequit: ragequit_addr,
+        gov_token,
+        ..
+    } = setup(ForfeitPolicy::SendToDao {});
+
+    let err: ContractError = app
+        .execute_contract(
+            dao.clone(),
+            ragequit_addr.clone(),
+            &ExecuteMsg::Timelock { msgs: vec![] },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoMessages {});
+
+    stake(&mut app, &gov_token, &staking, LEAVER, 40);
+    app.update_block(next_block);
+    timelock(&mut app, &dao, &ragequit_addr, 100).unwrap();
+    app.update_block(|block| block.height += 10);
+
+    unstake(&mut app, &staking, LEAVER, 40);
+    let err: ContractError = ragequit(&mut app, &gov_token, &ragequit_addr, LEAVER, 40)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ExitWindowClosed {});
+
+    // A new window snapshots stakes again.
+    stake(&mut app, &gov_token, &staking, LEAVER, 40);
+    app.update_block(next_block);
+    timelock(&mut app, &dao, &ragequit_addr, 100).unwrap();
+    assert_eq!(
+        exit_window(&app, &ragequit_addr).unwrap().opened_at,
+        app.block_info().height
+    );
+    unstake(&mut app, &staking, LEAVER, 40);
+    ragequit(&mut app, &gov_token, &ragequit_addr, LEAVER, 40).unwrap();
+    assert_eq!(
+        cw20_balance(&app, &gov_token, dao.as_str()),
+        Uint128::new(40)
+    );
+}
+
+#[test]
+fn test_forfeit_to_dao_and_preview() {
+    let Setup {
+        mut app,
+        dao,
+        staking,
+        ragequit: ragequit_addr,
+        gov_token,
+        treasury_token,
+    } = setup(ForfeitPolicy::SendToDao {});
+
+    stake(&mut app, &gov_token, &staking, STAYER, 30);
+    stake(&mut app, &gov_token, &staking, LEAVER, 40);
+    app.update_block(next_block);
+
+    // Without an open window the preview uses the staked value.
+    assert_eq!(
+        exit_preview(&app, &ragequit_addr, STAYER),
+        ExitShareResponse {
+            native: vec![coin(300, "ujuno")],
+            cw20: vec![Cw20Coin {
+                address: treasury_token.to_string(),
+                amount: Uint128::new(150),
+            }],
+        }
+    );
+
+    timelock(&mut app, &dao, &ragequit_addr, 100).unwrap();
+    unstake(&mut app, &staking, LEAVER, 40);
+    ragequit(&mut app, &gov_token, &ragequit_addr, LEAVER, 20).unwrap();
+    assert_eq!(
+        cw20_balance(&app, &gov_token, dao.as_str()),
+        Uint128::new(20)
+    );
+    assert_eq!(
+        app.wrap().query_balance(LEAVER, "ujuno").unwrap().amount,
+        Uint128::new(200)
+    );
+
+    // Tokens held by the DAO do not count towards the supply, so the
+    // leaver's remaining 20 tokens are a quarter of the remaining
+    // 80. While the window is open the preview uses the remaining
+    // allowance.
+    assert_eq!(
+        exit_preview(&app, &ragequit_addr, LEAVER),
+        ExitShareResponse {
+            native: vec![coin(200, "ujuno")],
+            cw20: vec![Cw20Coin {
+                address: treasury_token.to_string(),
+                amount: Uint128::new(100),
+            }],
+        }
+    );
+    ragequit(&mut app, &gov_token, &ragequit_addr, LEAVER, 20).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(LEAVER, "ujuno").unwrap().amount,
+        Uint128::new(400)
+    );
+}
+
+#[test]
+fn test_ragequit_does_not_keep_dao_governable() {
+    let Setup {
+        mut app,
+        dao,
+        ragequit,
+        ..
+    } = setup(ForfeitPolicy::Burn {});
+    let proposal_module = app
+        .wrap()
+        .query_wasm_smart::<Vec<ProposalModule>>(
+            dao.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_at: None,
+                limit: None,
+            },
+        )
+        .unwrap()
+        .into_iter()
+        .find(|module| module.address != ragequit)
+        .unwrap()
+        .address;
+
+    // Ragequit can not create proposals so the DAO may not disable
+    // its only other proposal module.
+    let err: cw_core::ContractError = app
+        .execute_contract(
+            dao.clone(),
+            dao.clone(),
+            &cw_core::msg::ExecuteMsg::UpdateProposalModules {
+                to_add: vec![],
+                to_disable: vec![proposal_module.to_string()],
+                to_remove: vec![],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, cw_core::ContractError::NoProposalModule {});
+
+    // Ragequit may be disabled.
+    app.execute_contract(
+        dao.clone(),
+        dao,
+        &cw_core::msg::ExecuteMsg::UpdateProposalModules {
+            to_add: vec![],
+            to_disable: vec![ragequit.to_string()],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+}
@@ -0,0 +1,194 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+};
+use cw2::set_contract_version;
+use cw_core_interface::voting::{
+    self, IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use stake_cw20::hooks::StakeChangedHookMsg;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{DAO, POWERS, TOTAL_POWER, TRANSFORM, VOTING_MODULE};
+
+const CONTRACT_NAME: &str = "crates.io:cw-transformed-voting";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    msg.transform.validate()?;
+
+    let voting_module = deps.api.addr_validate(&msg.voting_module)?;
+    VOTING_MODULE.save(deps.storage, &voting_module)?;
+    let staking_contract = load_staking_contract(deps.as_ref())?;
+
+    DAO.save(deps.storage, &info.sender)?;
+    TRANSFORM.save(deps.storage, &msg.transform)?;
+    TOTAL_POWER.save(deps.storage, &Uint128::zero(), env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", info.sender)
+        .add_attribute("voting_module", voting_module)
+        .add_attribute("staking_contract", staking_contract))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::StakeChangeHook(msg) => execute_stake_changed(deps, env, info, msg),
+        ExecuteMsg::Refresh { addresses } => execute_refresh(deps, env, addresses),
+    }
+}
+
+/// Loads the wrapped voting module's current staking contract. The
+/// DAO may change it, so it is not stored here.
+fn load_staking_contract(deps: Deps) -> StdResult<Addr> {
+    deps.querier.query_wasm_smart(
+        VOTING_MODULE.load(deps.storage)?,
+        &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+    )
+}
+
+pub fn execute_stake_changed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: StakeChangedHookMsg,
+) -> Result<Response, ContractError> {
+    let staking_contract = load_staking_contract(deps.as_ref())?;
+    if info.sender != staking_contract {
+        return Err(ContractError::InvalidHookSender {});
+    }
+    let addr = match msg {
+        StakeChangedHookMsg::Stake { addr, .. } => addr,
+        StakeChangedHookMsg::Unstake { addr, .. } => addr,
+    };
+    let power = update_power(deps, &env, &addr)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "stake_change_hook")
+        .add_attribute("address", addr)
+        .add_attribute("power", power))
+}
+
+pub fn execute_refresh(
+    mut deps: DepsMut,
+    env: Env,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    for address in &addresses {
+        let address = deps.api.addr_validate(address)?;
+        update_power(deps.branch(), &env, &address)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "refresh")
+        .add_attribute("count", addresses.len().to_string()))
+}
+
+/// Recomputes the transformed voting power of `address` from its
+/// current staked balance and updates the total power to match.
+fn update_power(deps: DepsMut, env: &Env, address: &Addr) -> StdResult<Uint128> {
+    let staking_contract = load_staking_contract(deps.as_ref())?;
+    let transform = TRANSFORM.load(deps.storage)?;
+
+    // The staking contract's snapshots do not reflect changes made
+    // in the current block until the next one. Querying at the next
+    // height returns the balances as they are now.
+    let height = Some(env.block.height + 1);
+    let balance: stake_cw20::msg::StakedBalanceAtHeightResponse = deps.querier.query_wasm_smart(
+        staking_contract,
+        &stake_cw20::msg::QueryMsg::StakedBalanceAtHeight {
+            address: address.to_string(),
+            height,
+        },
+    )?;
+
+    let power = transform.apply(balance.balance)?;
+    let old = POWERS.may_load(deps.storage, address)?.unwrap_or_default();
+    if power != old {
+        POWERS.save(deps.storage, address, &power, env.block.height)?;
+        TOTAL_POWER.update(deps.storage, env.block.height, |total| -> StdResult<_> {
+            Ok(total
+                .unwrap_or_default()
+                .checked_sub(old)?
+                .checked_add(power)?)
+        })?;
+    }
+    Ok(power)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            query_voting_power_at_height(deps, env, address, height)
+        }
+        QueryMsg::TotalPowerAtHeight { height } => query_total_power_at_height(deps, env, height),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::IsActive {} => query_is_active(deps),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::VotingModule {} => to_binary(&VOTING_MODULE.load(deps.storage)?),
+        QueryMsg::StakingContract {} => to_binary(&load_staking_contract(deps)?),
+        QueryMsg::Transform {} => to_binary(&TRANSFORM.load(deps.storage)?),
+    }
+}
+
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+    let power = POWERS
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+    let total = TOTAL_POWER
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+    let power = TRANSFORM.load(deps.storage)?.cap(power, total);
+    to_binary(&VotingPowerAtHeightResponse { power, height })
+}
+
+pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    let height = height.unwrap_or(env.block.height);
+    let power = TOTAL_POWER
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+    to_binary(&TotalPowerAtHeightResponse { power, height })
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&voting::InfoResponse { info })
+}
+
+pub fn query_is_active(deps: Deps) -> StdResult<Binary> {
+    // Voting modules are not required to implement this
+    // query. Lacking an implementation they are active by default.
+    let active = deps
+        .querier
+        .query_wasm_smart(
+            VOTING_MODULE.load(deps.storage)?,
+            &voting::Query::IsActive {},
+        )
+        .unwrap_or(IsActiveResponse { active: true })
+        .active;
+    to_binary(&IsActiveResponse { active })
+}
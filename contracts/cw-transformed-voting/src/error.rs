@@ -0,0 +1,26 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Stake change hooks may only be sent by the staking contract")]
+    InvalidHookSender {},
+
+    #[error("Voting power caps must be greater than zero")]
+    ZeroCap {},
+
+    #[error("Percentage caps must be greater than 0% and at most 100%")]
+    InvalidPercentageCap {},
+
+    #[error("Piecewise transforms must have at least one segment and the first segment must start at zero")]
+    InvalidFirstSegment {},
+
+    #[error("Piecewise segment starts must be strictly increasing")]
+    UnorderedSegments {},
+
+    #[error("Piecewise segment rates must be at most one")]
+    InvalidRate {},
+}
@@ -0,0 +1,65 @@
+use cosmwasm_std::{Decimal, Uint128};
+use cw_core_macros::{active_query, voting_query};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use stake_cw20::hooks::StakeChangedHookMsg;
+
+/// A segment of a piecewise transform. Staked tokens above `start`,
+/// and below the start of the next segment, are each worth `rate`
+/// voting power. `rate` may be at most one.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Segment {
+    pub start: Uint128,
+    pub rate: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Transform {
+    /// Voting power is the square root of the staked balance.
+    SquareRoot {},
+    /// Voting power is the staked balance, up to `cap`.
+    AbsoluteCap { cap: Uint128 },
+    /// Voting power is the staked balance, up to `percent` of the
+    /// total voting power at the same height. The total voting power
+    /// is the sum of the uncapped staked balances.
+    PercentageCap { percent: Decimal },
+    /// Voting power is computed from a piecewise linear function of
+    /// the staked balance. Segments must be ordered by `start` and
+    /// the first segment must start at zero.
+    Piecewise { segments: Vec<Segment> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The address of a cw20-staked-balance-voting module whose
+    /// voting power should be transformed.
+    pub voting_module: String,
+    pub transform: Transform,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Called by the staking contract when an address stakes or
+    /// unstakes. This contract must be registered as a hook on the
+    /// staking contract for voting power to be tracked.
+    StakeChangeHook(StakeChangedHookMsg),
+    /// Recomputes the voting power of `addresses` from their current
+    /// staked balances. May be called by anyone. Used to pick up
+    /// addresses that staked before this contract was registered as
+    /// a hook or before the voting module's staking contract was
+    /// changed.
+    Refresh { addresses: Vec<String> },
+}
+
+#[voting_query]
+#[active_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Dao {},
+    VotingModule {},
+    StakingContract {},
+    Transform {},
+}
@@ -0,0 +1,24 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+
+use crate::msg::Transform;
+
+pub const DAO: Item<Addr> = Item::new("dao");
+pub const VOTING_MODULE: Item<Addr> = Item::new("voting_module");
+pub const TRANSFORM: Item<Transform> = Item::new("transform");
+
+/// The transformed voting power of each address.
+pub const POWERS: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "powers",
+    "powers__checkpoints",
+    "powers__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The sum of all transformed voting powers.
+pub const TOTAL_POWER: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_power",
+    "total_power__checkpoints",
+    "total_power__changelog",
+    Strategy::EveryBlock,
+);
@@ -0,0 +1,574 @@
+use cosmwasm_std::{to_binary, Addr, Decimal, Empty, Uint128};
+use cw20::Cw20Coin;
+use cw_core_interface::voting::{TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
+use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+use stake_cw20::hooks::StakeChangedHookMsg;
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg, Segment, Transform},
+    transform::isqrt,
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+const ADDR3: &str = "addr3";
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn staking_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        stake_cw20::contract::execute,
+        stake_cw20::contract::instantiate,
+        stake_cw20::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn staked_balance_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_staked_balance_voting::contract::execute,
+        cw20_staked_balance_voting::contract::instantiate,
+        cw20_staked_balance_voting::contract::query,
+    )
+    .with_reply(cw20_staked_balance_voting::contract::reply);
+    Box::new(contract)
+}
+
+fn transformed_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+struct TestCase {
+    voting: Addr,
+    staking: Addr,
+    token: Addr,
+}
+
+/// Instantiates a cw20 staked balance voting module where ADDR1,
+/// ADDR2, and ADDR3 hold 10_000, 100, and 400 tokens.
+fn instantiate_staked_balance_voting(app: &mut App) -> (Addr, Addr, Addr) {
+    let cw20_id = app.store_code(cw20_contract());
+    let staking_id = app.store_code(staking_contract());
+    let voting_id = app.store_code(staked_balance_voting_contract());
+
+    let voting_module = app
+        .instantiate_contract(
+            voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw20_staked_balance_voting::msg::InstantiateMsg {
+                token_info: cw20_staked_balance_voting::msg::TokenInfo::New {
+                    code_id: cw20_id,
+                    label: "DAO DAO voting".to_string(),
+                    name: "DAO DAO".to_string(),
+                    symbol: "DAO".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![
+                        Cw20Coin {
+                            address: ADDR1.to_string(),
+                            amount: Uint128::new(10_000),
+                        },
+                        Cw20Coin {
+                            address: ADDR2.to_string(),
+                            amount: Uint128::new(100),
+                        },
+                        Cw20Coin {
+                            address: ADDR3.to_string(),
+                            amount: Uint128::new(400),
+                        },
+                    ],
+                    marketing: None,
+                    staking_code_id: staking_id,
+                    unstaking_duration: None,
+                    initial_dao_balance: None,
+                },
+                active_threshold: None,
+            },
+            &[],
+            "voting module",
+            None,
+        )
+        .unwrap();
+
+    let staking: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module.clone(),
+            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+        )
+        .unwrap();
+    let token: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module.clone(),
+            &cw20_staked_balance_voting::msg::QueryMsg::TokenContract {},
+        )
+        .unwrap();
+    (voting_module, staking, token)
+}
+
+/// Instantiates a transformed voting module wrapping a staked balance
+/// voting module and registers it as a hook on the staking
+/// contract. ADDR3 stakes before the hook is registered.
+fn setup_test_case(app: &mut App, transform: Transform) -> TestCase {
+    let (voting_module, staking, token) = instantiate_staked_balance_voting(app);
+    stake(app, &staking, &token, ADDR3, 400);
+
+    let transformed_id = app.store_code(transformed_voting_contract());
+    let voting = app
+        .instantiate_contract(
+            transformed_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                voting_module: voting_module.to_string(),
+                transform,
+            },
+            &[],
+            "transformed voting",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        staking.clone(),
+        &stake_cw20::msg::ExecuteMsg::AddHook {
+            addr: voting.to_string(),
+            gas_limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    TestCase {
+        voting,
+        staking,
+        token,
+    }
+}
+
+fn stake(app: &mut App, staking: &Addr, token: &Addr, sender: &str, amount: u128) {
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&stake_cw20::msg::ReceiveMsg::Stake {}).unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(sender), token.clone(), &msg, &[])
+        .unwrap();
+}
+
+fn voting_power(app: &App, voting: &Addr, address: &str, height: Option<u64>) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+fn total_power(app: &App, voting: &Addr, height: Option<u64>) -> Uint128 {
+    let res: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(voting, &QueryMsg::TotalPowerAtHeight { height })
+        .unwrap();
+    res.power
+}
+
+#[test]
+fn test_isqrt() {
+    assert_eq!(isqrt(0), 0);
+    assert_eq!(isqrt(1), 1);
+    assert_eq!(isqrt(3), 1);
+    assert_eq!(isqrt(4), 2);
+    assert_eq!(isqrt(99), 9);
+    assert_eq!(isqrt(10_000), 100);
+    assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+}
+
+#[test]
+fn test_square_root() {
+    let mut app = App::default();
+    let TestCase {
+        voting,
+        staking,
+        token,
+    } = setup_test_case(&mut app, Transform::SquareRoot {});
+
+    stake(&mut app, &staking, &token, ADDR1, 10_000);
+    stake(&mut app, &staking, &token, ADDR2, 100);
+    app.update_block(next_block);
+    let staked = app.block_info().height;
+
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(100));
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(10));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(110));
+
+    // ADDR3 staked before the hook was registered and has no power
+    // until refreshed.
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::zero());
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting.clone(),
+        &ExecuteMsg::Refresh {
+            addresses: vec![ADDR3.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::new(20));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(130));
+
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        staking,
+        &stake_cw20::msg::ExecuteMsg::Unstake {
+            amount: Uint128::new(7_500),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(50));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(80));
+
+    // Past heights are unchanged.
+    assert_eq!(
+        voting_power(&app, &voting, ADDR1, Some(staked)),
+        Uint128::new(100)
+    );
+    assert_eq!(total_power(&app, &voting, Some(staked)), Uint128::new(110));
+}
+
+#[test]
+fn test_caps() {
+    let mut app = App::default();
+    let TestCase {
+        voting,
+        staking,
+        token,
+    } = setup_test_case(
+        &mut app,
+        Transform::AbsoluteCap {
+            cap: Uint128::new(1_000),
+        },
+    );
+    stake(&mut app, &staking, &token, ADDR1, 10_000);
+    stake(&mut app, &staking, &token, ADDR2, 100);
+    app.update_block(next_block);
+    assert_eq!(
+        voting_power(&app, &voting, ADDR1, None),
+        Uint128::new(1_000)
+    );
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(100));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(1_100));
+
+    let TestCase {
+        voting,
+        staking,
+        token,
+    } = setup_test_case(
+        &mut app,
+        Transform::PercentageCap {
+            percent: Decimal::percent(50),
+        },
+    );
+    stake(&mut app, &staking, &token, ADDR2, 100);
+    app.update_block(next_block);
+    let before = app.block_info().height;
+    stake(&mut app, &staking, &token, ADDR1, 10_000);
+    app.update_block(next_block);
+
+    // 10_100 staked in total, so each address is capped at 5_050.
+    assert_eq!(
+        voting_power(&app, &voting, ADDR1, None),
+        Uint128::new(5_050)
+    );
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(100));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(10_100));
+
+    // Before ADDR1 staked ADDR2 was capped at half of 100.
+    assert_eq!(
+        voting_power(&app, &voting, ADDR2, Some(before)),
+        Uint128::new(50)
+    );
+
+    // Refreshing ADDR3 raises the total and so the cap.
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        voting.clone(),
+        &ExecuteMsg::Refresh {
+            addresses: vec![ADDR3.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(
+        voting_power(&app, &voting, ADDR1, None),
+        Uint128::new(5_250)
+    );
+    assert_eq!(voting_power(&app, &voting, ADDR3, None), Uint128::new(400));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(10_500));
+}
+
+#[test]
+fn test_piecewise() {
+    let mut app = App::default();
+    let TestCase {
+        voting,
+        staking,
+        token,
+    } = setup_test_case(
+        &mut app,
+        Transform::Piecewise {
+            segments: vec![
+                Segment {
+                    start: Uint128::zero(),
+                    rate: Decimal::one(),
+                },
+                Segment {
+                    start: Uint128::new(1_000),
+                    rate: Decimal::percent(50),
+                },
+                Segment {
+                    start: Uint128::new(5_000),
+                    rate: Decimal::zero(),
+                },
+            ],
+        },
+    );
+    stake(&mut app, &staking, &token, ADDR1, 10_000);
+    stake(&mut app, &staking, &token, ADDR2, 100);
+    app.update_block(next_block);
+
+    // 1_000 * 1 + 4_000 * 0.5 + 5_000 * 0
+    assert_eq!(
+        voting_power(&app, &voting, ADDR1, None),
+        Uint128::new(3_000)
+    );
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(100));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(3_100));
+}
+
+#[test]
+fn test_invalid_transforms() {
+    let mut app = App::default();
+    let (voting_module, _, _) = instantiate_staked_balance_voting(&mut app);
+    let transformed_id = app.store_code(transformed_voting_contract());
+
+    let mut instantiate = |transform: Transform| -> ContractError {
+        app.instantiate_contract(
+            transformed_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                voting_module: voting_module.to_string(),
+                transform,
+            },
+            &[],
+            "transformed voting",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap()
+    };
+
+    assert_eq!(
+        instantiate(Transform::AbsoluteCap {
+            cap: Uint128::zero()
+        }),
+        ContractError::ZeroCap {}
+    );
+    assert_eq!(
+        instantiate(Transform::PercentageCap {
+            percent: Decimal::zero()
+        }),
+        ContractError::InvalidPercentageCap {}
+    );
+    assert_eq!(
+        instantiate(Transform::PercentageCap {
+            percent: Decimal::percent(101)
+        }),
+        ContractError::InvalidPercentageCap {}
+    );
+    assert_eq!(
+        instantiate(Transform::Piecewise { segments: vec![] }),
+        ContractError::InvalidFirstSegment {}
+    );
+    assert_eq!(
+        instantiate(Transform::Piecewise {
+            segments: vec![
+                Segment {
+                    start: Uint128::zero(),
+                    rate: Decimal::one(),
+                },
+                Segment {
+                    start: Uint128::zero(),
+                    rate: Decimal::percent(50),
+                },
+            ],
+        }),
+        ContractError::UnorderedSegments {}
+    );
+    assert_eq!(
+        instantiate(Transform::Piecewise {
+            segments: vec![Segment {
+                start: Uint128::zero(),
+                rate: Decimal::percent(101),
+            }],
+        }),
+        ContractError::InvalidRate {}
+    );
+}
+
+#[test]
+fn test_piecewise_large_balance() {
+    let transform = Transform::Piecewise {
+        segments: vec![
+            Segment {
+                start: Uint128::zero(),
+                rate: Decimal::one(),
+            },
+            Segment {
+                start: Uint128::new(1_000),
+                rate: Decimal::percent(50),
+            },
+        ],
+    };
+    assert_eq!(
+        transform.apply(Uint128::MAX).unwrap(),
+        Uint128::new(1_000 + (u128::MAX - 1_000) / 2)
+    );
+}
+
+#[test]
+fn test_hook_sender() {
+    let mut app = App::default();
+    let TestCase { voting, .. } = setup_test_case(&mut app, Transform::SquareRoot {});
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting,
+            &ExecuteMsg::StakeChangeHook(StakeChangedHookMsg::Stake {
+                addr: Addr::unchecked(ADDR1),
+                amount: Uint128::new(1_000_000),
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidHookSender {});
+}
+
+#[test]
+fn test_update_staking_contract() {
+    let mut app = App::default();
+    let TestCase {
+        voting,
+        staking,
+        token,
+    } = setup_test_case(&mut app, Transform::SquareRoot {});
+    stake(&mut app, &staking, &token, ADDR1, 10_000);
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(100));
+
+    let staking_id = app.store_code(staking_contract());
+    let new_staking = app
+        .instantiate_contract(
+            staking_id,
+            Addr::unchecked(DAO_ADDR),
+            &stake_cw20::msg::InstantiateMsg {
+                owner: Some(DAO_ADDR.to_string()),
+                manager: None,
+                token_address: token.to_string(),
+                unstaking_duration: None,
+            },
+            &[],
+            "staking",
+            None,
+        )
+        .unwrap();
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(voting.clone(), &QueryMsg::VotingModule {})
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_module,
+        &cw20_staked_balance_voting::msg::ExecuteMsg::UpdateStakingContract {
+            new_staking_contract: new_staking.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        new_staking.clone(),
+        &stake_cw20::msg::ExecuteMsg::AddHook {
+            addr: voting.to_string(),
+            gas_limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let staking_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(voting.clone(), &QueryMsg::StakingContract {})
+        .unwrap();
+    assert_eq!(staking_contract, new_staking);
+
+    // Hooks from the new staking contract are accepted and those
+    // from the old one are not.
+    stake(&mut app, &new_staking, &token, ADDR2, 100);
+    let err: ContractError = app
+        .execute_contract(
+            staking,
+            voting.clone(),
+            &ExecuteMsg::StakeChangeHook(StakeChangedHookMsg::Stake {
+                addr: Addr::unchecked(ADDR1),
+                amount: Uint128::new(10_000),
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidHookSender {});
+
+    // Refreshing picks up balances in the new staking contract.
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        voting.clone(),
+        &ExecuteMsg::Refresh {
+            addresses: vec![ADDR1.to_string(), ADDR3.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::zero());
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(10));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(10));
+}
@@ -0,0 +1,104 @@
+use std::convert::TryFrom;
+
+use cosmwasm_std::{
+    Decimal, Fraction, OverflowError, OverflowOperation, StdError, StdResult, Uint128, Uint256,
+};
+
+use crate::error::ContractError;
+use crate::msg::{Segment, Transform};
+
+impl Transform {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        match self {
+            Transform::SquareRoot {} => Ok(()),
+            Transform::AbsoluteCap { cap } => {
+                if cap.is_zero() {
+                    return Err(ContractError::ZeroCap {});
+                }
+                Ok(())
+            }
+            Transform::PercentageCap { percent } => {
+                if percent.is_zero() || *percent > Decimal::one() {
+                    return Err(ContractError::InvalidPercentageCap {});
+                }
+                Ok(())
+            }
+            Transform::Piecewise { segments } => {
+                if segments.first().map(|s| s.start) != Some(Uint128::zero()) {
+                    return Err(ContractError::InvalidFirstSegment {});
+                }
+                if segments.windows(2).any(|w| w[0].start >= w[1].start) {
+                    return Err(ContractError::UnorderedSegments {});
+                }
+                // Rates above one would give large holders more
+                // power per token than small ones.
+                if segments.iter().any(|s| s.rate > Decimal::one()) {
+                    return Err(ContractError::InvalidRate {});
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Computes the voting power of an address with `balance` tokens
+    /// staked. Percentage caps depend on the total voting power so
+    /// are applied when voting power is queried by `cap`.
+    pub fn apply(&self, balance: Uint128) -> StdResult<Uint128> {
+        match self {
+            Transform::SquareRoot {} => Ok(Uint128::new(isqrt(balance.u128()))),
+            Transform::AbsoluteCap { cap } => Ok(balance.min(*cap)),
+            Transform::PercentageCap { .. } => Ok(balance),
+            Transform::Piecewise { segments } => apply_piecewise(segments, balance),
+        }
+    }
+
+    /// Caps `power`, as computed by `apply`, given the total voting
+    /// power at the same height.
+    pub fn cap(&self, power: Uint128, total: Uint128) -> Uint128 {
+        match self {
+            Transform::PercentageCap { percent } => power.min(total * *percent),
+            _ => power,
+        }
+    }
+}
+
+fn apply_piecewise(segments: &[Segment], balance: Uint128) -> StdResult<Uint128> {
+    let mut power = Uint128::zero();
+    for (i, segment) in segments.iter().enumerate() {
+        if balance <= segment.start {
+            break;
+        }
+        let end = segments
+            .get(i + 1)
+            .map_or(balance, |next| next.start.min(balance));
+        let tokens = end - segment.start;
+        let segment_power = Uint128::try_from(
+            tokens.full_mul(segment.rate.numerator()) / Uint256::from(segment.rate.denominator()),
+        )
+        .map_err(|_| {
+            StdError::overflow(OverflowError::new(
+                OverflowOperation::Mul,
+                tokens,
+                segment.rate,
+            ))
+        })?;
+        power = power.checked_add(segment_power)?;
+    }
+    Ok(power)
+}
+/// The largest integer whose square is at most `n`.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method starting from a value guaranteed to be at
+    // least the root. Iterates decrease until they reach it.
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
@@ -0,0 +1,41 @@
+use cw20::Cw20Coin;
+use cw20_base::msg::InstantiateMarketingInfo;
+use cw_core_macros::{token_query, voting_query};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenInfo {
+    /// Uses an existing cw20-snapshot token.
+    Existing { address: String },
+    /// Instantiates a new cw20-snapshot token. The DAO is the
+    /// token's minter.
+    New {
+        code_id: u64,
+        label: String,
+
+        name: String,
+        symbol: String,
+        decimals: u8,
+        initial_balances: Vec<Cw20Coin>,
+        marketing: Option<InstantiateMarketingInfo>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub token_info: TokenInfo,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {}
+
+#[token_query]
+#[voting_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Dao {},
+}
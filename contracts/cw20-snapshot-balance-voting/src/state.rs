@@ -0,0 +1,5 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+
+pub const TOKEN: Item<Addr> = Item::new("token");
+pub const DAO: Item<Addr> = Item::new("dao");
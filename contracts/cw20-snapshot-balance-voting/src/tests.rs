@@ -0,0 +1,226 @@
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw20::Cw20Coin;
+use cw_core_interface::voting::{TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
+use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+
+use crate::{
+    msg::{InstantiateMsg, QueryMsg, TokenInfo},
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw20_snapshot_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_snapshot::contract::execute,
+        cw20_snapshot::contract::instantiate,
+        cw20_snapshot::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn balance_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply);
+    Box::new(contract)
+}
+
+fn instantiate_voting(app: &mut App, msg: InstantiateMsg) -> anyhow::Result<Addr> {
+    let voting_id = app.store_code(balance_voting_contract());
+    app.instantiate_contract(
+        voting_id,
+        Addr::unchecked(DAO_ADDR),
+        &msg,
+        &[],
+        "voting module",
+        None,
+    )
+}
+
+fn new_token_info(app: &mut App, initial_balances: Vec<Cw20Coin>) -> TokenInfo {
+    TokenInfo::New {
+        code_id: app.store_code(cw20_snapshot_contract()),
+        label: "DAO DAO voting".to_string(),
+        name: "DAO DAO".to_string(),
+        symbol: "DAO".to_string(),
+        decimals: 6,
+        initial_balances,
+        marketing: None,
+    }
+}
+
+fn voting_power(app: &App, voting: &Addr, address: &str, height: Option<u64>) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+fn total_power(app: &App, voting: &Addr, height: Option<u64>) -> Uint128 {
+    let res: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(voting, &QueryMsg::TotalPowerAtHeight { height })
+        .unwrap();
+    res.power
+}
+
+#[test]
+fn test_instantiate() {
+    let mut app = App::default();
+    let token_info = new_token_info(
+        &mut app,
+        vec![Cw20Coin {
+            address: ADDR1.to_string(),
+            amount: Uint128::zero(),
+        }],
+    );
+    let err: ContractError = instantiate_voting(&mut app, InstantiateMsg { token_info })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InitialBalancesError {});
+
+    // Tokens that do not record snapshots may not be used.
+    let cw20_id = app.store_code(cw20_contract());
+    let token = app
+        .instantiate_contract(
+            cw20_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw20_base::msg::InstantiateMsg {
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: ADDR1.to_string(),
+                    amount: Uint128::new(100),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "token",
+            None,
+        )
+        .unwrap();
+    instantiate_voting(
+        &mut app,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                address: token.to_string(),
+            },
+        },
+    )
+    .unwrap_err();
+
+    let token_info = new_token_info(
+        &mut app,
+        vec![Cw20Coin {
+            address: ADDR1.to_string(),
+            amount: Uint128::new(100),
+        }],
+    );
+    let voting = instantiate_voting(&mut app, InstantiateMsg { token_info }).unwrap();
+    let token: Addr = app
+        .wrap()
+        .query_wasm_smart(voting.clone(), &QueryMsg::TokenContract {})
+        .unwrap();
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(voting, &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(dao, Addr::unchecked(DAO_ADDR));
+
+    // An existing snapshot token may be used.
+    instantiate_voting(
+        &mut app,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                address: token.to_string(),
+            },
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_voting_power_at_height() {
+    let mut app = App::default();
+    let token_info = new_token_info(
+        &mut app,
+        vec![Cw20Coin {
+            address: ADDR1.to_string(),
+            amount: Uint128::new(100),
+        }],
+    );
+    let voting = instantiate_voting(&mut app, InstantiateMsg { token_info }).unwrap();
+    let token: Addr = app
+        .wrap()
+        .query_wasm_smart(voting.clone(), &QueryMsg::TokenContract {})
+        .unwrap();
+    app.update_block(next_block);
+    let start = app.block_info().height;
+
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(100));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(100));
+
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        token.clone(),
+        &cw20::Cw20ExecuteMsg::Transfer {
+            recipient: ADDR2.to_string(),
+            amount: Uint128::new(40),
+        },
+        &[],
+    )
+    .unwrap();
+    // The DAO is the token's minter.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        token,
+        &cw20::Cw20ExecuteMsg::Mint {
+            recipient: ADDR2.to_string(),
+            amount: Uint128::new(50),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(voting_power(&app, &voting, ADDR1, None), Uint128::new(60));
+    assert_eq!(voting_power(&app, &voting, ADDR2, None), Uint128::new(90));
+    assert_eq!(total_power(&app, &voting, None), Uint128::new(150));
+
+    // Queries at a proposal's start height are unaffected by later
+    // transfers.
+    assert_eq!(
+        voting_power(&app, &voting, ADDR1, Some(start)),
+        Uint128::new(100)
+    );
+    assert_eq!(
+        voting_power(&app, &voting, ADDR2, Some(start)),
+        Uint128::zero()
+    );
+    assert_eq!(total_power(&app, &voting, Some(start)), Uint128::new(100));
+}
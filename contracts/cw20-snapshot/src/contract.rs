@@ -0,0 +1,160 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage,
+};
+use cw2::set_contract_version;
+use cw20_base::state::{BALANCES, TOKEN_INFO};
+
+use crate::error::ContractError;
+use crate::msg::{
+    BalanceAtHeightResponse, ExecuteMsg, InstantiateMsg, QueryMsg, TotalSupplyAtHeightResponse,
+};
+use crate::state::{BALANCE_SNAPSHOTS, TOTAL_SUPPLY_SNAPSHOT};
+
+const CONTRACT_NAME: &str = "crates.io:cw20-snapshot";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let addresses: Vec<String> = msg
+        .initial_balances
+        .iter()
+        .map(|coin| coin.address.clone())
+        .collect();
+    let res = cw20_base::contract::instantiate(deps.branch(), env.clone(), info, msg)?;
+    // cw20-base sets its own contract version.
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    snapshot_balances(deps.branch(), env.block.height, addresses)?;
+    snapshot_total_supply(deps.storage, env.block.height)?;
+
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    // The addresses whose balances the message may change, and
+    // whether it may change the total supply.
+    let (addresses, supply_changed) = match &msg {
+        ExecuteMsg::Transfer { recipient, .. } => {
+            (vec![info.sender.to_string(), recipient.clone()], false)
+        }
+        ExecuteMsg::Send { contract, .. } => {
+            (vec![info.sender.to_string(), contract.clone()], false)
+        }
+        ExecuteMsg::TransferFrom {
+            owner, recipient, ..
+        } => (vec![owner.clone(), recipient.clone()], false),
+        ExecuteMsg::SendFrom {
+            owner, contract, ..
+        } => (vec![owner.clone(), contract.clone()], false),
+        ExecuteMsg::Burn { .. } => (vec![info.sender.to_string()], true),
+        ExecuteMsg::BurnFrom { owner, .. } => (vec![owner.clone()], true),
+        ExecuteMsg::Mint { recipient, .. } => (vec![recipient.clone()], true),
+        ExecuteMsg::IncreaseAllowance { .. }
+        | ExecuteMsg::DecreaseAllowance { .. }
+        | ExecuteMsg::UpdateMarketing { .. }
+        | ExecuteMsg::UploadLogo(_) => (vec![], false),
+    };
+
+    let res = cw20_base::contract::execute(deps.branch(), env.clone(), info, msg)?;
+
+    snapshot_balances(deps.branch(), env.block.height, addresses)?;
+    if supply_changed {
+        snapshot_total_supply(deps.storage, env.block.height)?;
+    }
+
+    Ok(res)
+}
+
+/// Copies the current cw20-base balances of `addresses` into the
+/// balance snapshots.
+fn snapshot_balances(deps: DepsMut, height: u64, addresses: Vec<String>) -> StdResult<()> {
+    for address in addresses {
+        // cw20-base has already validated these addresses.
+        let address = deps.api.addr_validate(&address)?;
+        let balance = BALANCES
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default();
+        BALANCE_SNAPSHOTS.save(deps.storage, &address, &balance, height)?;
+    }
+    Ok(())
+}
+
+fn snapshot_total_supply(storage: &mut dyn Storage, height: u64) -> StdResult<()> {
+    let total_supply = TOKEN_INFO.load(storage)?.total_supply;
+    TOTAL_SUPPLY_SNAPSHOT.save(storage, &total_supply, height)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let base_msg = match msg {
+        QueryMsg::BalanceAtHeight { address, height } => {
+            return query_balance_at_height(deps, env, address, height)
+        }
+        QueryMsg::TotalSupplyAtHeight { height } => {
+            return query_total_supply_at_height(deps, env, height)
+        }
+        QueryMsg::Balance { address } => cw20_base::msg::QueryMsg::Balance { address },
+        QueryMsg::TokenInfo {} => cw20_base::msg::QueryMsg::TokenInfo {},
+        QueryMsg::Minter {} => cw20_base::msg::QueryMsg::Minter {},
+        QueryMsg::Allowance { owner, spender } => {
+            cw20_base::msg::QueryMsg::Allowance { owner, spender }
+        }
+        QueryMsg::AllAllowances {
+            owner,
+            start_after,
+            limit,
+        } => cw20_base::msg::QueryMsg::AllAllowances {
+            owner,
+            start_after,
+            limit,
+        },
+        QueryMsg::AllAccounts { start_after, limit } => {
+            cw20_base::msg::QueryMsg::AllAccounts { start_after, limit }
+        }
+        QueryMsg::MarketingInfo {} => cw20_base::msg::QueryMsg::MarketingInfo {},
+        QueryMsg::DownloadLogo {} => cw20_base::msg::QueryMsg::DownloadLogo {},
+    };
+    cw20_base::contract::query(deps, env, base_msg)
+}
+
+pub fn query_balance_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+    let balance = BALANCE_SNAPSHOTS
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+    to_binary(&BalanceAtHeightResponse { balance, height })
+}
+
+pub fn query_total_supply_at_height(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let height = height.unwrap_or(env.block.height);
+    let total_supply = TOTAL_SUPPLY_SNAPSHOT
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+    to_binary(&TotalSupplyAtHeightResponse {
+        total_supply,
+        height,
+    })
+}
@@ -0,0 +1,58 @@
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub use cw20_base::msg::{ExecuteMsg, InstantiateMsg};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the balance of the given address at a height,
+    /// defaulting to the current block. Changes made in a block are
+    /// visible from the next block.
+    /// Return type: BalanceAtHeightResponse.
+    BalanceAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+    /// Returns the total supply at a height, defaulting to the
+    /// current block.
+    /// Return type: TotalSupplyAtHeightResponse.
+    TotalSupplyAtHeight {
+        height: Option<u64>,
+    },
+
+    // The queries below are handled by cw20-base.
+    Balance {
+        address: String,
+    },
+    TokenInfo {},
+    Minter {},
+    Allowance {
+        owner: String,
+        spender: String,
+    },
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    MarketingInfo {},
+    DownloadLogo {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BalanceAtHeightResponse {
+    pub balance: Uint128,
+    pub height: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalSupplyAtHeightResponse {
+    pub total_supply: Uint128,
+    pub height: u64,
+}
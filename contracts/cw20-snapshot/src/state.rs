@@ -0,0 +1,20 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{SnapshotItem, SnapshotMap, Strategy};
+
+/// Snapshots of the balances stored by cw20-base. Updated after
+/// every message that changes a balance.
+pub const BALANCE_SNAPSHOTS: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "balance_snapshots",
+    "balance_snapshots__checkpoints",
+    "balance_snapshots__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Snapshots of the total supply stored by cw20-base. Updated after
+/// every mint and burn.
+pub const TOTAL_SUPPLY_SNAPSHOT: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_supply_snapshot",
+    "total_supply_snapshot__checkpoints",
+    "total_supply_snapshot__changelog",
+    Strategy::EveryBlock,
+);
@@ -0,0 +1,229 @@
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, MinterResponse};
+use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+
+use crate::msg::{BalanceAtHeightResponse, InstantiateMsg, QueryMsg, TotalSupplyAtHeightResponse};
+
+const MINTER: &str = "minter";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+const ADDR3: &str = "addr3";
+
+fn cw20_snapshot_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn setup_test_case(app: &mut App) -> Addr {
+    let code_id = app.store_code(cw20_snapshot_contract());
+    let token = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(MINTER),
+            &InstantiateMsg {
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: ADDR1.to_string(),
+                    amount: Uint128::new(100),
+                }],
+                mint: Some(MinterResponse {
+                    minter: MINTER.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
+            },
+            &[],
+            "token",
+            None,
+        )
+        .unwrap();
+    app.update_block(next_block);
+    token
+}
+
+fn balance_at_height(app: &App, token: &Addr, address: &str, height: Option<u64>) -> Uint128 {
+    let res: BalanceAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &QueryMsg::BalanceAtHeight {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.balance
+}
+
+fn total_supply_at_height(app: &App, token: &Addr, height: Option<u64>) -> Uint128 {
+    let res: TotalSupplyAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(token, &QueryMsg::TotalSupplyAtHeight { height })
+        .unwrap();
+    res.total_supply
+}
+
+#[test]
+fn test_snapshots() {
+    let mut app = App::default();
+    let token = setup_test_case(&mut app);
+    let start = app.block_info().height;
+
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        token.clone(),
+        &Cw20ExecuteMsg::Transfer {
+            recipient: ADDR2.to_string(),
+            amount: Uint128::new(30),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(MINTER),
+        token.clone(),
+        &Cw20ExecuteMsg::Mint {
+            recipient: ADDR2.to_string(),
+            amount: Uint128::new(20),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        token.clone(),
+        &Cw20ExecuteMsg::Burn {
+            amount: Uint128::new(10),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Changes are not visible until the next block.
+    assert_eq!(
+        balance_at_height(&app, &token, ADDR2, None),
+        Uint128::zero()
+    );
+    app.update_block(next_block);
+
+    assert_eq!(
+        balance_at_height(&app, &token, ADDR1, None),
+        Uint128::new(70)
+    );
+    assert_eq!(
+        balance_at_height(&app, &token, ADDR2, None),
+        Uint128::new(40)
+    );
+    assert_eq!(
+        total_supply_at_height(&app, &token, None),
+        Uint128::new(110)
+    );
+
+    assert_eq!(
+        balance_at_height(&app, &token, ADDR1, Some(start)),
+        Uint128::new(100)
+    );
+    assert_eq!(
+        balance_at_height(&app, &token, ADDR2, Some(start)),
+        Uint128::zero()
+    );
+    assert_eq!(
+        total_supply_at_height(&app, &token, Some(start)),
+        Uint128::new(100)
+    );
+
+    // Other queries are handled by cw20-base.
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &QueryMsg::Balance {
+                address: ADDR2.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.balance, Uint128::new(40));
+}
+
+#[test]
+fn test_allowance_snapshots() {
+    let mut app = App::default();
+    let token = setup_test_case(&mut app);
+    let start = app.block_info().height;
+
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        token.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: ADDR2.to_string(),
+            amount: Uint128::new(50),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        token.clone(),
+        &Cw20ExecuteMsg::TransferFrom {
+            owner: ADDR1.to_string(),
+            recipient: ADDR3.to_string(),
+            amount: Uint128::new(25),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        token.clone(),
+        &Cw20ExecuteMsg::BurnFrom {
+            owner: ADDR1.to_string(),
+            amount: Uint128::new(25),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(
+        balance_at_height(&app, &token, ADDR1, None),
+        Uint128::new(50)
+    );
+    assert_eq!(
+        balance_at_height(&app, &token, ADDR2, None),
+        Uint128::zero()
+    );
+    assert_eq!(
+        balance_at_height(&app, &token, ADDR3, None),
+        Uint128::new(25)
+    );
+    assert_eq!(total_supply_at_height(&app, &token, None), Uint128::new(75));
+    assert_eq!(
+        balance_at_height(&app, &token, ADDR3, Some(start)),
+        Uint128::zero()
+    );
+
+    // Failed messages do not change snapshots.
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        token.clone(),
+        &Cw20ExecuteMsg::TransferFrom {
+            owner: ADDR1.to_string(),
+            recipient: ADDR3.to_string(),
+            amount: Uint128::new(25),
+        },
+        &[],
+    )
+    .unwrap_err();
+    app.update_block(next_block);
+    assert_eq!(
+        balance_at_height(&app, &token, ADDR1, None),
+        Uint128::new(50)
+    );
+}
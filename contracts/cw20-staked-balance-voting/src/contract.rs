@@ -74,7 +74,11 @@ pub fn instantiate(
                         return Err(ContractError::StakingContractMismatch {});
                     }
 
-                    STAKING_CONTRACT.save(deps.storage, &staking_contract_address)?;
+                    STAKING_CONTRACT.save(
+                        deps.storage,
+                        &staking_contract_address,
+                        env.block.height,
+                    )?;
                     Ok(Response::default()
                         .add_attribute("action", "instantiate")
                         .add_attribute("token", "existing_token")
@@ -191,6 +195,9 @@ pub fn execute(
         ExecuteMsg::UpdateActiveThreshold { new_threshold } => {
             execute_update_active_threshold(deps, env, info, new_threshold)
         }
+        ExecuteMsg::UpdateStakingContract {
+            new_staking_contract,
+        } => execute_update_staking_contract(deps, env, info, new_staking_contract),
     }
 }
 
@@ -224,6 +231,36 @@ pub fn execute_update_active_threshold(
 
     Ok(Response::new().add_attribute("action", "update_active_threshold"))
 }
+
+pub fn execute_update_staking_contract(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_staking_contract: String,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let new_staking_contract = deps.api.addr_validate(&new_staking_contract)?;
+    let resp: stake_cw20::msg::GetConfigResponse = deps.querier.query_wasm_smart(
+        &new_staking_contract,
+        &stake_cw20::msg::QueryMsg::GetConfig {},
+    )?;
+    let token = TOKEN.load(deps.storage)?;
+    if resp.token_address != token {
+        return Err(ContractError::StakingContractMismatch {});
+    }
+
+    let old_staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    STAKING_CONTRACT.save(deps.storage, &new_staking_contract, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_staking_contract")
+        .add_attribute("old_staking_contract", old_staking_contract)
+        .add_attribute("new_staking_contract", new_staking_contract))
+}
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -250,19 +287,29 @@ pub fn query_staking_contract(deps: Deps) -> StdResult<Binary> {
     to_binary(&staking_contract)
 }
 
+/// Loads the staking contract that was in place at `height`.
+fn load_staking_contract_at_height(deps: Deps, height: u64) -> StdResult<Addr> {
+    match STAKING_CONTRACT.may_load_at_height(deps.storage, height)? {
+        Some(staking_contract) => Ok(staking_contract),
+        // Heights before the staking contract was first set.
+        None => STAKING_CONTRACT.load(deps.storage),
+    }
+}
+
 pub fn query_voting_power_at_height(
     deps: Deps,
-    _env: Env,
+    env: Env,
     address: String,
     height: Option<u64>,
 ) -> StdResult<Binary> {
-    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    let height = height.unwrap_or(env.block.height);
+    let staking_contract = load_staking_contract_at_height(deps, height)?;
     let address = deps.api.addr_validate(&address)?;
     let res: stake_cw20::msg::StakedBalanceAtHeightResponse = deps.querier.query_wasm_smart(
         staking_contract,
         &stake_cw20::msg::QueryMsg::StakedBalanceAtHeight {
             address: address.to_string(),
-            height,
+            height: Some(height),
         },
     )?;
     to_binary(&cw_core_interface::voting::VotingPowerAtHeightResponse {
@@ -271,15 +318,14 @@ pub fn query_voting_power_at_height(
     })
 }
 
-pub fn query_total_power_at_height(
-    deps: Deps,
-    _env: Env,
-    height: Option<u64>,
-) -> StdResult<Binary> {
-    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    let height = height.unwrap_or(env.block.height);
+    let staking_contract = load_staking_contract_at_height(deps, height)?;
     let res: stake_cw20::msg::TotalStakedAtHeightResponse = deps.querier.query_wasm_smart(
         staking_contract,
-        &stake_cw20::msg::QueryMsg::TotalStakedAtHeight { height },
+        &stake_cw20::msg::QueryMsg::TotalStakedAtHeight {
+            height: Some(height),
+        },
     )?;
     to_binary(&cw_core_interface::voting::TotalPowerAtHeightResponse {
         power: res.total,
@@ -398,7 +444,11 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                     }
 
                     // Save staking contract addr
-                    STAKING_CONTRACT.save(deps.storage, &staking_contract_addr)?;
+                    STAKING_CONTRACT.save(
+                        deps.storage,
+                        &staking_contract_addr,
+                        env.block.height,
+                    )?;
 
                     Ok(Response::new().add_attribute("staking_contract", staking_contract_addr))
                 }
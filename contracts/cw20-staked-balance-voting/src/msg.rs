@@ -60,6 +60,11 @@ pub enum ExecuteMsg {
     UpdateActiveThreshold {
         new_threshold: Option<ActiveThreshold>,
     },
+    /// Replaces the staking contract voting power is read from. The
+    /// new staking contract must stake the same token. Voting power
+    /// at heights before the replacement is still read from the old
+    /// staking contract.
+    UpdateStakingContract { new_staking_contract: String },
 }
 
 #[voting_query]
@@ -1,12 +1,21 @@
 use crate::msg::ActiveThreshold;
 use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, SnapshotItem, Strategy};
 use cw_utils::Duration;
 
 pub const ACTIVE_THRESHOLD: Item<ActiveThreshold> = Item::new("active_threshold");
 pub const TOKEN: Item<Addr> = Item::new("token");
 pub const DAO: Item<Addr> = Item::new("dao");
-pub const STAKING_CONTRACT: Item<Addr> = Item::new("staking_contract");
+/// The staking contract voting power is read from. Snapshotted so
+/// that queries for voting power at heights before the staking
+/// contract was replaced use the staking contract in place at that
+/// height.
+pub const STAKING_CONTRACT: SnapshotItem<Addr> = SnapshotItem::new(
+    "staking_contract",
+    "staking_contract__checkpoints",
+    "staking_contract__changelog",
+    Strategy::EveryBlock,
+);
 pub const STAKING_CONTRACT_UNSTAKING_DURATION: Item<Option<Duration>> =
     Item::new("staking_contract_unstaking_duration");
 pub const STAKING_CONTRACT_CODE_ID: Item<u64> = Item::new("staking_contract_code_id");
@@ -1,12 +1,15 @@
 use cosmwasm_std::{to_binary, Addr, Decimal, Empty, Uint128};
 use cw2::ContractVersion;
 use cw20::{BalanceResponse, Cw20Coin, MinterResponse, TokenInfoResponse};
-use cw_core_interface::voting::{InfoResponse, IsActiveResponse, VotingPowerAtHeightResponse};
+use cw_core_interface::voting::{
+    InfoResponse, IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
 use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
 
 use crate::msg::{
     ActiveThreshold, ActiveThresholdResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StakingInfo,
 };
+use crate::ContractError;
 
 const DAO_ADDR: &str = "dao";
 const CREATOR_ADDR: &str = "creator";
@@ -1251,3 +1254,194 @@ fn test_active_threshold_absolute_count_invalid() {
         },
     );
 }
+
+#[test]
+fn test_update_staking_contract() {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_contract());
+    let voting_id = app.store_code(staked_balance_voting_contract());
+    let staking_contract_id = app.store_code(staking_contract());
+
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            token_info: crate::msg::TokenInfo::New {
+                code_id: cw20_id,
+                label: "DAO DAO voting".to_string(),
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: CREATOR_ADDR.to_string(),
+                    amount: Uint128::from(200u64),
+                }],
+                marketing: None,
+                unstaking_duration: None,
+                staking_code_id: staking_contract_id,
+                initial_dao_balance: None,
+            },
+            active_threshold: None,
+        },
+    );
+
+    let token_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::TokenContract {})
+        .unwrap();
+    let old_staking_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::StakingContract {})
+        .unwrap();
+
+    stake_tokens(
+        &mut app,
+        old_staking_addr.clone(),
+        token_addr.clone(),
+        CREATOR_ADDR,
+        100,
+    );
+    app.update_block(next_block);
+    let before_update = app.block_info().height;
+
+    let new_staking_addr = app
+        .instantiate_contract(
+            staking_contract_id,
+            Addr::unchecked(DAO_ADDR),
+            &stake_cw20::msg::InstantiateMsg {
+                owner: Some(DAO_ADDR.to_string()),
+                manager: None,
+                token_address: token_addr.to_string(),
+                unstaking_duration: None,
+            },
+            &[],
+            "new staking",
+            None,
+        )
+        .unwrap();
+
+    // A staking contract for a different token.
+    let other_token_addr = app
+        .instantiate_contract(
+            cw20_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw20_base::msg::InstantiateMsg {
+                name: "Other".to_string(),
+                symbol: "OTHER".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: CREATOR_ADDR.to_string(),
+                    amount: Uint128::from(2u64),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "other token",
+            None,
+        )
+        .unwrap();
+    let other_staking_addr = app
+        .instantiate_contract(
+            staking_contract_id,
+            Addr::unchecked(DAO_ADDR),
+            &stake_cw20::msg::InstantiateMsg {
+                owner: Some(DAO_ADDR.to_string()),
+                manager: None,
+                token_address: other_token_addr.to_string(),
+                unstaking_duration: None,
+            },
+            &[],
+            "other staking",
+            None,
+        )
+        .unwrap();
+
+    // Expect failure as sender is not the DAO
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateStakingContract {
+            new_staking_contract: new_staking_addr.to_string(),
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    // Expect failure as the staking contract stakes a different token
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            voting_addr.clone(),
+            &ExecuteMsg::UpdateStakingContract {
+                new_staking_contract: other_staking_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::StakingContractMismatch {}));
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateStakingContract {
+            new_staking_contract: new_staking_addr.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let staking_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::StakingContract {})
+        .unwrap();
+    assert_eq!(staking_addr, new_staking_addr);
+
+    // Nothing has been staked in the new staking contract yet.
+    let creator_voting_power: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::VotingPowerAtHeight {
+                address: CREATOR_ADDR.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(creator_voting_power.power, Uint128::zero());
+
+    // Queries before the update use the old staking contract.
+    let creator_voting_power: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::VotingPowerAtHeight {
+                address: CREATOR_ADDR.to_string(),
+                height: Some(before_update),
+            },
+        )
+        .unwrap();
+    assert_eq!(creator_voting_power.power, Uint128::new(100));
+    let total_power: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::TotalPowerAtHeight {
+                height: Some(before_update),
+            },
+        )
+        .unwrap();
+    assert_eq!(total_power.power, Uint128::new(100));
+
+    stake_tokens(&mut app, new_staking_addr, token_addr, CREATOR_ADDR, 50);
+    app.update_block(next_block);
+
+    let total_power: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(voting_addr, &QueryMsg::TotalPowerAtHeight { height: None })
+        .unwrap();
+    assert_eq!(total_power.power, Uint128::new(50));
+}
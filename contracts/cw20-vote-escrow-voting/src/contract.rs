@@ -0,0 +1,365 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Storage, Uint128, Uint256, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_core_interface::voting::{TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
+use std::convert::TryInto;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, LockResponse, QueryMsg, ReceiveMsg};
+use crate::state::{
+    Checkpoint, Config, Lock, LockTotals, CHECKPOINT, CONFIG, DAO, LOCKS, MAX_EPOCHS, SLOPE_CHANGES,
+};
+
+const CONTRACT_NAME: &str = "crates.io:cw20-vote-escrow-voting";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    DAO.save(deps.storage, &info.sender)?;
+
+    if msg.max_lock_duration == 0 {
+        return Err(ContractError::ZeroMaxLockDuration {});
+    }
+    if msg.epoch_length == 0
+        || msg.epoch_length > msg.max_lock_duration
+        || msg.max_lock_duration / msg.epoch_length > MAX_EPOCHS
+    {
+        return Err(ContractError::InvalidEpochLength {
+            max_epochs: MAX_EPOCHS,
+        });
+    }
+    let token_address = deps.api.addr_validate(&msg.token_address)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            token_address: token_address.clone(),
+            max_lock_duration: msg.max_lock_duration,
+            epoch_length: msg.epoch_length,
+        },
+    )?;
+    CHECKPOINT.save(
+        deps.storage,
+        &Checkpoint {
+            height: env.block.height,
+            totals: LockTotals::default(),
+        },
+        env.block.height,
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "instantiate")
+        .add_attribute("token_address", token_address)
+        .add_attribute("max_lock_duration", msg.max_lock_duration.to_string())
+        .add_attribute("epoch_length", msg.epoch_length.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, env, info, wrapper),
+        ExecuteMsg::ExtendLock { duration } => execute_extend_lock(deps, env, info, duration),
+        ExecuteMsg::Withdraw {} => execute_withdraw(deps, env, info),
+    }
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.token_address {
+        return Err(ContractError::InvalidToken {
+            received: info.sender.into_string(),
+            expected: config.token_address.into_string(),
+        });
+    }
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    match msg {
+        ReceiveMsg::CreateLock { duration } => {
+            execute_create_lock(deps, env, config, sender, wrapper.amount, duration)
+        }
+        ReceiveMsg::IncreaseAmount {} => execute_increase_amount(deps, env, sender, wrapper.amount),
+    }
+}
+
+fn lock_end(env: &Env, config: &Config, duration: u64) -> Result<u64, ContractError> {
+    if duration == 0 || duration > config.max_lock_duration {
+        return Err(ContractError::InvalidLockDuration {
+            max: config.max_lock_duration,
+        });
+    }
+    let end = (env.block.height + duration) / config.epoch_length * config.epoch_length;
+    if end <= env.block.height {
+        return Err(ContractError::LockTooShort {
+            epoch_length: config.epoch_length,
+        });
+    }
+    Ok(end)
+}
+
+/// Loads `owner`'s lock, erroring if it has ended.
+fn load_active_lock(storage: &dyn Storage, env: &Env, owner: &Addr) -> Result<Lock, ContractError> {
+    let lock = LOCKS
+        .may_load(storage, owner)?
+        .ok_or(ContractError::NoLock {})?;
+    if lock.end <= env.block.height {
+        return Err(ContractError::LockEnded { end: lock.end });
+    }
+    Ok(lock)
+}
+
+fn lock_totals(lock: &Lock) -> LockTotals {
+    let amount = Uint256::from(lock.amount.u128());
+    LockTotals {
+        amount,
+        weighted_end: amount * Uint256::from(lock.end),
+    }
+}
+
+/// Advances `checkpoint` to `height` by removing the locks that ended
+/// at the epoch boundaries in between. Every lock in the checkpoint
+/// ends within the maximum lock duration of it, so this reads at most
+/// `MAX_EPOCHS + 1` slope changes.
+fn advance_checkpoint(
+    storage: &dyn Storage,
+    config: &Config,
+    checkpoint: Checkpoint,
+    height: u64,
+) -> StdResult<Checkpoint> {
+    let mut totals = checkpoint.totals;
+    let last = height.min(checkpoint.height + config.max_lock_duration);
+    let mut boundary = (checkpoint.height / config.epoch_length + 1) * config.epoch_length;
+    while boundary <= last {
+        if let Some(ended) = SLOPE_CHANGES.may_load(storage, boundary)? {
+            totals.amount -= ended.amount;
+            totals.weighted_end -= ended.weighted_end;
+        }
+        boundary += config.epoch_length;
+    }
+    Ok(Checkpoint { height, totals })
+}
+
+/// Replaces `old` with `new` in the lock totals.
+fn update_lock_totals(
+    storage: &mut dyn Storage,
+    config: &Config,
+    height: u64,
+    old: Option<&Lock>,
+    new: &Lock,
+) -> StdResult<()> {
+    let checkpoint = CHECKPOINT.load(storage)?;
+    let mut checkpoint = advance_checkpoint(storage, config, checkpoint, height)?;
+    if let Some(old) = old {
+        let old = lock_totals(old);
+        checkpoint.totals.amount -= old.amount;
+        checkpoint.totals.weighted_end -= old.weighted_end;
+    }
+    let added = lock_totals(new);
+    checkpoint.totals.amount += added.amount;
+    checkpoint.totals.weighted_end += added.weighted_end;
+    CHECKPOINT.save(storage, &checkpoint, height)?;
+
+    if let Some(old_lock) = old {
+        let old = lock_totals(old_lock);
+        let mut ends = SLOPE_CHANGES.load(storage, old_lock.end)?;
+        ends.amount -= old.amount;
+        ends.weighted_end -= old.weighted_end;
+        if ends.amount.is_zero() {
+            SLOPE_CHANGES.remove(storage, old_lock.end);
+        } else {
+            SLOPE_CHANGES.save(storage, old_lock.end, &ends)?;
+        }
+    }
+    let mut ends = SLOPE_CHANGES
+        .may_load(storage, new.end)?
+        .unwrap_or_default();
+    ends.amount += added.amount;
+    ends.weighted_end += added.weighted_end;
+    SLOPE_CHANGES.save(storage, new.end, &ends)?;
+
+    Ok(())
+}
+
+pub fn execute_create_lock(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    sender: Addr,
+    amount: Uint128,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    if LOCKS.may_load(deps.storage, &sender)?.is_some() {
+        return Err(ContractError::LockExists {});
+    }
+    let lock = Lock {
+        amount,
+        end: lock_end(&env, &config, duration)?,
+    };
+    LOCKS.save(deps.storage, &sender, &lock, env.block.height)?;
+    update_lock_totals(deps.storage, &config, env.block.height, None, &lock)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "create_lock")
+        .add_attribute("from", sender)
+        .add_attribute("amount", amount)
+        .add_attribute("end", lock.end.to_string()))
+}
+
+pub fn execute_increase_amount(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let old = load_active_lock(deps.storage, &env, &sender)?;
+    let lock = Lock {
+        amount: old.amount.checked_add(amount).map_err(StdError::overflow)?,
+        end: old.end,
+    };
+    LOCKS.save(deps.storage, &sender, &lock, env.block.height)?;
+    update_lock_totals(deps.storage, &config, env.block.height, Some(&old), &lock)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "increase_amount")
+        .add_attribute("from", sender)
+        .add_attribute("amount", lock.amount))
+}
+
+pub fn execute_extend_lock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let old = load_active_lock(deps.storage, &env, &info.sender)?;
+    let end = lock_end(&env, &config, duration)?;
+    if end <= old.end {
+        return Err(ContractError::LockNotExtended {
+            current_end: old.end,
+        });
+    }
+    let lock = Lock {
+        amount: old.amount,
+        end,
+    };
+    LOCKS.save(deps.storage, &info.sender, &lock, env.block.height)?;
+    update_lock_totals(deps.storage, &config, env.block.height, Some(&old), &lock)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "extend_lock")
+        .add_attribute("from", info.sender)
+        .add_attribute("end", end.to_string()))
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let lock = LOCKS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NoLock {})?;
+    if lock.end > env.block.height {
+        return Err(ContractError::LockNotEnded { end: lock.end });
+    }
+    // The lock totals are left as they are. The slope change at the
+    // lock's end already removed it from them.
+    LOCKS.remove(deps.storage, &info.sender, env.block.height)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let msg = WasmMsg::Execute {
+        contract_addr: config.token_address.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount: lock.amount,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::default()
+        .add_message(msg)
+        .add_attribute("action", "withdraw")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", lock.amount))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            query_voting_power_at_height(deps, env, address, height)
+        }
+        QueryMsg::TotalPowerAtHeight { height } => query_total_power_at_height(deps, env, height),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Lock { address } => query_lock(deps, address),
+    }
+}
+
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+    let power = LOCKS
+        .may_load_at_height(deps.storage, &address, height)?
+        .map(|lock| lock.power(height, config.max_lock_duration))
+        .unwrap_or_default();
+    to_binary(&VotingPowerAtHeightResponse { power, height })
+}
+
+pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let height = height.unwrap_or(env.block.height);
+    // A lock can only be changed before it ends and only changes the
+    // slope changes after that, so the current slope changes are
+    // correct for advancing any past checkpoint.
+    let totals = match CHECKPOINT.may_load_at_height(deps.storage, height)? {
+        Some(checkpoint) => advance_checkpoint(deps.storage, &config, checkpoint, height)?.totals,
+        None => LockTotals::default(),
+    };
+    let power = (totals.weighted_end - totals.amount * Uint256::from(height))
+        / Uint256::from(config.max_lock_duration);
+    to_binary(&TotalPowerAtHeightResponse {
+        power: power.try_into()?,
+        height,
+    })
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&cw_core_interface::voting::InfoResponse { info })
+}
+
+pub fn query_lock(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    to_binary(&LockResponse {
+        lock: LOCKS.may_load(deps.storage, &address)?,
+    })
+}
@@ -0,0 +1,40 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Invalid token. Got ({received}), expected ({expected})")]
+    InvalidToken { received: String, expected: String },
+
+    #[error("Lock duration must be between 1 and {max} blocks")]
+    InvalidLockDuration { max: u64 },
+
+    #[error("An extended lock must end after the current lock ({current_end})")]
+    LockNotExtended { current_end: u64 },
+
+    #[error(
+        "Address already has a lock. Increase or extend it, or withdraw it once it has ended."
+    )]
+    LockExists {},
+
+    #[error("Address has no lock")]
+    NoLock {},
+
+    #[error("Lock ended at height ({end}) and may only be withdrawn")]
+    LockEnded { end: u64 },
+
+    #[error("Lock does not end until height ({end})")]
+    LockNotEnded { end: u64 },
+
+    #[error("Maximum lock duration must be non-zero")]
+    ZeroMaxLockDuration {},
+
+    #[error("Epoch length must be between 1 and the maximum lock duration, which may span at most ({max_epochs}) epochs")]
+    InvalidEpochLength { max_epochs: u64 },
+
+    #[error("Lock must last until at least the next epoch boundary. Epochs are ({epoch_length}) blocks long.")]
+    LockTooShort { epoch_length: u64 },
+}
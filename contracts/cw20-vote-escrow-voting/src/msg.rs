@@ -0,0 +1,55 @@
+use cw20::Cw20ReceiveMsg;
+use cw_core_macros::voting_query;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::Lock;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The cw20 token that may be locked for voting power.
+    pub token_address: String,
+    /// The longest a lock may last, in blocks. Tokens locked for this
+    /// long receive one unit of voting power per token.
+    pub max_lock_duration: u64,
+    /// Locks end at multiples of this many blocks. The maximum lock
+    /// duration may span at most 256 epochs.
+    pub epoch_length: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+    /// Extends the sender's lock so that it ends `duration` blocks
+    /// from now, rounded down to the start of an epoch.
+    ExtendLock {
+        duration: u64,
+    },
+    /// Returns the sender's tokens once their lock has ended.
+    Withdraw {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    /// Locks the sent tokens for `duration` blocks, rounded down to
+    /// the start of an epoch.
+    CreateLock { duration: u64 },
+    /// Adds the sent tokens to the sender's existing lock.
+    IncreaseAmount {},
+}
+
+#[voting_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    Dao {},
+    Lock { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockResponse {
+    pub lock: Option<Lock>,
+}
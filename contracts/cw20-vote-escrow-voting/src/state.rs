@@ -0,0 +1,80 @@
+use cosmwasm_std::{Addr, Uint128, Uint256};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub token_address: Addr,
+    pub max_lock_duration: u64,
+    pub epoch_length: u64,
+}
+
+/// The most epochs the maximum lock duration may span. Computing
+/// total power takes at most one storage read per epoch.
+pub const MAX_EPOCHS: u64 = 256;
+
+/// Tokens locked until the height `end`, which is always a multiple
+/// of the epoch length.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Lock {
+    pub amount: Uint128,
+    pub end: u64,
+}
+
+impl Lock {
+    /// Voting power at `height`. Decays linearly from `amount` for a
+    /// lock of the maximum duration to zero at `end`.
+    pub fn power(&self, height: u64, max_lock_duration: u64) -> Uint128 {
+        if height >= self.end {
+            return Uint128::zero();
+        }
+        self.amount
+            .multiply_ratio(self.end - height, max_lock_duration)
+    }
+}
+
+/// Sums over a set of locks, from which their total power at any
+/// height before they end can be computed as
+/// `(weighted_end - amount * height) / max_lock_duration`.
+///
+/// `amount` is the rate at which their power decays, scaled by
+/// `max_lock_duration`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct LockTotals {
+    /// The sum of the locks' amounts.
+    pub amount: Uint256,
+    /// The sum of each lock's amount multiplied by its end height.
+    pub weighted_end: Uint256,
+}
+
+/// The totals of the locks active at `height`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Checkpoint {
+    pub height: u64,
+    pub totals: LockTotals,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const DAO: Item<Addr> = Item::new("dao");
+
+pub const LOCKS: SnapshotMap<&Addr, Lock> = SnapshotMap::new(
+    "locks",
+    "locks__checkpoints",
+    "locks__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The totals of the active locks as of the last time a lock was
+/// created, increased or extended. The totals at a later height are
+/// found by removing the slope changes at the epoch boundaries in
+/// between.
+pub const CHECKPOINT: SnapshotItem<Checkpoint> = SnapshotItem::new(
+    "checkpoint",
+    "checkpoint__checkpoints",
+    "checkpoint__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The totals of the locks ending at each epoch boundary.
+pub const SLOPE_CHANGES: Map<u64, LockTotals> = Map::new("slope_changes");
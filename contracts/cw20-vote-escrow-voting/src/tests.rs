@@ -0,0 +1,566 @@
+use cosmwasm_std::{to_binary, Addr, Empty, Uint128};
+use cw20::Cw20Coin;
+use cw_core_interface::voting::{TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, LockResponse, QueryMsg, ReceiveMsg},
+    state::{Lock, MAX_EPOCHS},
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+const MAX_LOCK_DURATION: u64 = 100;
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn escrow_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// Instantiates a token and a voting module for it. Epochs of one
+/// block leave lock ends unrounded.
+fn setup(epoch_length: u64) -> (App, Addr, Addr) {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_contract());
+    let voting_id = app.store_code(escrow_voting_contract());
+
+    let token_addr = app
+        .instantiate_contract(
+            cw20_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw20_base::msg::InstantiateMsg {
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![
+                    Cw20Coin {
+                        address: ADDR1.to_string(),
+                        amount: Uint128::new(1000),
+                    },
+                    Cw20Coin {
+                        address: ADDR2.to_string(),
+                        amount: Uint128::new(1000),
+                    },
+                ],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "token",
+            None,
+        )
+        .unwrap();
+    let voting_addr = app
+        .instantiate_contract(
+            voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                token_address: token_addr.to_string(),
+                max_lock_duration: MAX_LOCK_DURATION,
+                epoch_length,
+            },
+            &[],
+            "voting module",
+            None,
+        )
+        .unwrap();
+
+    (app, token_addr, voting_addr)
+}
+
+fn send(
+    app: &mut App,
+    token_addr: &Addr,
+    voting_addr: &Addr,
+    sender: &str,
+    amount: u128,
+    msg: ReceiveMsg,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        token_addr.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: voting_addr.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_binary(&msg).unwrap(),
+        },
+        &[],
+    )
+}
+
+fn voting_power(app: &App, voting_addr: &Addr, address: &str, height: u64) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height: Some(height),
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+fn total_power(app: &App, voting_addr: &Addr, height: u64) -> Uint128 {
+    let res: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr,
+            &QueryMsg::TotalPowerAtHeight {
+                height: Some(height),
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+#[test]
+fn test_power_decays() {
+    let (mut app, token_addr, voting_addr) = setup(1);
+    let start = app.block_info().height;
+
+    send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR1,
+        100,
+        ReceiveMsg::CreateLock { duration: 100 },
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 1);
+    send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR2,
+        200,
+        ReceiveMsg::CreateLock { duration: 50 },
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 100);
+
+    // Power is snapshotted at the start of each block.
+    assert_eq!(total_power(&app, &voting_addr, start), Uint128::zero());
+    assert_eq!(
+        voting_power(&app, &voting_addr, ADDR1, start + 1),
+        Uint128::new(99)
+    );
+    assert_eq!(total_power(&app, &voting_addr, start + 1), Uint128::new(99));
+
+    assert_eq!(
+        voting_power(&app, &voting_addr, ADDR1, start + 11),
+        Uint128::new(89)
+    );
+    assert_eq!(
+        voting_power(&app, &voting_addr, ADDR2, start + 11),
+        Uint128::new(80)
+    );
+    assert_eq!(
+        total_power(&app, &voting_addr, start + 11),
+        Uint128::new(169)
+    );
+
+    // ADDR2's lock has ended.
+    assert_eq!(
+        voting_power(&app, &voting_addr, ADDR2, start + 51),
+        Uint128::zero()
+    );
+    assert_eq!(
+        total_power(&app, &voting_addr, start + 60),
+        Uint128::new(40)
+    );
+    assert_eq!(
+        total_power(&app, &voting_addr, start + 100),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_increase_extend_withdraw() {
+    let (mut app, token_addr, voting_addr) = setup(1);
+    let start = app.block_info().height;
+
+    let err: ContractError = send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR1,
+        100,
+        ReceiveMsg::CreateLock {
+            duration: MAX_LOCK_DURATION + 1,
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidLockDuration {
+            max: MAX_LOCK_DURATION
+        }
+    );
+
+    send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR1,
+        100,
+        ReceiveMsg::CreateLock { duration: 20 },
+    )
+    .unwrap();
+    let err: ContractError = send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR1,
+        100,
+        ReceiveMsg::CreateLock { duration: 20 },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::LockExists {});
+    app.update_block(|block| block.height += 10);
+
+    send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR1,
+        100,
+        ReceiveMsg::IncreaseAmount {},
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::ExtendLock { duration: 5 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::LockNotExtended {
+            current_end: start + 20
+        }
+    );
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting_addr.clone(),
+        &ExecuteMsg::ExtendLock { duration: 50 },
+        &[],
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 1);
+
+    // Power before the changes is unaffected by them.
+    assert_eq!(
+        voting_power(&app, &voting_addr, ADDR1, start + 10),
+        Uint128::new(10)
+    );
+    assert_eq!(
+        total_power(&app, &voting_addr, start + 10),
+        Uint128::new(10)
+    );
+    assert_eq!(
+        voting_power(&app, &voting_addr, ADDR1, start + 11),
+        Uint128::new(98)
+    );
+    assert_eq!(
+        total_power(&app, &voting_addr, start + 11),
+        Uint128::new(98)
+    );
+    // The old end no longer applies.
+    assert_eq!(
+        total_power(&app, &voting_addr, start + 30),
+        Uint128::new(60)
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::Withdraw {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::LockNotEnded { end: start + 60 });
+
+    app.update_block(|block| block.height += 50);
+    let err: ContractError = send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR1,
+        100,
+        ReceiveMsg::IncreaseAmount {},
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::LockEnded { end: start + 60 });
+
+    let lock: LockResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting_addr,
+            &QueryMsg::Lock {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        lock.lock,
+        Some(Lock {
+            amount: Uint128::new(200),
+            end: start + 60,
+        })
+    );
+
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting_addr.clone(),
+        &ExecuteMsg::Withdraw {},
+        &[],
+    )
+    .unwrap();
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &token_addr,
+            &cw20::Cw20QueryMsg::Balance {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(1000));
+    app.update_block(|block| block.height += 1);
+
+    // Withdrawing does not change past power.
+    assert_eq!(
+        total_power(&app, &voting_addr, start + 11),
+        Uint128::new(98)
+    );
+    assert_eq!(
+        total_power(&app, &voting_addr, app.block_info().height),
+        Uint128::zero()
+    );
+
+    // A new lock may be created once the old one is withdrawn.
+    send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR1,
+        100,
+        ReceiveMsg::CreateLock { duration: 100 },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_invalid_token() {
+    let (mut app, _, voting_addr) = setup(1);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::Receive(cw20::Cw20ReceiveMsg {
+                sender: ADDR1.to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&ReceiveMsg::CreateLock { duration: 10 }).unwrap(),
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidToken { .. }));
+}
+
+fn lock_end(app: &App, voting_addr: &Addr, address: &str) -> u64 {
+    let lock: LockResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr,
+            &QueryMsg::Lock {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    lock.lock.unwrap().end
+}
+
+#[test]
+fn test_epochs() {
+    let (mut app, token_addr, voting_addr) = setup(10);
+    app.update_block(|block| block.height = 13003);
+
+    // Lock ends are rounded down to the start of an epoch.
+    let err: ContractError = send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR1,
+        100,
+        ReceiveMsg::CreateLock { duration: 5 },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::LockTooShort { epoch_length: 10 });
+    send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR1,
+        100,
+        ReceiveMsg::CreateLock { duration: 15 },
+    )
+    .unwrap();
+    assert_eq!(lock_end(&app, &voting_addr, ADDR1), 13010);
+
+    // Extending within the same epoch does not extend the lock.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::ExtendLock { duration: 16 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::LockNotExtended { current_end: 13010 });
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting_addr.clone(),
+        &ExecuteMsg::ExtendLock { duration: 17 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(lock_end(&app, &voting_addr, ADDR1), 13020);
+
+    // The maximum lock duration must span a bounded number of epochs.
+    let voting_id = app.store_code(escrow_voting_contract());
+    for (max_lock_duration, epoch_length) in [
+        (MAX_LOCK_DURATION, 0),
+        (MAX_LOCK_DURATION, MAX_LOCK_DURATION + 1),
+        (MAX_EPOCHS + 1, 1),
+    ] {
+        let err: ContractError = app
+            .instantiate_contract(
+                voting_id,
+                Addr::unchecked(DAO_ADDR),
+                &InstantiateMsg {
+                    token_address: token_addr.to_string(),
+                    max_lock_duration,
+                    epoch_length,
+                },
+                &[],
+                "voting module",
+                None,
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(
+            err,
+            ContractError::InvalidEpochLength {
+                max_epochs: MAX_EPOCHS
+            }
+        );
+    }
+}
+
+#[test]
+fn test_total_power_matches_locks() {
+    let (mut app, token_addr, voting_addr) = setup(10);
+    app.update_block(|block| block.height = 13000);
+
+    // Amounts are multiples of the maximum lock duration so that
+    // individual powers are not rounded.
+    send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR1,
+        300,
+        ReceiveMsg::CreateLock { duration: 100 },
+    )
+    .unwrap();
+    send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR2,
+        200,
+        ReceiveMsg::CreateLock { duration: 45 },
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 23);
+
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        voting_addr.clone(),
+        &ExecuteMsg::ExtendLock { duration: 70 },
+        &[],
+    )
+    .unwrap();
+    send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR1,
+        500,
+        ReceiveMsg::IncreaseAmount {},
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 31);
+    send(
+        &mut app,
+        &token_addr,
+        &voting_addr,
+        ADDR2,
+        100,
+        ReceiveMsg::IncreaseAmount {},
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 200);
+
+    for height in 13000..13120 {
+        let sum = voting_power(&app, &voting_addr, ADDR1, height)
+            + voting_power(&app, &voting_addr, ADDR2, height);
+        assert_eq!(total_power(&app, &voting_addr, height), sum, "{}", height);
+    }
+    assert_eq!(total_power(&app, &voting_addr, 13100), Uint128::zero());
+    assert_eq!(total_power(&app, &voting_addr, 113000), Uint128::zero());
+    assert_eq!(
+        total_power(&app, &voting_addr, 13055),
+        Uint128::new(800 * 45 / 100 + 300 * 35 / 100)
+    );
+}
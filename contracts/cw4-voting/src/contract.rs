@@ -1,21 +1,30 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
-    SubMsg, Uint128, WasmMsg,
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError,
+    StdResult, SubMsg, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
 use cw_utils::parse_reply_instantiate_data;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{DAO_ADDRESS, GROUP_CONTRACT, TOTAL_WEIGHT, USER_WEIGHTS};
+use crate::msg::{
+    ActiveThreshold, ActiveThresholdResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use crate::state::{
+    ACTIVE_THRESHOLD, DAO_ADDRESS, GROUP_CONTRACT, MEMBER_COUNT, TOTAL_WEIGHT, USER_WEIGHTS,
+};
+use cw_core_interface::voting::IsActiveResponse;
 
 const CONTRACT_NAME: &str = "crates.io:cw4-voting";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const INSTANTIATE_GROUP_REPLY_ID: u64 = 0;
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -29,19 +38,30 @@ pub fn instantiate(
     }
 
     let mut total_weight = Uint128::zero();
+    let mut member_count = 0u64;
     for member in msg.initial_members.iter() {
         let member_addr = deps.api.addr_validate(&member.addr)?;
         let weight = Uint128::from(member.weight);
         USER_WEIGHTS.save(deps.storage, &member_addr, &weight, env.block.height)?;
         total_weight += weight;
+        if !weight.is_zero() {
+            member_count += 1;
+        }
     }
 
     if total_weight.is_zero() {
         return Err(ContractError::ZeroTotalWeight {});
     }
     TOTAL_WEIGHT.save(deps.storage, &total_weight, env.block.height)?;
+    MEMBER_COUNT.save(deps.storage, &member_count)?;
+
+    if let Some(active_threshold) = msg.active_threshold {
+        assert_valid_active_threshold(&active_threshold)?;
+        ACTIVE_THRESHOLD.save(deps.storage, &active_threshold)?;
+    }
 
-    // We need to set ourself as the CW4 admin it is then transferred to the DAO in the reply
+    // We remain the CW4 admin so that the DAO can manage membership
+    // through this contract with `UpdateMembers`.
     let msg = WasmMsg::Instantiate {
         admin: Some(info.sender.to_string()),
         code_id: msg.cw4_group_code_id,
@@ -62,6 +82,20 @@ pub fn instantiate(
         .add_submessage(msg))
 }
 
+/// A threshold of zero would make the DAO active regardless of its
+/// membership, so it is rejected. No threshold should be set instead.
+fn assert_valid_active_threshold(active_threshold: &ActiveThreshold) -> Result<(), ContractError> {
+    match active_threshold {
+        ActiveThreshold::MemberCount { count } if *count == 0 => {
+            Err(ContractError::InvalidActiveCount {})
+        }
+        ActiveThreshold::TotalWeight { weight } if weight.is_zero() => {
+            Err(ContractError::InvalidActiveWeight {})
+        }
+        _ => Ok(()),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -73,9 +107,89 @@ pub fn execute(
         ExecuteMsg::MemberChangedHook { diffs } => {
             execute_member_changed_hook(deps, env, info, diffs)
         }
+        ExecuteMsg::UpdateMembers { remove, add } => execute_forward_to_group(
+            deps,
+            info,
+            "update_members",
+            cw4_group::msg::ExecuteMsg::UpdateMembers { remove, add },
+        ),
+        ExecuteMsg::UpdateActiveThreshold { new_threshold } => {
+            execute_update_active_threshold(deps, info, new_threshold)
+        }
+        ExecuteMsg::AddGroupHook { addr } => execute_forward_to_group(
+            deps,
+            info,
+            "add_group_hook",
+            cw4_group::msg::ExecuteMsg::AddHook { addr },
+        ),
+        ExecuteMsg::RemoveGroupHook { addr } => {
+            // Voting power would stop tracking membership.
+            if addr == env.contract.address.as_str() {
+                return Err(ContractError::CannotRemoveOwnHook {});
+            }
+            execute_forward_to_group(
+                deps,
+                info,
+                "remove_group_hook",
+                cw4_group::msg::ExecuteMsg::RemoveHook { addr },
+            )
+        }
+        ExecuteMsg::UpdateGroupAdmin { admin } => execute_forward_to_group(
+            deps,
+            info,
+            "update_group_admin",
+            cw4_group::msg::ExecuteMsg::UpdateAdmin { admin },
+        ),
     }
 }
 
+pub fn execute_update_active_threshold(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_active_threshold: Option<ActiveThreshold>,
+) -> Result<Response, ContractError> {
+    let dao = DAO_ADDRESS.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(active_threshold) = new_active_threshold {
+        assert_valid_active_threshold(&active_threshold)?;
+        ACTIVE_THRESHOLD.save(deps.storage, &active_threshold)?;
+    } else {
+        ACTIVE_THRESHOLD.remove(deps.storage);
+    }
+
+    Ok(Response::new().add_attribute("action", "update_active_threshold"))
+}
+
+/// Sends `msg` to the group contract, of which this contract is the
+/// admin. Only callable by the DAO.
+pub fn execute_forward_to_group(
+    deps: DepsMut,
+    info: MessageInfo,
+    action: &str,
+    msg: cw4_group::msg::ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let dao = DAO_ADDRESS.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Membership changes update weights when the group calls our
+    // member changed hook.
+    let group_contract = GROUP_CONTRACT.load(deps.storage)?;
+    let msg = WasmMsg::Execute {
+        contract_addr: group_contract.to_string(),
+        msg: to_binary(&msg)?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", action)
+        .add_message(msg))
+}
+
 pub fn execute_member_changed_hook(
     deps: DepsMut,
     env: Env,
@@ -92,6 +206,7 @@ pub fn execute_member_changed_hook(
     // In seperate counters to apply at once and prevent underflow
     let mut positive_difference: Uint128 = Uint128::zero();
     let mut negative_difference: Uint128 = Uint128::zero();
+    let mut member_count = MEMBER_COUNT.load(deps.storage)?;
     for diff in diffs {
         let user_address = deps.api.addr_validate(&diff.key)?;
         let weight = diff.new.unwrap_or_default();
@@ -102,6 +217,11 @@ pub fn execute_member_changed_hook(
         } else {
             negative_difference += Uint128::from(old - weight);
         }
+        if old == 0 && weight != 0 {
+            member_count += 1;
+        } else if old != 0 && weight == 0 {
+            member_count -= 1;
+        }
         USER_WEIGHTS.save(
             deps.storage,
             &user_address,
@@ -115,6 +235,7 @@ pub fn execute_member_changed_hook(
         .checked_sub(negative_difference)
         .map_err(StdError::overflow)?;
     TOTAL_WEIGHT.save(deps.storage, &new_total_weight, env.block.height)?;
+    MEMBER_COUNT.save(deps.storage, &member_count)?;
 
     Ok(Response::new()
         .add_attribute("action", "member_changed_hook")
@@ -131,9 +252,77 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::GroupContract {} => to_binary(&GROUP_CONTRACT.load(deps.storage)?),
         QueryMsg::Dao {} => to_binary(&DAO_ADDRESS.load(deps.storage)?),
+        QueryMsg::IsActive {} => query_is_active(deps),
+        QueryMsg::ActiveThreshold {} => query_active_threshold(deps),
+        QueryMsg::ListMembers { start_after, limit } => {
+            query_list_members(deps, start_after, limit)
+        }
+        QueryMsg::MemberAtHeight { address, height } => {
+            query_member_at_height(deps, env, address, height)
+        }
     }
 }
 
+pub fn query_is_active(deps: Deps) -> StdResult<Binary> {
+    let active = match ACTIVE_THRESHOLD.may_load(deps.storage)? {
+        Some(ActiveThreshold::MemberCount { count }) => MEMBER_COUNT.load(deps.storage)? >= count,
+        Some(ActiveThreshold::TotalWeight { weight }) => TOTAL_WEIGHT.load(deps.storage)? >= weight,
+        None => true,
+    };
+    to_binary(&IsActiveResponse { active })
+}
+
+pub fn query_active_threshold(deps: Deps) -> StdResult<Binary> {
+    to_binary(&ActiveThresholdResponse {
+        active_threshold: ACTIVE_THRESHOLD.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_list_members(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let min = start_after.as_ref().map(Bound::exclusive);
+
+    // Removed members remain in storage with a weight of zero so
+    // that their weight at past heights may still be queried.
+    let members = USER_WEIGHTS
+        .range(deps.storage, min, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((_, weight)) if weight.is_zero()))
+        .take(limit)
+        .map(|item| {
+            let (addr, weight) = item?;
+            Ok(cw4::Member {
+                addr: addr.into_string(),
+                // Weights are set from cw4 members so always fit in a u64.
+                weight: weight.u128() as u64,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&cw4::MemberListResponse { members })
+}
+
+pub fn query_member_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+    let weight = USER_WEIGHTS
+        .may_load_at_height(deps.storage, &address, height)?
+        .filter(|weight| !weight.is_zero())
+        .map(|weight| weight.u128() as u64);
+    to_binary(&cw4::MemberResponse { weight })
+}
+
 pub fn query_voting_power_at_height(
     deps: Deps,
     env: Env,
@@ -162,6 +351,26 @@ pub fn query_info(deps: Deps) -> StdResult<Binary> {
     to_binary(&cw_core_interface::voting::InfoResponse { info })
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Contracts instantiated before the member count was tracked
+    // count their members once.
+    if MEMBER_COUNT.may_load(deps.storage)?.is_none() {
+        let mut member_count = 0u64;
+        for item in USER_WEIGHTS.range(deps.storage, None, None, Order::Ascending) {
+            let (_, weight) = item?;
+            if !weight.is_zero() {
+                member_count += 1;
+            }
+        }
+        MEMBER_COUNT.save(deps.storage, &member_count)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "migrate"))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
@@ -174,27 +383,17 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                         return Err(ContractError::DuplicateGroupContract {});
                     }
                     let group_contract = deps.api.addr_validate(&res.contract_address)?;
-                    let dao_address = DAO_ADDRESS.load(deps.storage)?;
                     GROUP_CONTRACT.save(deps.storage, &group_contract)?;
-                    let msg1 = WasmMsg::Execute {
+                    let msg = WasmMsg::Execute {
                         contract_addr: group_contract.to_string(),
                         msg: to_binary(&cw4_group::msg::ExecuteMsg::AddHook {
                             addr: env.contract.address.to_string(),
                         })?,
                         funds: vec![],
                     };
-                    // Transfer admin status to the DAO
-                    let msg2 = WasmMsg::Execute {
-                        contract_addr: group_contract.to_string(),
-                        msg: to_binary(&cw4_group::msg::ExecuteMsg::UpdateAdmin {
-                            admin: Some(dao_address.to_string()),
-                        })?,
-                        funds: vec![],
-                    };
                     Ok(Response::default()
                         .add_attribute("group_contract_address", group_contract)
-                        .add_message(msg1)
-                        .add_message(msg2))
+                        .add_message(msg))
                 }
                 Err(_) => Err(ContractError::GroupContractInstantiateError {}),
             }
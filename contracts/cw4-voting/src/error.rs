@@ -21,6 +21,15 @@ pub enum ContractError {
     #[error("Total weight of the CW4 contract cannot be zero")]
     ZeroTotalWeight {},
 
+    #[error("Active threshold member count must be greater than zero")]
+    InvalidActiveCount {},
+
+    #[error("Active threshold weight must be greater than zero")]
+    InvalidActiveWeight {},
+
+    #[error("Can not remove this contract's hook from the group contract")]
+    CannotRemoveOwnHook {},
+
     #[error("Got a submessage reply with unknown id: {id}")]
     UnknownReplyId { id: u64 },
 }
@@ -1,23 +1,86 @@
-use cw_core_macros::voting_query;
+use cosmwasm_std::Uint128;
+use cw_core_macros::{active_query, voting_query};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ActiveThreshold {
+    /// The DAO is active once it has at least this many members.
+    /// Must be greater than zero.
+    MemberCount { count: u64 },
+    /// The DAO is active once the total weight of its members is at
+    /// least this amount. Must be greater than zero.
+    TotalWeight { weight: Uint128 },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub cw4_group_code_id: u64,
     pub initial_members: Vec<cw4::Member>,
+    pub active_threshold: Option<ActiveThreshold>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    MemberChangedHook { diffs: Vec<cw4::MemberDiff> },
+    MemberChangedHook {
+        diffs: Vec<cw4::MemberDiff>,
+    },
+    /// Adds, updates, and removes members of the group contract. Only
+    /// callable by the DAO.
+    UpdateMembers {
+        remove: Vec<String>,
+        add: Vec<cw4::Member>,
+    },
+    /// Updates the active threshold. Only callable by the DAO.
+    UpdateActiveThreshold {
+        new_threshold: Option<ActiveThreshold>,
+    },
+    /// Adds a hook to the group contract. Only callable by the DAO.
+    AddGroupHook {
+        addr: String,
+    },
+    /// Removes a hook from the group contract. This contract's own
+    /// hook may not be removed. Only callable by the DAO.
+    RemoveGroupHook {
+        addr: String,
+    },
+    /// Sets the admin of the group contract. Once this contract is no
+    /// longer the admin, messages forwarded to the group will
+    /// fail. Only callable by the DAO.
+    UpdateGroupAdmin {
+        admin: Option<String>,
+    },
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[voting_query]
+#[active_query]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     GroupContract {},
     Dao {},
+    ActiveThreshold {},
+    /// Lists members with a non-zero weight. Returns
+    /// cw4::MemberListResponse.
+    ListMembers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Gets the weight of a member at a height, defaulting to the
+    /// current block. Returns cw4::MemberResponse.
+    MemberAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ActiveThresholdResponse {
+    pub active_threshold: Option<ActiveThreshold>,
 }
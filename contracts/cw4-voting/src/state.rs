@@ -1,6 +1,8 @@
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
 
+use crate::msg::ActiveThreshold;
+
 pub const USER_WEIGHTS: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
     "user_weights",
     "user_weights__checkpoints",
@@ -17,3 +19,6 @@ pub const TOTAL_WEIGHT: SnapshotItem<Uint128> = SnapshotItem::new(
 
 pub const GROUP_CONTRACT: Item<Addr> = Item::new("group_contract");
 pub const DAO_ADDRESS: Item<Addr> = Item::new("dao_address");
+pub const ACTIVE_THRESHOLD: Item<ActiveThreshold> = Item::new("active_threshold");
+/// The number of members with a non-zero weight.
+pub const MEMBER_COUNT: Item<u64> = Item::new("member_count");
@@ -1,12 +1,14 @@
 use cosmwasm_std::{Addr, Empty, Uint128};
 use cw2::ContractVersion;
 use cw_core_interface::voting::{
-    InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+    InfoResponse, IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
 use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
 
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{
+        ActiveThreshold, ActiveThresholdResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    },
     ContractError,
 };
 
@@ -30,7 +32,8 @@ fn voting_contract() -> Box<dyn Contract<Empty>> {
         crate::contract::instantiate,
         crate::contract::query,
     )
-    .with_reply(crate::contract::reply);
+    .with_reply(crate::contract::reply)
+    .with_migrate(crate::contract::migrate);
     Box::new(contract)
 }
 
@@ -70,6 +73,7 @@ fn setup_test_case(app: &mut App) -> Addr {
         InstantiateMsg {
             cw4_group_code_id: cw4_id,
             initial_members: members,
+            active_threshold: None,
         },
     )
 }
@@ -86,6 +90,7 @@ fn test_instantiate() {
     let msg = InstantiateMsg {
         cw4_group_code_id: cw4_id,
         initial_members: vec![],
+        active_threshold: None,
     };
     let _err = app
         .instantiate_contract(
@@ -115,6 +120,7 @@ fn test_instantiate() {
                 weight: 0,
             },
         ],
+        active_threshold: None,
     };
     let _err = app
         .instantiate_contract(
@@ -126,6 +132,29 @@ fn test_instantiate() {
             None,
         )
         .unwrap_err();
+
+    // Instantiate with a zero active threshold, error
+    let msg = InstantiateMsg {
+        cw4_group_code_id: cw4_id,
+        initial_members: vec![cw4::Member {
+            addr: ADDR1.to_string(),
+            weight: 1,
+        }],
+        active_threshold: Some(ActiveThreshold::MemberCount { count: 0 }),
+    };
+    let err: ContractError = app
+        .instantiate_contract(
+            voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &msg,
+            &[],
+            "voting module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidActiveCount {}));
 }
 
 #[test]
@@ -182,7 +211,7 @@ fn test_permissions() {
     let err: ContractError = app
         .execute_contract(
             voting_addr.clone(),
-            voting_addr,
+            voting_addr.clone(),
             &ExecuteMsg::MemberChangedHook { diffs: vec![] },
             &[],
         )
@@ -190,6 +219,25 @@ fn test_permissions() {
         .downcast()
         .unwrap();
     assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Only the DAO may update members.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr,
+            &ExecuteMsg::UpdateMembers {
+                remove: vec![],
+                add: vec![cw4::Member {
+                    addr: ADDR1.to_string(),
+                    weight: 100,
+                }],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
 }
 
 #[test]
@@ -227,7 +275,7 @@ fn test_power_at_height() {
     assert_eq!(total_voting_power.height, app.block_info().height);
 
     // Update ADDR1's weight to 2
-    let msg = cw4_group::msg::ExecuteMsg::UpdateMembers {
+    let msg = ExecuteMsg::UpdateMembers {
         remove: vec![],
         add: vec![cw4::Member {
             addr: ADDR1.to_string(),
@@ -262,7 +310,7 @@ fn test_power_at_height() {
         .unwrap();
     assert_eq!(cw4_power.weight.unwrap(), 1);
 
-    app.execute_contract(Addr::unchecked(DAO_ADDR), cw4_addr.clone(), &msg, &[])
+    app.execute_contract(Addr::unchecked(DAO_ADDR), voting_addr.clone(), &msg, &[])
         .unwrap();
     app.update_block(next_block);
 
@@ -319,7 +367,7 @@ fn test_power_at_height() {
     assert_eq!(total_voting_power.height, app.block_info().height - 1);
 
     // Update ADDR1's weight back to 1
-    let msg = cw4_group::msg::ExecuteMsg::UpdateMembers {
+    let msg = ExecuteMsg::UpdateMembers {
         remove: vec![],
         add: vec![cw4::Member {
             addr: ADDR1.to_string(),
@@ -327,7 +375,7 @@ fn test_power_at_height() {
         }],
     };
 
-    app.execute_contract(Addr::unchecked(DAO_ADDR), cw4_addr.clone(), &msg, &[])
+    app.execute_contract(Addr::unchecked(DAO_ADDR), voting_addr.clone(), &msg, &[])
         .unwrap();
     app.update_block(next_block);
 
@@ -370,12 +418,12 @@ fn test_power_at_height() {
     assert_eq!(total_voting_power.height, app.block_info().height - 1);
 
     // Remove address 2 completely
-    let msg = cw4_group::msg::ExecuteMsg::UpdateMembers {
+    let msg = ExecuteMsg::UpdateMembers {
         remove: vec![ADDR2.to_string()],
         add: vec![],
     };
 
-    app.execute_contract(Addr::unchecked(DAO_ADDR), cw4_addr.clone(), &msg, &[])
+    app.execute_contract(Addr::unchecked(DAO_ADDR), voting_addr.clone(), &msg, &[])
         .unwrap();
     app.update_block(next_block);
 
@@ -418,7 +466,7 @@ fn test_power_at_height() {
     assert_eq!(total_voting_power.height, app.block_info().height - 1);
 
     // Readd ADDR2 with 10 power
-    let msg = cw4_group::msg::ExecuteMsg::UpdateMembers {
+    let msg = ExecuteMsg::UpdateMembers {
         remove: vec![],
         add: vec![cw4::Member {
             addr: ADDR2.to_string(),
@@ -426,7 +474,7 @@ fn test_power_at_height() {
         }],
     };
 
-    app.execute_contract(Addr::unchecked(DAO_ADDR), cw4_addr, &msg, &[])
+    app.execute_contract(Addr::unchecked(DAO_ADDR), voting_addr.clone(), &msg, &[])
         .unwrap();
     app.update_block(next_block);
 
@@ -468,3 +516,432 @@ fn test_power_at_height() {
     assert_eq!(total_voting_power.power, Uint128::new(2u128));
     assert_eq!(total_voting_power.height, app.block_info().height - 1);
 }
+
+#[test]
+fn test_list_members() {
+    let mut app = App::default();
+    let voting_addr = setup_test_case(&mut app);
+    app.update_block(next_block);
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateMembers {
+            remove: vec![ADDR2.to_string()],
+            add: vec![cw4::Member {
+                addr: ADDR3.to_string(),
+                weight: 5,
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    let updated_height = app.block_info().height;
+    app.update_block(next_block);
+
+    // Removed members are not listed.
+    let members: cw4::MemberListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::ListMembers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        members.members,
+        vec![
+            cw4::Member {
+                addr: ADDR1.to_string(),
+                weight: 1,
+            },
+            cw4::Member {
+                addr: ADDR3.to_string(),
+                weight: 5,
+            },
+        ]
+    );
+
+    let members: cw4::MemberListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::ListMembers {
+                start_after: Some(ADDR1.to_string()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        members.members,
+        vec![cw4::Member {
+            addr: ADDR3.to_string(),
+            weight: 5,
+        }]
+    );
+
+    let member: cw4::MemberResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::MemberAtHeight {
+                address: ADDR2.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(member.weight, None);
+
+    let member: cw4::MemberResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr,
+            &QueryMsg::MemberAtHeight {
+                address: ADDR2.to_string(),
+                height: Some(updated_height),
+            },
+        )
+        .unwrap();
+    assert_eq!(member.weight, Some(1));
+}
+
+#[test]
+fn test_active_threshold() {
+    let mut app = App::default();
+    let cw4_id = app.store_code(cw4_contract());
+    let voting_id = app.store_code(voting_contract());
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            cw4_group_code_id: cw4_id,
+            initial_members: vec![
+                cw4::Member {
+                    addr: ADDR1.to_string(),
+                    weight: 1,
+                },
+                cw4::Member {
+                    addr: ADDR2.to_string(),
+                    weight: 1,
+                },
+            ],
+            active_threshold: Some(ActiveThreshold::MemberCount { count: 3 }),
+        },
+    );
+
+    let is_active = |app: &App| -> bool {
+        let res: IsActiveResponse = app
+            .wrap()
+            .query_wasm_smart(&voting_addr, &QueryMsg::IsActive {})
+            .unwrap();
+        res.active
+    };
+
+    // The DAO is inactive until its roster is complete.
+    assert!(!is_active(&app));
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateMembers {
+            remove: vec![],
+            add: vec![cw4::Member {
+                addr: ADDR3.to_string(),
+                weight: 1,
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    assert!(is_active(&app));
+
+    // Increasing a member's weight does not make up for a removed member.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateMembers {
+            remove: vec![ADDR3.to_string()],
+            add: vec![cw4::Member {
+                addr: ADDR1.to_string(),
+                weight: 5,
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    assert!(!is_active(&app));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::UpdateActiveThreshold {
+                new_threshold: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateActiveThreshold {
+            new_threshold: Some(ActiveThreshold::TotalWeight {
+                weight: Uint128::new(6),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    assert!(is_active(&app));
+
+    // Zero thresholds are rejected.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            voting_addr.clone(),
+            &ExecuteMsg::UpdateActiveThreshold {
+                new_threshold: Some(ActiveThreshold::MemberCount { count: 0 }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidActiveCount {}));
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            voting_addr.clone(),
+            &ExecuteMsg::UpdateActiveThreshold {
+                new_threshold: Some(ActiveThreshold::TotalWeight {
+                    weight: Uint128::zero(),
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidActiveWeight {}));
+
+    let threshold: ActiveThresholdResponse = app
+        .wrap()
+        .query_wasm_smart(&voting_addr, &QueryMsg::ActiveThreshold {})
+        .unwrap();
+    assert_eq!(
+        threshold.active_threshold,
+        Some(ActiveThreshold::TotalWeight {
+            weight: Uint128::new(6)
+        })
+    );
+}
+
+#[test]
+fn test_group_hooks_and_admin() {
+    let mut app = App::default();
+    let voting_addr = setup_test_case(&mut app);
+    let group_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::GroupContract {})
+        .unwrap();
+
+    // Only the DAO may manage the group.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::AddGroupHook {
+                addr: ADDR1.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::AddGroupHook {
+            addr: ADDR1.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let hooks: cw4::HooksResponse = app
+        .wrap()
+        .query_wasm_smart(group_addr.clone(), &cw4_group::msg::QueryMsg::Hooks {})
+        .unwrap();
+    assert_eq!(
+        hooks.hooks,
+        vec![voting_addr.to_string(), ADDR1.to_string()]
+    );
+
+    // Our own hook keeps voting power in sync with membership.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            voting_addr.clone(),
+            &ExecuteMsg::RemoveGroupHook {
+                addr: voting_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::CannotRemoveOwnHook {}));
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::RemoveGroupHook {
+            addr: ADDR1.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let hooks: cw4::HooksResponse = app
+        .wrap()
+        .query_wasm_smart(group_addr.clone(), &cw4_group::msg::QueryMsg::Hooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks, vec![voting_addr.to_string()]);
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateGroupAdmin {
+            admin: Some(DAO_ADDR.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    let admin: cw4::AdminResponse = app
+        .wrap()
+        .query_wasm_smart(group_addr, &cw4_group::msg::QueryMsg::Admin {})
+        .unwrap();
+    assert_eq!(admin.admin, Some(DAO_ADDR.to_string()));
+
+    // We are no longer the group admin so can not update members.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr,
+        &ExecuteMsg::UpdateMembers {
+            remove: vec![ADDR1.to_string()],
+            add: vec![],
+        },
+        &[],
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_migrate_from_dao_group_admin() {
+    let mut app = App::default();
+    let cw4_id = app.store_code(cw4_contract());
+    let voting_id = app.store_code(voting_contract());
+    let voting_addr = app
+        .instantiate_contract(
+            voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                cw4_group_code_id: cw4_id,
+                initial_members: vec![cw4::Member {
+                    addr: ADDR1.to_string(),
+                    weight: 1,
+                }],
+                active_threshold: None,
+            },
+            &[],
+            "voting module",
+            Some(DAO_ADDR.to_string()),
+        )
+        .unwrap();
+    let group_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::GroupContract {})
+        .unwrap();
+
+    // Earlier versions of this contract made the DAO the group admin.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateGroupAdmin {
+            admin: Some(DAO_ADDR.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.migrate_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &MigrateMsg {},
+        voting_id,
+    )
+    .unwrap();
+
+    // After migrating the DAO hands the group admin back to this
+    // contract.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        group_addr,
+        &cw4_group::msg::ExecuteMsg::UpdateAdmin {
+            admin: Some(voting_addr.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateMembers {
+            remove: vec![],
+            add: vec![cw4::Member {
+                addr: ADDR2.to_string(),
+                weight: 2,
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let total: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(voting_addr, &QueryMsg::TotalPowerAtHeight { height: None })
+        .unwrap();
+    assert_eq!(total.power, Uint128::new(3));
+}
+
+#[test]
+fn test_migrate_counts_members() {
+    let mut deps = cosmwasm_std::testing::mock_dependencies();
+    let env = cosmwasm_std::testing::mock_env();
+
+    // Contracts instantiated before members were counted have
+    // weights but no count.
+    for (addr, weight) in [(ADDR1, 1u128), (ADDR2, 0), (ADDR3, 5)] {
+        crate::state::USER_WEIGHTS
+            .save(
+                deps.as_mut().storage,
+                &Addr::unchecked(addr),
+                &Uint128::new(weight),
+                env.block.height,
+            )
+            .unwrap();
+    }
+    crate::contract::migrate(deps.as_mut(), env, MigrateMsg {}).unwrap();
+    assert_eq!(
+        crate::state::MEMBER_COUNT
+            .load(deps.as_ref().storage)
+            .unwrap(),
+        2
+    );
+}
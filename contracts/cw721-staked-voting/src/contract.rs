@@ -0,0 +1,427 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response,
+    StdResult, Uint128, Uint256, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw721::{Cw721ReceiveMsg, NumTokensResponse};
+use cw_core_interface::voting::{
+    IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use cw_storage_plus::Bound;
+use cw_utils::Duration;
+use std::convert::TryInto;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ActiveThreshold, ActiveThresholdResponse, ExecuteMsg, InstantiateMsg, NftClaimsResponse,
+    QueryMsg,
+};
+use crate::state::{
+    Config, NftClaim, ACTIVE_THRESHOLD, CONFIG, DAO, MAX_CLAIMS, NFT_BALANCES, NFT_CLAIMS,
+    STAKED_NFTS_PER_OWNER, TOTAL_STAKED_NFTS,
+};
+
+const CONTRACT_NAME: &str = "crates.io:cw721-staked-voting";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// We multiply by this when calculating needed power for being active
+// when using active threshold with percent
+const PRECISION_FACTOR: u128 = 10u128.pow(9);
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    DAO.save(deps.storage, &info.sender)?;
+
+    let nft_address = deps.api.addr_validate(&msg.nft_address)?;
+    let config = Config {
+        nft_address: nft_address.clone(),
+        unstaking_duration: msg.unstaking_duration,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    TOTAL_STAKED_NFTS.save(deps.storage, &Uint128::zero(), env.block.height)?;
+
+    if let Some(active_threshold) = msg.active_threshold {
+        assert_valid_active_threshold(deps.as_ref(), &nft_address, &active_threshold)?;
+        ACTIVE_THRESHOLD.save(deps.storage, &active_threshold)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "instantiate")
+        .add_attribute("nft_address", nft_address))
+}
+
+fn query_nft_supply(deps: Deps, nft_address: &Addr) -> StdResult<Uint128> {
+    let res: NumTokensResponse = deps
+        .querier
+        .query_wasm_smart(nft_address, &cw721::Cw721QueryMsg::NumTokens {})?;
+    Ok(Uint128::from(res.count))
+}
+
+fn assert_valid_active_threshold(
+    deps: Deps,
+    nft_address: &Addr,
+    active_threshold: &ActiveThreshold,
+) -> Result<(), ContractError> {
+    match active_threshold {
+        ActiveThreshold::Percentage { percent } => {
+            if *percent > Decimal::percent(100) || percent.is_zero() {
+                return Err(ContractError::InvalidActivePercentage {});
+            }
+        }
+        ActiveThreshold::AbsoluteCount { count } => {
+            if *count > query_nft_supply(deps, nft_address)? {
+                return Err(ContractError::InvalidAbsoluteCount {});
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(msg) => execute_stake(deps, env, info, msg),
+        ExecuteMsg::Unstake { token_ids } => execute_unstake(deps, env, info, token_ids),
+        ExecuteMsg::ClaimNfts {} => execute_claim_nfts(deps, env, info),
+        ExecuteMsg::UpdateConfig { duration } => execute_update_config(deps, info, duration),
+        ExecuteMsg::UpdateActiveThreshold { new_threshold } => {
+            execute_update_active_threshold(deps, info, new_threshold)
+        }
+    }
+}
+
+pub fn execute_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.nft_address {
+        return Err(ContractError::InvalidNftContract {
+            received: info.sender.into_string(),
+            expected: config.nft_address.into_string(),
+        });
+    }
+    let staker = deps.api.addr_validate(&wrapper.sender)?;
+
+    STAKED_NFTS_PER_OWNER.save(
+        deps.storage,
+        (&staker, &wrapper.token_id),
+        &Empty {},
+        env.block.height,
+    )?;
+    NFT_BALANCES.update(
+        deps.storage,
+        &staker,
+        env.block.height,
+        |balance| -> StdResult<Uint128> {
+            Ok(balance.unwrap_or_default().checked_add(Uint128::new(1))?)
+        },
+    )?;
+    TOTAL_STAKED_NFTS.update(
+        deps.storage,
+        env.block.height,
+        |total| -> StdResult<Uint128> {
+            Ok(total.unwrap_or_default().checked_add(Uint128::new(1))?)
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "stake")
+        .add_attribute("from", staker)
+        .add_attribute("token_id", wrapper.token_id))
+}
+
+pub fn execute_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_ids: Vec<String>,
+) -> Result<Response, ContractError> {
+    if token_ids.is_empty() {
+        return Err(ContractError::ZeroUnstake {});
+    }
+
+    for token_id in &token_ids {
+        if STAKED_NFTS_PER_OWNER
+            .may_load(deps.storage, (&info.sender, token_id))?
+            .is_none()
+        {
+            return Err(ContractError::NotStaked {
+                token_id: token_id.clone(),
+            });
+        }
+        STAKED_NFTS_PER_OWNER.remove(deps.storage, (&info.sender, token_id), env.block.height)?;
+    }
+
+    let count = Uint128::from(token_ids.len() as u128);
+    NFT_BALANCES.update(
+        deps.storage,
+        &info.sender,
+        env.block.height,
+        |balance| -> StdResult<Uint128> { Ok(balance.unwrap_or_default().checked_sub(count)?) },
+    )?;
+    TOTAL_STAKED_NFTS.update(
+        deps.storage,
+        env.block.height,
+        |total| -> StdResult<Uint128> { Ok(total.unwrap_or_default().checked_sub(count)?) },
+    )?;
+
+    let config = CONFIG.load(deps.storage)?;
+    match config.unstaking_duration {
+        None => {
+            let msgs = token_ids
+                .iter()
+                .map(|token_id| transfer_nft_msg(&config.nft_address, &info.sender, token_id))
+                .collect::<StdResult<Vec<_>>>()?;
+            Ok(Response::default()
+                .add_messages(msgs)
+                .add_attribute("action", "unstake")
+                .add_attribute("from", info.sender)
+                .add_attribute("claim_duration", "None"))
+        }
+        Some(duration) => {
+            let mut claims = NFT_CLAIMS
+                .may_load(deps.storage, &info.sender)?
+                .unwrap_or_default();
+            if claims.len() + token_ids.len() > MAX_CLAIMS as usize {
+                return Err(ContractError::TooManyClaims {});
+            }
+            let release_at = duration.after(&env.block);
+            claims.extend(token_ids.into_iter().map(|token_id| NftClaim {
+                token_id,
+                release_at,
+            }));
+            NFT_CLAIMS.save(deps.storage, &info.sender, &claims)?;
+
+            Ok(Response::default()
+                .add_attribute("action", "unstake")
+                .add_attribute("from", info.sender)
+                .add_attribute("claim_duration", format!("{}", duration)))
+        }
+    }
+}
+
+fn transfer_nft_msg(nft_address: &Addr, recipient: &Addr, token_id: &str) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: nft_address.to_string(),
+        msg: to_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: token_id.to_string(),
+        })?,
+        funds: vec![],
+    })
+}
+
+pub fn execute_claim_nfts(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let claims = NFT_CLAIMS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let (released, pending): (Vec<_>, Vec<_>) = claims
+        .into_iter()
+        .partition(|claim| claim.release_at.is_expired(&env.block));
+    if released.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    if pending.is_empty() {
+        NFT_CLAIMS.remove(deps.storage, &info.sender);
+    } else {
+        NFT_CLAIMS.save(deps.storage, &info.sender, &pending)?;
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let msgs = released
+        .iter()
+        .map(|claim| transfer_nft_msg(&config.nft_address, &info.sender, &claim.token_id))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::default()
+        .add_messages(msgs)
+        .add_attribute("action", "claim_nfts")
+        .add_attribute("from", info.sender)
+        .add_attribute("claimed", released.len().to_string()))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    duration: Option<Duration>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.unstaking_duration = duration;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_config")
+        .add_attribute(
+            "unstaking_duration",
+            duration
+                .map(|d| format!("{}", d))
+                .unwrap_or_else(|| "None".to_string()),
+        ))
+}
+
+pub fn execute_update_active_threshold(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_active_threshold: Option<ActiveThreshold>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(active_threshold) = new_active_threshold {
+        let config = CONFIG.load(deps.storage)?;
+        assert_valid_active_threshold(deps.as_ref(), &config.nft_address, &active_threshold)?;
+        ACTIVE_THRESHOLD.save(deps.storage, &active_threshold)?;
+    } else {
+        ACTIVE_THRESHOLD.remove(deps.storage);
+    }
+
+    Ok(Response::new().add_attribute("action", "update_active_threshold"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            query_voting_power_at_height(deps, env, address, height)
+        }
+        QueryMsg::TotalPowerAtHeight { height } => query_total_power_at_height(deps, env, height),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::Config {} => query_config(deps),
+        QueryMsg::Dao {} => query_dao(deps),
+        QueryMsg::IsActive {} => query_is_active(deps),
+        QueryMsg::ActiveThreshold {} => query_active_threshold(deps),
+        QueryMsg::NftClaims { address } => query_nft_claims(deps, address),
+        QueryMsg::StakedNfts {
+            address,
+            start_after,
+            limit,
+        } => query_staked_nfts(deps, address, start_after, limit),
+    }
+}
+
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+    let power = NFT_BALANCES
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+    to_binary(&VotingPowerAtHeightResponse { power, height })
+}
+
+pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    let height = height.unwrap_or(env.block.height);
+    let power = TOTAL_STAKED_NFTS
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+    to_binary(&TotalPowerAtHeightResponse { power, height })
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&cw_core_interface::voting::InfoResponse { info })
+}
+
+pub fn query_config(deps: Deps) -> StdResult<Binary> {
+    to_binary(&CONFIG.load(deps.storage)?)
+}
+
+pub fn query_dao(deps: Deps) -> StdResult<Binary> {
+    let dao = DAO.load(deps.storage)?;
+    to_binary(&dao)
+}
+
+pub fn query_is_active(deps: Deps) -> StdResult<Binary> {
+    let threshold = ACTIVE_THRESHOLD.may_load(deps.storage)?;
+    if let Some(threshold) = threshold {
+        let staked = TOTAL_STAKED_NFTS.load(deps.storage)?;
+        match threshold {
+            ActiveThreshold::AbsoluteCount { count } => to_binary(&IsActiveResponse {
+                active: staked >= count,
+            }),
+            ActiveThreshold::Percentage { percent } => {
+                let config = CONFIG.load(deps.storage)?;
+                let total_power =
+                    query_nft_supply(deps, &config.nft_address)?.full_mul(PRECISION_FACTOR);
+                let applied = total_power.multiply_ratio(
+                    percent.atomics(),
+                    Uint256::from(10u64).pow(percent.decimal_places()),
+                );
+                let rounded = (applied + Uint256::from(PRECISION_FACTOR) - Uint256::from(1u128))
+                    / Uint256::from(PRECISION_FACTOR);
+                let count: Uint128 = rounded.try_into().unwrap();
+                to_binary(&IsActiveResponse {
+                    active: staked >= count,
+                })
+            }
+        }
+    } else {
+        to_binary(&IsActiveResponse { active: true })
+    }
+}
+
+pub fn query_active_threshold(deps: Deps) -> StdResult<Binary> {
+    to_binary(&ActiveThresholdResponse {
+        active_threshold: ACTIVE_THRESHOLD.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_nft_claims(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    to_binary(&NftClaimsResponse {
+        nft_claims: NFT_CLAIMS
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default(),
+    })
+}
+
+pub fn query_staked_nfts(
+    deps: Deps,
+    address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.as_deref().map(Bound::exclusive);
+    let token_ids = STAKED_NFTS_PER_OWNER
+        .prefix(&address)
+        .keys(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<String>>>()?;
+    to_binary(&token_ids)
+}
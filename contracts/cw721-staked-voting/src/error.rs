@@ -0,0 +1,32 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid NFT contract. Got ({received}), expected ({expected})")]
+    InvalidNftContract { received: String, expected: String },
+
+    #[error("Can not unstake an NFT that has not been staked ({token_id})")]
+    NotStaked { token_id: String },
+
+    #[error("Must unstake at least one NFT")]
+    ZeroUnstake {},
+
+    #[error("Too many outstanding claims. Claim some NFTs before unstaking more.")]
+    TooManyClaims {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Active threshold percentage must be greater than 0 and less than 1")]
+    InvalidActivePercentage {},
+
+    #[error("Absolute count threshold cannot be greater than the total NFT supply")]
+    InvalidAbsoluteCount {},
+}
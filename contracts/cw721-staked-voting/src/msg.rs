@@ -0,0 +1,77 @@
+use cosmwasm_std::{Decimal, Uint128};
+use cw721::Cw721ReceiveMsg;
+use cw_core_macros::{active_query, voting_query};
+use cw_utils::Duration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ActiveThreshold {
+    /// The DAO is active once at least this many NFTs are staked.
+    AbsoluteCount { count: Uint128 },
+    /// The DAO is active once at least this percentage of the NFT
+    /// collection's supply is staked.
+    Percentage { percent: Decimal },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address of the cw721 contract whose NFTs may be staked.
+    pub nft_address: String,
+    /// How long NFTs remain locked after being unstaked before they
+    /// may be claimed. If `None`, unstaked NFTs are returned
+    /// immediately.
+    pub unstaking_duration: Option<Duration>,
+    pub active_threshold: Option<ActiveThreshold>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Stakes an NFT. Sent by the NFT contract when an NFT is sent
+    /// to this contract with `SendNft`.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Unstakes the sender's NFTs with the given token IDs.
+    Unstake { token_ids: Vec<String> },
+    /// Returns all of the sender's unstaked NFTs whose unstaking
+    /// duration has passed.
+    ClaimNfts {},
+    /// Updates the unstaking duration. Only callable by the DAO.
+    UpdateConfig { duration: Option<Duration> },
+    /// Updates the active threshold. Only callable by the DAO.
+    UpdateActiveThreshold {
+        new_threshold: Option<ActiveThreshold>,
+    },
+}
+
+#[voting_query]
+#[active_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    Dao {},
+    ActiveThreshold {},
+    /// Lists the NFTs an address has unstaked but not yet claimed.
+    NftClaims {
+        address: String,
+    },
+    /// Lists the token IDs of the NFTs an address has staked.
+    StakedNfts {
+        address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ActiveThresholdResponse {
+    pub active_threshold: Option<ActiveThreshold>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NftClaimsResponse {
+    pub nft_claims: Vec<crate::state::NftClaim>,
+}
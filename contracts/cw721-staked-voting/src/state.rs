@@ -0,0 +1,53 @@
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
+use cw_utils::{Duration, Expiration};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::ActiveThreshold;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub nft_address: Addr,
+    pub unstaking_duration: Option<Duration>,
+}
+
+/// An unstaked NFT that may be claimed once `release_at` has passed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NftClaim {
+    pub token_id: String,
+    pub release_at: Expiration,
+}
+
+pub const ACTIVE_THRESHOLD: Item<ActiveThreshold> = Item::new("active_threshold");
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const DAO: Item<Addr> = Item::new("dao");
+
+/// The token IDs staked by each address.
+pub const STAKED_NFTS_PER_OWNER: SnapshotMap<(&Addr, &str), Empty> = SnapshotMap::new(
+    "staked_nfts_per_owner",
+    "staked_nfts_per_owner__checkpoints",
+    "staked_nfts_per_owner__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The number of NFTs staked by each address. This is that address'
+/// voting power.
+pub const NFT_BALANCES: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "nft_balances",
+    "nft_balances__checkpoints",
+    "nft_balances__changelog",
+    Strategy::EveryBlock,
+);
+
+pub const TOTAL_STAKED_NFTS: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_staked_nfts",
+    "total_staked_nfts__checkpoints",
+    "total_staked_nfts__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The maximum number of claims that may be outstanding.
+pub const MAX_CLAIMS: u64 = 70;
+
+pub const NFT_CLAIMS: Map<&Addr, Vec<NftClaim>> = Map::new("nft_claims");
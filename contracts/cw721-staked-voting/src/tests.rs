@@ -0,0 +1,375 @@
+use cosmwasm_std::{to_binary, Addr, Decimal, Empty, Uint128};
+use cw721::OwnerOfResponse;
+use cw_core_interface::voting::{
+    IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use cw_utils::{Duration, Expiration};
+
+use crate::{
+    msg::{ActiveThreshold, ExecuteMsg, InstantiateMsg, NftClaimsResponse, QueryMsg},
+    state::NftClaim,
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+
+fn cw721_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    );
+    Box::new(contract)
+}
+
+fn staked_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// Instantiates a cw721 contract with `count` NFTs minted to ADDR1
+/// and a voting module for it.
+fn setup(
+    count: u64,
+    unstaking_duration: Option<Duration>,
+    active_threshold: Option<ActiveThreshold>,
+) -> (App, Addr, Addr) {
+    let mut app = App::default();
+    let cw721_id = app.store_code(cw721_contract());
+    let voting_id = app.store_code(staked_voting_contract());
+
+    let nft_addr = app
+        .instantiate_contract(
+            cw721_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw721_base::msg::InstantiateMsg {
+                name: "Gators".to_string(),
+                symbol: "GATOR".to_string(),
+                minter: DAO_ADDR.to_string(),
+            },
+            &[],
+            "nft",
+            None,
+        )
+        .unwrap();
+    for i in 0..count {
+        app.execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            nft_addr.clone(),
+            &cw721_base::msg::ExecuteMsg::Mint(cw721_base::msg::MintMsg::<Option<Empty>> {
+                token_id: i.to_string(),
+                owner: ADDR1.to_string(),
+                token_uri: None,
+                extension: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    }
+
+    let voting_addr = app
+        .instantiate_contract(
+            voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                nft_address: nft_addr.to_string(),
+                unstaking_duration,
+                active_threshold,
+            },
+            &[],
+            "voting module",
+            None,
+        )
+        .unwrap();
+
+    (app, nft_addr, voting_addr)
+}
+
+fn stake(app: &mut App, nft_addr: &Addr, voting_addr: &Addr, sender: &str, token_id: &str) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        nft_addr.clone(),
+        &cw721_base::msg::ExecuteMsg::<Option<Empty>>::SendNft {
+            contract: voting_addr.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_binary("").unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn unstake(
+    app: &mut App,
+    voting_addr: &Addr,
+    sender: &str,
+    token_ids: &[&str],
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        voting_addr.clone(),
+        &ExecuteMsg::Unstake {
+            token_ids: token_ids.iter().map(|id| id.to_string()).collect(),
+        },
+        &[],
+    )
+}
+
+fn voting_power(app: &App, voting_addr: &Addr, address: &str, height: Option<u64>) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+fn total_power(app: &App, voting_addr: &Addr, height: Option<u64>) -> Uint128 {
+    let res: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(voting_addr, &QueryMsg::TotalPowerAtHeight { height })
+        .unwrap();
+    res.power
+}
+
+fn owner_of(app: &App, nft_addr: &Addr, token_id: &str) -> String {
+    let res: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            nft_addr,
+            &cw721::Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    res.owner
+}
+
+#[test]
+fn test_stake_and_unstake_with_claims() {
+    let (mut app, nft_addr, voting_addr) = setup(3, Some(Duration::Height(5)), None);
+
+    stake(&mut app, &nft_addr, &voting_addr, ADDR1, "0");
+    stake(&mut app, &nft_addr, &voting_addr, ADDR1, "1");
+    let staked_height = app.block_info().height;
+    app.update_block(|block| block.height += 1);
+
+    assert_eq!(
+        voting_power(&app, &voting_addr, ADDR1, None),
+        Uint128::new(2)
+    );
+    assert_eq!(total_power(&app, &voting_addr, None), Uint128::new(2));
+    // Power is snapshotted at the start of each block.
+    assert_eq!(
+        voting_power(&app, &voting_addr, ADDR1, Some(staked_height)),
+        Uint128::zero()
+    );
+
+    let staked: Vec<String> = app
+        .wrap()
+        .query_wasm_smart(
+            &voting_addr,
+            &QueryMsg::StakedNfts {
+                address: ADDR1.to_string(),
+                start_after: Some("0".to_string()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(staked, vec!["1".to_string()]);
+
+    // Only staked NFTs owned by the sender may be unstaked.
+    let err: ContractError = unstake(&mut app, &voting_addr, ADDR2, &["0"])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NotStaked {
+            token_id: "0".to_string()
+        }
+    );
+
+    unstake(&mut app, &voting_addr, ADDR1, &["0"]).unwrap();
+    let unstaked_height = app.block_info().height;
+    app.update_block(|block| block.height += 1);
+    assert_eq!(
+        voting_power(&app, &voting_addr, ADDR1, None),
+        Uint128::new(1)
+    );
+    assert_eq!(
+        voting_power(&app, &voting_addr, ADDR1, Some(unstaked_height)),
+        Uint128::new(2)
+    );
+
+    let claims: NftClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting_addr,
+            &QueryMsg::NftClaims {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        claims.nft_claims,
+        vec![NftClaim {
+            token_id: "0".to_string(),
+            release_at: Expiration::AtHeight(unstaked_height + 5),
+        }]
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::ClaimNfts {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    app.update_block(|block| block.height += 5);
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting_addr.clone(),
+        &ExecuteMsg::ClaimNfts {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &nft_addr, "0"), ADDR1.to_string());
+    assert_eq!(owner_of(&app, &nft_addr, "1"), voting_addr.to_string());
+}
+
+#[test]
+fn test_unstake_without_duration() {
+    let (mut app, nft_addr, voting_addr) = setup(2, None, None);
+
+    stake(&mut app, &nft_addr, &voting_addr, ADDR1, "0");
+    stake(&mut app, &nft_addr, &voting_addr, ADDR1, "1");
+    unstake(&mut app, &voting_addr, ADDR1, &["0", "1"]).unwrap();
+    app.update_block(|block| block.height += 1);
+
+    assert_eq!(owner_of(&app, &nft_addr, "0"), ADDR1.to_string());
+    assert_eq!(owner_of(&app, &nft_addr, "1"), ADDR1.to_string());
+    assert_eq!(total_power(&app, &voting_addr, None), Uint128::zero());
+
+    // NFTs may not be staked by calling the receive hook directly.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::ReceiveNft(cw721::Cw721ReceiveMsg {
+                sender: ADDR1.to_string(),
+                token_id: "0".to_string(),
+                msg: to_binary("").unwrap(),
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidNftContract {
+            received: ADDR1.to_string(),
+            expected: nft_addr.to_string(),
+        }
+    );
+
+    // Only the DAO may update the unstaking duration.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                duration: Some(Duration::Height(1)),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_active_threshold() {
+    let (mut app, nft_addr, voting_addr) = setup(
+        4,
+        None,
+        Some(ActiveThreshold::Percentage {
+            percent: Decimal::percent(50),
+        }),
+    );
+
+    let is_active = |app: &App| -> bool {
+        let res: IsActiveResponse = app
+            .wrap()
+            .query_wasm_smart(&voting_addr, &QueryMsg::IsActive {})
+            .unwrap();
+        res.active
+    };
+
+    assert!(!is_active(&app));
+    stake(&mut app, &nft_addr, &voting_addr, ADDR1, "0");
+    assert!(!is_active(&app));
+    stake(&mut app, &nft_addr, &voting_addr, ADDR1, "1");
+    assert!(is_active(&app));
+
+    // Absolute thresholds may not exceed the collection's supply.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            voting_addr.clone(),
+            &ExecuteMsg::UpdateActiveThreshold {
+                new_threshold: Some(ActiveThreshold::AbsoluteCount {
+                    count: Uint128::new(5),
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidAbsoluteCount {});
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateActiveThreshold {
+            new_threshold: Some(ActiveThreshold::AbsoluteCount {
+                count: Uint128::new(3),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    assert!(!is_active(&app));
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateActiveThreshold {
+            new_threshold: None,
+        },
+        &[],
+    )
+    .unwrap();
+    assert!(is_active(&app));
+}
@@ -0,0 +1,26 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
+use cosmwasm_std::Addr;
+use dao_factory::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{CodeIds, CreatedDao},
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+
+    // Auto TS code generation expects the query return type as QueryNameResponse
+    // Here we map query resonses to the correct name
+    export_schema_with_title(&schema_for!(CodeIds), &out_dir, "CodeIdsResponse");
+    export_schema_with_title(&schema_for!(Addr), &out_dir, "OwnerResponse");
+    export_schema_with_title(&schema_for!(Vec<CreatedDao>), &out_dir, "ListDaosResponse");
+}
@@ -0,0 +1,284 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply, Response, StdResult,
+    SubMsg, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_core::msg::{Admin, ModuleInstantiateInfo};
+use cw_storage_plus::Bound;
+use cw_utils::parse_reply_instantiate_data;
+
+use crate::error::ContractError;
+use crate::msg::{DaoTemplate, ExecuteMsg, InstantiateMsg, QueryMsg, VotingTemplate};
+use crate::state::{
+    CodeIds, CreatedDao, PendingDao, CODE_IDS, CREATOR_DAOS, DAOS, OWNER, PENDING_DAO,
+};
+
+const CONTRACT_NAME: &str = "crates.io:dao-factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const INSTANTIATE_DAO_REPLY_ID: u64 = 0;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    OWNER.save(deps.storage, &info.sender)?;
+    CODE_IDS.save(deps.storage, &msg.code_ids)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("owner", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateDao { template } => execute_create_dao(deps, env, info, template),
+        ExecuteMsg::UpdateCodeIds { code_ids } => execute_update_code_ids(deps, info, code_ids),
+    }
+}
+
+pub fn execute_create_dao(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    template: DaoTemplate,
+) -> Result<Response, ContractError> {
+    let code_ids = CODE_IDS.load(deps.storage)?;
+    let admin = template.admin.clone();
+    let msg = template.into_core_instantiate_msg(&code_ids)?;
+
+    // If the DAO is to be its own admin the factory is the initial
+    // admin so that it may transfer adminship to the DAO once the
+    // DAO's address is known.
+    let (admin, make_self_admin) = match admin {
+        Admin::Address { addr } => (Some(deps.api.addr_validate(&addr)?.to_string()), false),
+        Admin::CoreContract {} => (Some(env.contract.address.to_string()), true),
+        Admin::None {} => (None, false),
+    };
+    let instantiate = WasmMsg::Instantiate {
+        admin,
+        code_id: code_ids.cw_core,
+        msg: to_binary(&msg)?,
+        funds: vec![],
+        label: msg.name,
+    };
+    PENDING_DAO.save(
+        deps.storage,
+        &PendingDao {
+            creator: info.sender.clone(),
+            make_self_admin,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_dao")
+        .add_attribute("creator", info.sender)
+        .add_submessage(SubMsg::reply_on_success(
+            instantiate,
+            INSTANTIATE_DAO_REPLY_ID,
+        )))
+}
+
+impl DaoTemplate {
+    /// Validates the template and converts it into an instantiate
+    /// message for cw-core.
+    pub fn into_core_instantiate_msg(
+        self,
+        code_ids: &CodeIds,
+    ) -> Result<cw_core::msg::InstantiateMsg, ContractError> {
+        if self.name.trim().is_empty() {
+            return Err(ContractError::EmptyName {});
+        }
+        self.proposals.threshold.validate()?;
+
+        let voting_module_instantiate_info = match self.voting {
+            VotingTemplate::Membership { members } => {
+                if members.iter().all(|member| member.weight == 0) {
+                    return Err(ContractError::NoMembers {});
+                }
+                ModuleInstantiateInfo {
+                    code_id: code_ids.cw4_voting,
+                    msg: to_binary(&cw4_voting::msg::InstantiateMsg {
+                        cw4_group_code_id: code_ids.cw4_group,
+                        initial_members: members,
+                        active_threshold: None,
+                    })?,
+                    admin: Admin::CoreContract {},
+                    label: format!("{} voting module", self.name),
+                }
+            }
+            VotingTemplate::Token {
+                name,
+                symbol,
+                decimals,
+                initial_balances,
+                initial_dao_balance,
+                unstaking_duration,
+                active_threshold,
+            } => {
+                if initial_balances.is_empty() {
+                    return Err(ContractError::NoInitialBalances {});
+                }
+                ModuleInstantiateInfo {
+                    code_id: code_ids.cw20_staked_balance_voting,
+                    msg: to_binary(&cw20_staked_balance_voting::msg::InstantiateMsg {
+                        token_info: cw20_staked_balance_voting::msg::TokenInfo::New {
+                            code_id: code_ids.cw20_base,
+                            label: format!("{} token", self.name),
+                            name,
+                            symbol,
+                            decimals,
+                            initial_balances,
+                            marketing: None,
+                            staking_code_id: code_ids.stake_cw20,
+                            unstaking_duration,
+                            initial_dao_balance,
+                        },
+                        active_threshold,
+                    })?,
+                    admin: Admin::CoreContract {},
+                    label: format!("{} voting module", self.name),
+                }
+            }
+        };
+
+        let proposal_module_instantiate_info = ModuleInstantiateInfo {
+            code_id: code_ids.cw_proposal_single,
+            msg: to_binary(&cw_proposal_single::msg::InstantiateMsg {
+                threshold: self.proposals.threshold,
+                max_voting_period: self.proposals.max_voting_period,
+                only_members_execute: self.proposals.only_members_execute,
+                deposit_info: self.proposals.deposit_info,
+            })?,
+            admin: Admin::CoreContract {},
+            label: format!("{} proposal module", self.name),
+        };
+
+        Ok(cw_core::msg::InstantiateMsg {
+            name: self.name,
+            description: self.description,
+            image_url: self.image_url,
+            automatically_add_cw20s: self.automatically_add_cw20s,
+            automatically_add_cw721s: self.automatically_add_cw721s,
+            voting_module_instantiate_info,
+            proposal_modules_instantiate_info: vec![proposal_module_instantiate_info],
+            initial_items: None,
+            pause_guardian: None,
+            parent_dao: None,
+            admin: None,
+        })
+    }
+}
+
+pub fn execute_update_code_ids(
+    deps: DepsMut,
+    info: MessageInfo,
+    code_ids: CodeIds,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    CODE_IDS.save(deps.storage, &code_ids)?;
+    Ok(Response::new().add_attribute("action", "update_code_ids"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::CodeIds {} => to_binary(&CODE_IDS.load(deps.storage)?),
+        QueryMsg::Owner {} => to_binary(&OWNER.load(deps.storage)?),
+        QueryMsg::ListDaos {
+            creator,
+            start_after,
+            limit,
+        } => query_list_daos(deps, creator, start_after, limit),
+    }
+}
+
+pub fn query_list_daos(
+    deps: Deps,
+    creator: Option<String>,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|a| deps.api.addr_validate(&a))
+        .transpose()?;
+    let daos: Box<dyn Iterator<Item = StdResult<CreatedDao>>> = match creator {
+        Some(creator) => {
+            let creator = deps.api.addr_validate(&creator)?;
+            Box::new(
+                CREATOR_DAOS
+                    .prefix(creator.clone())
+                    .keys(
+                        deps.storage,
+                        start_after.map(Bound::exclusive),
+                        None,
+                        Order::Ascending,
+                    )
+                    .map(move |dao| {
+                        dao.map(|dao| CreatedDao {
+                            dao,
+                            creator: creator.clone(),
+                        })
+                    }),
+            )
+        }
+        None => Box::new(
+            DAOS.range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .map(|item| item.map(|(dao, creator)| CreatedDao { dao, creator })),
+        ),
+    };
+    let daos: Vec<CreatedDao> = match limit {
+        Some(limit) => daos.take(limit as usize).collect::<StdResult<_>>()?,
+        None => daos.collect::<StdResult<_>>()?,
+    };
+    to_binary(&daos)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        INSTANTIATE_DAO_REPLY_ID => {
+            let res = parse_reply_instantiate_data(msg)?;
+            let dao = deps.api.addr_validate(&res.contract_address)?;
+            let PendingDao {
+                creator,
+                make_self_admin,
+            } = PENDING_DAO.load(deps.storage)?;
+            PENDING_DAO.remove(deps.storage);
+
+            DAOS.save(deps.storage, dao.clone(), &creator)?;
+            CREATOR_DAOS.save(deps.storage, (creator, dao.clone()), &Empty {})?;
+
+            let mut response = Response::new()
+                .add_attribute("dao", dao.clone())
+                .set_data(to_binary(&dao)?);
+            if make_self_admin {
+                response = response.add_message(WasmMsg::UpdateAdmin {
+                    contract_addr: dao.to_string(),
+                    admin: dao.to_string(),
+                });
+            }
+            Ok(response)
+        }
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
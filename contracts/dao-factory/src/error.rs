@@ -0,0 +1,30 @@
+use cosmwasm_std::StdError;
+use cw_utils::ParseReplyError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    ThresholdError(#[from] voting::ThresholdError),
+
+    #[error("{0}")]
+    ParseReplyError(#[from] ParseReplyError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("A DAO's name can not be empty")]
+    EmptyName {},
+
+    #[error("A membership DAO must have at least one member with non-zero weight")]
+    NoMembers {},
+
+    #[error("A token DAO must have at least one initial balance")]
+    NoInitialBalances {},
+
+    #[error("Got a submessage reply with unknown id: {id}")]
+    UnknownReplyId { id: u64 },
+}
@@ -0,0 +1,93 @@
+use cosmwasm_std::Uint128;
+use cw20::Cw20Coin;
+use cw20_staked_balance_voting::msg::ActiveThreshold;
+use cw_core::msg::Admin;
+use cw_proposal_single::msg::DepositInfo;
+use cw_utils::Duration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use voting::Threshold;
+
+use crate::state::CodeIds;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The code IDs of the contracts used to create DAOs.
+    pub code_ids: CodeIds,
+}
+
+/// How voting power is distributed in a new DAO.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VotingTemplate {
+    /// Voting power is the weight of each member in a new cw4-group.
+    Membership { members: Vec<cw4::Member> },
+    /// Voting power is the amount of a new cw20 token each address
+    /// has staked.
+    Token {
+        name: String,
+        symbol: String,
+        decimals: u8,
+        initial_balances: Vec<Cw20Coin>,
+        /// Tokens minted to the DAO's treasury.
+        initial_dao_balance: Option<Uint128>,
+        unstaking_duration: Option<Duration>,
+        active_threshold: Option<ActiveThreshold>,
+    },
+}
+
+/// How proposals are created and pass in a new DAO.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalTemplate {
+    /// The threshold a proposal must reach to pass.
+    pub threshold: Threshold,
+    /// The maximum amount of time a proposal may be voted on.
+    pub max_voting_period: Duration,
+    /// If true only members may execute passed proposals.
+    pub only_members_execute: bool,
+    /// The deposit required to create a proposal, if any.
+    pub deposit_info: Option<DepositInfo>,
+}
+
+/// A description of a DAO to be created by the factory.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DaoTemplate {
+    /// The admin of the DAO's core contract. `CoreContract` makes
+    /// the DAO its own admin.
+    pub admin: Admin,
+    pub name: String,
+    pub description: String,
+    pub image_url: Option<String>,
+    pub automatically_add_cw20s: bool,
+    pub automatically_add_cw721s: bool,
+    pub voting: VotingTemplate,
+    pub proposals: ProposalTemplate,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Creates a new DAO from `template`. The sender is recorded as
+    /// the DAO's creator.
+    CreateDao { template: DaoTemplate },
+    /// Callable by the owner. Updates the code IDs used to create
+    /// new DAOs.
+    UpdateCodeIds { code_ids: CodeIds },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the code IDs used to create DAOs. Returns CodeIds.
+    CodeIds {},
+    /// Returns the factory's owner. Returns Addr.
+    Owner {},
+    /// Lists the DAOs created by this factory, optionally only those
+    /// created by `creator`. Returns Vec<CreatedDao>.
+    ListDaos {
+        creator: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+}
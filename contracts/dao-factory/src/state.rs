@@ -0,0 +1,43 @@
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The code IDs of the contracts used to create DAOs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CodeIds {
+    pub cw_core: u64,
+    pub cw_proposal_single: u64,
+    pub cw4_voting: u64,
+    pub cw4_group: u64,
+    pub cw20_staked_balance_voting: u64,
+    pub cw20_base: u64,
+    pub stake_cw20: u64,
+}
+
+/// A DAO created by the factory.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreatedDao {
+    pub dao: Addr,
+    pub creator: Addr,
+}
+
+pub const OWNER: Item<Addr> = Item::new("owner");
+pub const CODE_IDS: Item<CodeIds> = Item::new("code_ids");
+
+/// Information about the DAO currently being instantiated. Read in
+/// the reply handler.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingDao {
+    pub creator: Addr,
+    /// If true the factory is the DAO's admin and should transfer
+    /// adminship to the DAO.
+    pub make_self_admin: bool,
+}
+
+pub const PENDING_DAO: Item<PendingDao> = Item::new("pending_dao");
+
+/// Map from DAO address to the DAO's creator.
+pub const DAOS: Map<Addr, Addr> = Map::new("daos");
+/// Set of (creator, DAO) pairs for listing DAOs by creator.
+pub const CREATOR_DAOS: Map<(Addr, Addr), Empty> = Map::new("creator_daos");
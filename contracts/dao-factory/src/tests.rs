@@ -0,0 +1,430 @@
+use cosmwasm_std::{Addr, ContractInfoResponse, Decimal, Empty, QueryRequest, Uint128, WasmQuery};
+use cw20::Cw20Coin;
+use cw_core::msg::Admin;
+use cw_core_interface::voting::VotingPowerAtHeightResponse;
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_utils::Duration;
+use voting::{PercentageThreshold, Threshold};
+
+use crate::{
+    msg::{DaoTemplate, ExecuteMsg, InstantiateMsg, ProposalTemplate, QueryMsg, VotingTemplate},
+    state::{CodeIds, CreatedDao},
+    ContractError,
+};
+
+const OWNER_ADDR: &str = "owner";
+const CREATOR_ADDR: &str = "creator";
+
+fn factory_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply);
+    Box::new(contract)
+}
+
+fn cw_core_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_core::contract::execute,
+        cw_core::contract::instantiate,
+        cw_core::contract::query,
+    )
+    .with_reply(cw_core::contract::reply);
+    Box::new(contract)
+}
+
+fn proposal_single_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_proposal_single::contract::execute,
+        cw_proposal_single::contract::instantiate,
+        cw_proposal_single::contract::query,
+    )
+    .with_reply(cw_proposal_single::contract::reply);
+    Box::new(contract)
+}
+
+fn cw4_group_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw4_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_voting::contract::execute,
+        cw4_voting::contract::instantiate,
+        cw4_voting::contract::query,
+    )
+    .with_reply(cw4_voting::contract::reply);
+    Box::new(contract)
+}
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn stake_cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        stake_cw20::contract::execute,
+        stake_cw20::contract::instantiate,
+        stake_cw20::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn staked_balance_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_staked_balance_voting::contract::execute,
+        cw20_staked_balance_voting::contract::instantiate,
+        cw20_staked_balance_voting::contract::query,
+    )
+    .with_reply(cw20_staked_balance_voting::contract::reply);
+    Box::new(contract)
+}
+
+fn setup_factory(app: &mut App) -> Addr {
+    let code_ids = CodeIds {
+        cw_core: app.store_code(cw_core_contract()),
+        cw_proposal_single: app.store_code(proposal_single_contract()),
+        cw4_voting: app.store_code(cw4_voting_contract()),
+        cw4_group: app.store_code(cw4_group_contract()),
+        cw20_staked_balance_voting: app.store_code(staked_balance_voting_contract()),
+        cw20_base: app.store_code(cw20_contract()),
+        stake_cw20: app.store_code(stake_cw20_contract()),
+    };
+    let factory_id = app.store_code(factory_contract());
+    app.instantiate_contract(
+        factory_id,
+        Addr::unchecked(OWNER_ADDR),
+        &InstantiateMsg { code_ids },
+        &[],
+        "dao factory",
+        None,
+    )
+    .unwrap()
+}
+
+fn template(voting: VotingTemplate) -> DaoTemplate {
+    DaoTemplate {
+        admin: Admin::None {},
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs.".to_string(),
+        image_url: None,
+        automatically_add_cw20s: true,
+        automatically_add_cw721s: true,
+        voting,
+        proposals: ProposalTemplate {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Time(60 * 60 * 24 * 7),
+            only_members_execute: true,
+            deposit_info: None,
+        },
+    }
+}
+
+fn create_dao(app: &mut App, factory: &Addr, sender: &str, template: DaoTemplate) -> Addr {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        factory.clone(),
+        &ExecuteMsg::CreateDao { template },
+        &[],
+    )
+    .unwrap();
+
+    let daos: Vec<CreatedDao> = app
+        .wrap()
+        .query_wasm_smart(
+            factory,
+            &QueryMsg::ListDaos {
+                creator: Some(sender.to_string()),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    daos.into_iter().last().unwrap().dao
+}
+
+fn voting_power(app: &App, dao: &Addr, address: &str) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            dao,
+            &cw_core::msg::QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+fn contract_info(app: &App, contract: &Addr) -> ContractInfoResponse {
+    app.wrap()
+        .query(&QueryRequest::Wasm(WasmQuery::ContractInfo {
+            contract_addr: contract.to_string(),
+        }))
+        .unwrap()
+}
+
+#[test]
+fn test_create_membership_dao() {
+    let mut app = App::default();
+    let factory = setup_factory(&mut app);
+
+    let mut template = template(VotingTemplate::Membership {
+        members: vec![cw4::Member {
+            addr: CREATOR_ADDR.to_string(),
+            weight: 1,
+        }],
+    });
+    template.admin = Admin::Address {
+        addr: CREATOR_ADDR.to_string(),
+    };
+    let dao = create_dao(&mut app, &factory, CREATOR_ADDR, template);
+
+    let state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(dao.clone(), &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(state.config.name, "DAO DAO");
+    assert_eq!(state.governance_modules.len(), 1);
+
+    // Voting power is snapshotted so becomes visible in the next block.
+    app.update_block(|block| block.height += 1);
+    assert_eq!(voting_power(&app, &dao, CREATOR_ADDR), Uint128::new(1));
+
+    let info = contract_info(&app, &dao);
+    assert_eq!(info.admin, Some(CREATOR_ADDR.to_string()));
+}
+
+#[test]
+fn test_create_token_dao() {
+    let mut app = App::default();
+    let factory = setup_factory(&mut app);
+
+    let dao = create_dao(
+        &mut app,
+        &factory,
+        CREATOR_ADDR,
+        template(VotingTemplate::Token {
+            name: "DAO".to_string(),
+            symbol: "DAO".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(100),
+            }],
+            initial_dao_balance: Some(Uint128::new(1000)),
+            unstaking_duration: None,
+            active_threshold: None,
+        }),
+    );
+
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(dao.clone(), &cw_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let token: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &cw20_staked_balance_voting::msg::QueryMsg::TokenContract {},
+        )
+        .unwrap();
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: dao.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(1000));
+
+    // Nothing has been staked yet.
+    assert_eq!(voting_power(&app, &dao, CREATOR_ADDR), Uint128::zero());
+
+    let info = contract_info(&app, &dao);
+    assert_eq!(info.admin, None);
+}
+
+#[test]
+fn test_invalid_templates() {
+    let mut app = App::default();
+    let factory = setup_factory(&mut app);
+
+    let mut bad_threshold = template(VotingTemplate::Membership {
+        members: vec![cw4::Member {
+            addr: CREATOR_ADDR.to_string(),
+            weight: 1,
+        }],
+    });
+    bad_threshold.proposals.threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Percent(Decimal::zero()),
+    };
+    let mut empty_name = bad_threshold.clone();
+    empty_name.name = " ".to_string();
+    empty_name.proposals.threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+
+    let cases = vec![
+        bad_threshold,
+        empty_name,
+        template(VotingTemplate::Membership {
+            members: vec![cw4::Member {
+                addr: CREATOR_ADDR.to_string(),
+                weight: 0,
+            }],
+        }),
+        template(VotingTemplate::Token {
+            name: "DAO".to_string(),
+            symbol: "DAO".to_string(),
+            decimals: 6,
+            initial_balances: vec![],
+            initial_dao_balance: None,
+            unstaking_duration: None,
+            active_threshold: None,
+        }),
+    ];
+    let errors: Vec<ContractError> = cases
+        .into_iter()
+        .map(|template| {
+            app.execute_contract(
+                Addr::unchecked(CREATOR_ADDR),
+                factory.clone(),
+                &ExecuteMsg::CreateDao { template },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap()
+        })
+        .collect();
+    assert!(matches!(errors[0], ContractError::ThresholdError(_)));
+    assert!(matches!(errors[1], ContractError::EmptyName {}));
+    assert!(matches!(errors[2], ContractError::NoMembers {}));
+    assert!(matches!(errors[3], ContractError::NoInitialBalances {}));
+}
+
+#[test]
+fn test_list_daos() {
+    let mut app = App::default();
+    let factory = setup_factory(&mut app);
+
+    let membership = || {
+        template(VotingTemplate::Membership {
+            members: vec![cw4::Member {
+                addr: CREATOR_ADDR.to_string(),
+                weight: 1,
+            }],
+        })
+    };
+    let first = create_dao(&mut app, &factory, CREATOR_ADDR, membership());
+    let second = create_dao(&mut app, &factory, CREATOR_ADDR, membership());
+    let other = create_dao(&mut app, &factory, "other", membership());
+
+    let all: Vec<CreatedDao> = app
+        .wrap()
+        .query_wasm_smart(
+            factory.clone(),
+            &QueryMsg::ListDaos {
+                creator: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(all.len(), 3);
+    assert!(all.contains(&CreatedDao {
+        dao: other,
+        creator: Addr::unchecked("other"),
+    }));
+
+    let mut created: Vec<Addr> = vec![first, second];
+    created.sort();
+    let by_creator: Vec<CreatedDao> = app
+        .wrap()
+        .query_wasm_smart(
+            factory.clone(),
+            &QueryMsg::ListDaos {
+                creator: Some(CREATOR_ADDR.to_string()),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        by_creator.into_iter().map(|d| d.dao).collect::<Vec<_>>(),
+        created
+    );
+
+    let page: Vec<CreatedDao> = app
+        .wrap()
+        .query_wasm_smart(
+            factory,
+            &QueryMsg::ListDaos {
+                creator: Some(CREATOR_ADDR.to_string()),
+                start_after: Some(created[0].to_string()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].dao, created[1]);
+}
+
+#[test]
+fn test_update_code_ids() {
+    let mut app = App::default();
+    let factory = setup_factory(&mut app);
+
+    let mut code_ids: CodeIds = app
+        .wrap()
+        .query_wasm_smart(factory.clone(), &QueryMsg::CodeIds {})
+        .unwrap();
+    code_ids.cw_core = 42;
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            factory.clone(),
+            &ExecuteMsg::UpdateCodeIds {
+                code_ids: code_ids.clone(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        Addr::unchecked(OWNER_ADDR),
+        factory.clone(),
+        &ExecuteMsg::UpdateCodeIds {
+            code_ids: code_ids.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    let updated: CodeIds = app
+        .wrap()
+        .query_wasm_smart(factory, &QueryMsg::CodeIds {})
+        .unwrap();
+    assert_eq!(updated, code_ids);
+}
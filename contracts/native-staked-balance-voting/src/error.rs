@@ -0,0 +1,24 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Amount being unstaked must be non-zero and no more than the staked balance")]
+    InvalidUnstakeAmount {},
+
+    #[error("Too many outstanding claims. Claim some tokens before unstaking more.")]
+    TooManyClaims {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+}
@@ -0,0 +1,57 @@
+use cosmwasm_std::Uint128;
+use cw_core_macros::{active_query, voting_query};
+use cw_utils::Duration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ActiveThreshold {
+    AbsoluteCount { count: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The native denom that may be staked for voting power.
+    pub denom: String,
+    /// How long tokens remain locked after being unstaked before
+    /// they may be claimed. If `None`, unstaked tokens are returned
+    /// immediately.
+    pub unstaking_duration: Option<Duration>,
+    pub active_threshold: Option<ActiveThreshold>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Stakes the native tokens sent with this message.
+    Stake {},
+    /// Unstakes `amount` of the sender's staked tokens.
+    Unstake { amount: Uint128 },
+    /// Returns all of the sender's unstaked tokens whose unstaking
+    /// duration has passed.
+    Claim {},
+    /// Updates the unstaking duration. Only callable by the DAO.
+    UpdateConfig { duration: Option<Duration> },
+    /// Updates the active threshold. Only callable by the DAO.
+    UpdateActiveThreshold {
+        new_threshold: Option<ActiveThreshold>,
+    },
+}
+
+#[voting_query]
+#[active_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    Dao {},
+    ActiveThreshold {},
+    Claims { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ActiveThresholdResponse {
+    pub active_threshold: Option<ActiveThreshold>,
+}
@@ -0,0 +1,227 @@
+use cosmwasm_std::{coins, Addr, Empty, Uint128};
+use cw_controllers::ClaimsResponse;
+use cw_core_interface::voting::{
+    IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use cw_utils::{Duration, PaymentError};
+
+use crate::{
+    msg::{ActiveThreshold, ExecuteMsg, InstantiateMsg, QueryMsg},
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const DENOM: &str = "ujuno";
+
+fn staked_balance_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn setup(
+    unstaking_duration: Option<Duration>,
+    active_threshold: Option<ActiveThreshold>,
+) -> (App, Addr) {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(ADDR1), coins(1000, DENOM))
+            .unwrap();
+    });
+    let code_id = app.store_code(staked_balance_voting_contract());
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                denom: DENOM.to_string(),
+                unstaking_duration,
+                active_threshold,
+            },
+            &[],
+            "voting module",
+            None,
+        )
+        .unwrap();
+    (app, addr)
+}
+
+fn stake(app: &mut App, voting_addr: &Addr, amount: u128) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting_addr.clone(),
+        &ExecuteMsg::Stake {},
+        &coins(amount, DENOM),
+    )
+}
+
+fn unstake(app: &mut App, voting_addr: &Addr, amount: u128) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting_addr.clone(),
+        &ExecuteMsg::Unstake {
+            amount: Uint128::new(amount),
+        },
+        &[],
+    )
+}
+
+fn voting_power(app: &App, voting_addr: &Addr, height: Option<u64>) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr,
+            &QueryMsg::VotingPowerAtHeight {
+                address: ADDR1.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+fn balance(app: &App) -> Uint128 {
+    app.wrap().query_balance(ADDR1, DENOM).unwrap().amount
+}
+
+#[test]
+fn test_stake_unstake_claim() {
+    let (mut app, voting_addr) = setup(Some(Duration::Height(5)), None);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::Stake {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Payment(PaymentError::NoFunds {}));
+
+    stake(&mut app, &voting_addr, 100).unwrap();
+    let staked_height = app.block_info().height;
+    app.update_block(|block| block.height += 1);
+
+    assert_eq!(voting_power(&app, &voting_addr, None), Uint128::new(100));
+    assert_eq!(
+        voting_power(&app, &voting_addr, Some(staked_height)),
+        Uint128::zero()
+    );
+    let total: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(&voting_addr, &QueryMsg::TotalPowerAtHeight { height: None })
+        .unwrap();
+    assert_eq!(total.power, Uint128::new(100));
+
+    let err: ContractError = unstake(&mut app, &voting_addr, 101)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidUnstakeAmount {});
+
+    unstake(&mut app, &voting_addr, 40).unwrap();
+    app.update_block(|block| block.height += 1);
+    assert_eq!(voting_power(&app, &voting_addr, None), Uint128::new(60));
+    assert_eq!(balance(&app), Uint128::new(900));
+
+    let claims: ClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting_addr,
+            &QueryMsg::Claims {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(claims.claims.len(), 1);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::Claim {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    app.update_block(|block| block.height += 5);
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting_addr.clone(),
+        &ExecuteMsg::Claim {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(balance(&app), Uint128::new(940));
+}
+
+#[test]
+fn test_unstake_without_duration() {
+    let (mut app, voting_addr) = setup(None, None);
+
+    stake(&mut app, &voting_addr, 100).unwrap();
+    unstake(&mut app, &voting_addr, 100).unwrap();
+    assert_eq!(balance(&app), Uint128::new(1000));
+
+    // Only the DAO may update the unstaking duration.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                duration: Some(Duration::Height(1)),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_active_threshold() {
+    let (mut app, voting_addr) = setup(
+        None,
+        Some(ActiveThreshold::AbsoluteCount {
+            count: Uint128::new(100),
+        }),
+    );
+
+    let is_active = |app: &App| -> bool {
+        let res: IsActiveResponse = app
+            .wrap()
+            .query_wasm_smart(&voting_addr, &QueryMsg::IsActive {})
+            .unwrap();
+        res.active
+    };
+
+    stake(&mut app, &voting_addr, 99).unwrap();
+    assert!(!is_active(&app));
+    stake(&mut app, &voting_addr, 1).unwrap();
+    assert!(is_active(&app));
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateActiveThreshold {
+            new_threshold: Some(ActiveThreshold::AbsoluteCount {
+                count: Uint128::new(101),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    assert!(!is_active(&app));
+}
@@ -42,7 +42,7 @@ pub fn instantiate(
 
     let reward_token = match msg.reward_token {
         Denom::Native(denom) => Denom::Native(denom),
-        Cw20(addr) => Cw20(deps.api.addr_validate(&addr.to_string())?),
+        Cw20(addr) => Cw20(deps.api.addr_validate(addr.as_str())?),
     };
 
     // Verify contract provided is a staking contract
@@ -654,6 +654,7 @@ mod tests {
             .unwrap();
         let msg = stake_cw20::msg::ExecuteMsg::AddHook {
             addr: reward_addr.to_string(),
+            gas_limit: None,
         };
         let _result = app
             .execute_contract(Addr::unchecked(OWNER), staking_contract, &msg, &[])
@@ -1857,7 +1858,7 @@ mod tests {
                 owner.clone(),
                 reward_addr.clone(),
                 &fund_msg,
-                &*invalid_funding,
+                &invalid_funding,
             )
             .unwrap_err()
             .downcast()
@@ -1954,7 +1955,7 @@ mod tests {
 
         let err: ContractError = app
             .borrow_mut()
-            .execute_contract(admin, reward_addr, &fund_msg, &*invalid_funding)
+            .execute_contract(admin, reward_addr, &fund_msg, &invalid_funding)
             .unwrap_err()
             .downcast()
             .unwrap();
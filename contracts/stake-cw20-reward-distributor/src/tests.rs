@@ -221,7 +221,7 @@ fn test_distribute() {
     app.execute_contract(Addr::unchecked(OWNER), cw20_addr.clone(), &msg, &[])
         .unwrap();
 
-    app.update_block(|mut block| block.height += 10);
+    app.update_block(|block| block.height += 10);
     app.execute_contract(
         Addr::unchecked(OWNER),
         distributor_addr.clone(),
@@ -237,7 +237,7 @@ fn test_distribute() {
     assert_eq!(distributor_info.balance, Uint128::new(990));
     assert_eq!(distributor_info.last_payment_block, app.block_info().height);
 
-    app.update_block(|mut block| block.height += 500);
+    app.update_block(|block| block.height += 500);
     app.execute_contract(
         Addr::unchecked(OWNER),
         distributor_addr.clone(),
@@ -253,7 +253,7 @@ fn test_distribute() {
     assert_eq!(distributor_info.balance, Uint128::new(490));
     assert_eq!(distributor_info.last_payment_block, app.block_info().height);
 
-    app.update_block(|mut block| block.height += 1000);
+    app.update_block(|block| block.height += 1000);
     app.execute_contract(
         Addr::unchecked(OWNER),
         distributor_addr.clone(),
@@ -270,7 +270,7 @@ fn test_distribute() {
     assert_eq!(distributor_info.last_payment_block, app.block_info().height);
 
     // Pays out nothing
-    app.update_block(|mut block| block.height += 1100);
+    app.update_block(|block| block.height += 1100);
     let _err = app
         .execute_contract(
             Addr::unchecked(OWNER),
@@ -416,7 +416,7 @@ fn test_withdraw() {
     app.execute_contract(Addr::unchecked(OWNER), cw20_addr.clone(), &msg, &[])
         .unwrap();
 
-    app.update_block(|mut block| block.height += 10);
+    app.update_block(|block| block.height += 10);
     app.execute_contract(
         Addr::unchecked(OWNER),
         distributor_addr.clone(),
@@ -481,7 +481,7 @@ fn test_dao_deploy() {
     };
     let distributor_addr = instantiate_distributor(&mut app, msg);
 
-    app.update_block(|mut block| block.height += 1000);
+    app.update_block(|block| block.height += 1000);
 
     let msg = ExecuteMsg::UpdateConfig {
         owner: OWNER.to_string(),
@@ -499,7 +499,7 @@ fn test_dao_deploy() {
     app.execute_contract(Addr::unchecked(OWNER), cw20_addr.clone(), &msg, &[])
         .unwrap();
 
-    app.update_block(|mut block| block.height += 10);
+    app.update_block(|block| block.height += 10);
     app.execute_contract(
         Addr::unchecked(OWNER),
         distributor_addr.clone(),
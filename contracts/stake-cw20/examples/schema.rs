@@ -8,9 +8,9 @@ use cw20::{
     TokenInfoResponse,
 };
 use stake_cw20::msg::{
-    ClaimsResponse, ExecuteMsg, GetConfigResponse, GetHooksResponse, InstantiateMsg, QueryMsg,
-    StakedBalanceAtHeightResponse, StakedValueResponse, TotalStakedAtHeightResponse,
-    TotalValueResponse,
+    ClaimsResponse, ExecuteMsg, GetConfigResponse, GetHookFailurePolicyResponse, GetHooksResponse,
+    HookGasLimitsResponse, InstantiateMsg, QueryMsg, StakedBalanceAtHeightResponse,
+    StakedValueResponse, TotalStakedAtHeightResponse, TotalValueResponse,
 };
 
 fn main() {
@@ -28,6 +28,8 @@ fn main() {
     export_schema(&schema_for!(TotalValueResponse), &out_dir);
     export_schema(&schema_for!(GetConfigResponse), &out_dir);
     export_schema(&schema_for!(GetHooksResponse), &out_dir);
+    export_schema(&schema_for!(GetHookFailurePolicyResponse), &out_dir);
+    export_schema(&schema_for!(HookGasLimitsResponse), &out_dir);
     export_schema(&schema_for!(ClaimsResponse), &out_dir);
     export_schema(&schema_for!(AllowanceResponse), &out_dir);
     export_schema(&schema_for!(BalanceResponse), &out_dir);
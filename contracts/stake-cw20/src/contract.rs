@@ -2,20 +2,21 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
-    StdError, StdResult, Uint128,
+    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, Event, MessageInfo, Reply,
+    Response, StdError, StdResult, Uint128,
 };
 
 use cw20::Cw20ReceiveMsg;
 
 use crate::hooks::{stake_hook_msgs, unstake_hook_msgs};
 use crate::msg::{
-    ExecuteMsg, GetConfigResponse, GetHooksResponse, InstantiateMsg, QueryMsg, ReceiveMsg,
-    StakedBalanceAtHeightResponse, StakedValueResponse, TotalStakedAtHeightResponse,
-    TotalValueResponse,
+    ExecuteMsg, GetConfigResponse, GetHookFailurePolicyResponse, GetHooksResponse, InstantiateMsg,
+    QueryMsg, ReceiveMsg, StakedBalanceAtHeightResponse, StakedValueResponse,
+    TotalStakedAtHeightResponse, TotalValueResponse,
 };
 use crate::state::{
-    Config, BALANCE, CLAIMS, CONFIG, HOOKS, MAX_CLAIMS, STAKED_BALANCES, STAKED_TOTAL,
+    Config, HookFailurePolicy, BALANCE, CLAIMS, CONFIG, HOOKS, HOOK_FAILURE_POLICY,
+    HOOK_REPLY_ADDRS, MAX_CLAIMS, STAKED_BALANCES, STAKED_TOTAL,
 };
 use crate::ContractError;
 use cw2::set_contract_version;
@@ -55,7 +56,7 @@ pub fn instantiate(
     let config = Config {
         owner,
         manager,
-        token_address: deps.api.addr_validate(&*msg.token_address)?,
+        token_address: deps.api.addr_validate(&msg.token_address)?,
         unstaking_duration: msg.unstaking_duration,
     };
     CONFIG.save(deps.storage, &config)?;
@@ -80,8 +81,13 @@ pub fn execute(
             manager,
             duration,
         } => execute_update_config(info, deps, owner, manager, duration),
-        ExecuteMsg::AddHook { addr } => execute_add_hook(deps, env, info, addr),
+        ExecuteMsg::AddHook { addr, gas_limit } => {
+            execute_add_hook(deps, env, info, addr, gas_limit)
+        }
         ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, env, info, addr),
+        ExecuteMsg::UpdateHookFailurePolicy { policy } => {
+            execute_update_hook_failure_policy(deps, info, policy)
+        }
     }
 }
 
@@ -93,10 +99,10 @@ pub fn execute_update_config(
     duration: Option<Duration>,
 ) -> Result<Response, ContractError> {
     let new_owner = new_owner
-        .map(|new_owner| deps.api.addr_validate(&*new_owner))
+        .map(|new_owner| deps.api.addr_validate(&new_owner))
         .transpose()?;
     let new_manager = new_manager
-        .map(|new_manager| deps.api.addr_validate(&*new_manager))
+        .map(|new_manager| deps.api.addr_validate(&new_manager))
         .transpose()?;
     let mut config: Config = CONFIG.load(deps.storage)?;
     if Some(info.sender.clone()) != config.owner && Some(info.sender.clone()) != config.manager {
@@ -314,16 +320,23 @@ pub fn execute_add_hook(
     _env: Env,
     info: MessageInfo,
     addr: String,
+    gas_limit: Option<u64>,
 ) -> Result<Response, ContractError> {
     let addr = deps.api.addr_validate(&addr)?;
     let config: Config = CONFIG.load(deps.storage)?;
     if config.owner != Some(info.sender.clone()) && config.manager != Some(info.sender) {
         return Err(ContractError::Unauthorized {});
     };
-    HOOKS.add_hook(deps.storage, addr.clone())?;
+    HOOKS.add_hook(deps.storage, addr.clone(), gas_limit)?;
     Ok(Response::new()
         .add_attribute("action", "add_hook")
-        .add_attribute("hook", addr))
+        .add_attribute("hook", addr)
+        .add_attribute(
+            "gas_limit",
+            gas_limit
+                .map(|g| g.to_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ))
 }
 
 pub fn execute_remove_hook(
@@ -343,6 +356,21 @@ pub fn execute_remove_hook(
         .add_attribute("hook", addr))
 }
 
+pub fn execute_update_hook_failure_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    policy: HookFailurePolicy,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if config.owner != Some(info.sender) {
+        return Err(ContractError::Unauthorized {});
+    };
+    HOOK_FAILURE_POLICY.save(deps.storage, &policy)?;
+    Ok(Response::new()
+        .add_attribute("action", "update_hook_failure_policy")
+        .add_attribute("policy", format!("{:?}", policy)))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -357,6 +385,8 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::TotalValue {} => to_binary(&query_total_value(deps, env)?),
         QueryMsg::Claims { address } => to_binary(&query_claims(deps, address)?),
         QueryMsg::GetHooks {} => to_binary(&query_hooks(deps)?),
+        QueryMsg::GetHookGasLimits {} => to_binary(&HOOKS.query_hook_gas_limits(deps)?),
+        QueryMsg::GetHookFailurePolicy {} => to_binary(&query_hook_failure_policy(deps)?),
     }
 }
 
@@ -436,15 +466,44 @@ pub fn query_hooks(deps: Deps) -> StdResult<GetHooksResponse> {
     })
 }
 
+pub fn query_hook_failure_policy(deps: Deps) -> StdResult<GetHookFailurePolicyResponse> {
+    Ok(GetHookFailurePolicyResponse {
+        policy: HOOK_FAILURE_POLICY
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    // Hook messages only reply on error. Their reply IDs map to the
+    // address of the hook they were sent to.
+    let hook = HOOK_REPLY_ADDRS.load(deps.storage, msg.id)?;
+    let error = msg.result.into_result().err().unwrap_or_default();
+    let policy = HOOK_FAILURE_POLICY
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if policy == HookFailurePolicy::RemoveHook {
+        HOOKS.remove_hook(deps.storage, hook.clone())?;
+    }
+    Ok(Response::new().add_event(
+        Event::new("stake_hook_failure")
+            .add_attribute("hook", hook)
+            .add_attribute("error", error)
+            .add_attribute("policy", format!("{:?}", policy)),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use std::borrow::BorrowMut;
 
     use crate::msg::{
-        ExecuteMsg, GetConfigResponse, QueryMsg, ReceiveMsg, StakedBalanceAtHeightResponse,
+        ExecuteMsg, GetConfigResponse, GetHookFailurePolicyResponse, GetHooksResponse,
+        HookGasLimit, HookGasLimitsResponse, QueryMsg, ReceiveMsg, StakedBalanceAtHeightResponse,
         StakedValueResponse, TotalStakedAtHeightResponse, TotalValueResponse,
     };
-    use crate::state::MAX_CLAIMS;
+    use crate::state::{HookFailurePolicy, MAX_CLAIMS};
     use crate::ContractError;
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::{to_binary, Addr, Empty, MessageInfo, Uint128};
@@ -468,7 +527,8 @@ mod tests {
             crate::contract::execute,
             crate::contract::instantiate,
             crate::contract::query,
-        );
+        )
+        .with_reply(crate::contract::reply);
         Box::new(contract)
     }
 
@@ -1384,4 +1444,201 @@ mod tests {
         claim_tokens(&mut app, &staking_addr, info).unwrap();
         assert_eq!(get_balance(&app, &cw20_addr, ADDR2), Uint128::from(100u128));
     }
+
+    #[test]
+    fn test_failing_hook() {
+        let mut app = mock_app();
+        let amount1 = Uint128::from(100u128);
+        let initial_balances = vec![Cw20Coin {
+            address: ADDR1.to_string(),
+            amount: amount1,
+        }];
+        let (staking_addr, cw20_addr) = setup_test_case(&mut app, initial_balances, None);
+
+        // A hook may not be registered with a zero gas limit.
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                staking_addr.clone(),
+                &ExecuteMsg::AddHook {
+                    addr: cw20_addr.to_string(),
+                    gas_limit: Some(0),
+                },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(
+            err,
+            ContractError::HookError(indexable_hooks::HookError::ZeroGasLimit {})
+        );
+
+        // The cw20 contract does not understand hook messages so it
+        // makes for a failing hook.
+        let add_hook = ExecuteMsg::AddHook {
+            addr: cw20_addr.to_string(),
+            gas_limit: Some(100_000),
+        };
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            staking_addr.clone(),
+            &add_hook,
+            &[],
+        )
+        .unwrap();
+
+        // Failing hooks are kept unless the owner opts in to removing
+        // them.
+        let policy: GetHookFailurePolicyResponse = app
+            .wrap()
+            .query_wasm_smart(&staking_addr, &QueryMsg::GetHookFailurePolicy {})
+            .unwrap();
+        assert_eq!(policy.policy, HookFailurePolicy::Ignore);
+
+        // Only the owner may change the policy.
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked("manager"),
+                staking_addr.clone(),
+                &ExecuteMsg::UpdateHookFailurePolicy {
+                    policy: HookFailurePolicy::RemoveHook,
+                },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            staking_addr.clone(),
+            &ExecuteMsg::UpdateHookFailurePolicy {
+                policy: HookFailurePolicy::Ignore,
+            },
+            &[],
+        )
+        .unwrap();
+        let policy: GetHookFailurePolicyResponse = app
+            .wrap()
+            .query_wasm_smart(&staking_addr, &QueryMsg::GetHookFailurePolicy {})
+            .unwrap();
+        assert_eq!(policy.policy, HookFailurePolicy::Ignore);
+
+        // Staking succeeds and the hook is kept.
+        let info = mock_info(ADDR1, &[]);
+        let res = stake_tokens(
+            &mut app,
+            &staking_addr,
+            &cw20_addr,
+            info.clone(),
+            Uint128::new(50),
+        )
+        .unwrap();
+        assert!(res.events.iter().any(|e| e.ty == "wasm-stake_hook_failure"));
+        let hooks: GetHooksResponse = app
+            .wrap()
+            .query_wasm_smart(&staking_addr, &QueryMsg::GetHooks {})
+            .unwrap();
+        assert_eq!(hooks.hooks, vec![cw20_addr.to_string()]);
+        let gas_limits: HookGasLimitsResponse = app
+            .wrap()
+            .query_wasm_smart(&staking_addr, &QueryMsg::GetHookGasLimits {})
+            .unwrap();
+        assert_eq!(
+            gas_limits.hooks,
+            vec![HookGasLimit {
+                addr: cw20_addr.to_string(),
+                gas_limit: Some(100_000),
+            }]
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            staking_addr.clone(),
+            &ExecuteMsg::UpdateHookFailurePolicy {
+                policy: HookFailurePolicy::RemoveHook,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Unstaking succeeds and the hook is removed.
+        app.update_block(next_block);
+        unstake_tokens(&mut app, &staking_addr, info, Uint128::new(50)).unwrap();
+        let hooks: GetHooksResponse = app
+            .wrap()
+            .query_wasm_smart(&staking_addr, &QueryMsg::GetHooks {})
+            .unwrap();
+        assert!(hooks.hooks.is_empty());
+        assert_eq!(get_balance(&app, &cw20_addr, ADDR1), amount1);
+    }
+
+    #[test]
+    fn test_multiple_failing_hooks() {
+        let mut app = mock_app();
+        let amount1 = Uint128::from(100u128);
+        let initial_balances = vec![Cw20Coin {
+            address: ADDR1.to_string(),
+            amount: amount1,
+        }];
+        let (staking_addr, cw20_addr) = setup_test_case(&mut app, initial_balances, None);
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            staking_addr.clone(),
+            &ExecuteMsg::UpdateHookFailurePolicy {
+                policy: HookFailurePolicy::RemoveHook,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Register three hooks that all fail. Each failure must remove
+        // the hook that failed and not whichever hook now sits at its
+        // original index.
+        let failing_hooks: Vec<Addr> = (0..3).map(|_| instantiate_cw20(&mut app, vec![])).collect();
+        for hook in &failing_hooks {
+            app.execute_contract(
+                Addr::unchecked("owner"),
+                staking_addr.clone(),
+                &ExecuteMsg::AddHook {
+                    addr: hook.to_string(),
+                    gas_limit: None,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        let info = mock_info(ADDR1, &[]);
+        let res =
+            stake_tokens(&mut app, &staking_addr, &cw20_addr, info, Uint128::new(50)).unwrap();
+        let failed: Vec<String> = res
+            .events
+            .iter()
+            .filter(|e| e.ty == "wasm-stake_hook_failure")
+            .flat_map(|e| e.attributes.iter().filter(|a| a.key == "hook"))
+            .map(|a| a.value.clone())
+            .collect();
+        assert_eq!(
+            failed,
+            failing_hooks
+                .iter()
+                .map(|h| h.to_string())
+                .collect::<Vec<_>>()
+        );
+
+        let hooks: GetHooksResponse = app
+            .wrap()
+            .query_wasm_smart(&staking_addr, &QueryMsg::GetHooks {})
+            .unwrap();
+        assert!(hooks.hooks.is_empty());
+        app.update_block(next_block);
+        assert_eq!(
+            query_staked_balance(&app, &staking_addr, ADDR1),
+            Uint128::new(50)
+        );
+    }
 }
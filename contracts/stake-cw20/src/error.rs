@@ -18,7 +18,7 @@ pub enum ContractError {
     #[error("No admin configured")]
     NoAdminConfigured {},
     #[error("{0}")]
-    HookError(#[from] cw_controllers::HookError),
+    HookError(#[from] indexable_hooks::HookError),
     #[error("Only owner can change owner")]
     OnlyOwnerCanChangeOwner {},
 }
@@ -1,5 +1,5 @@
-use crate::state::HOOKS;
-use cosmwasm_std::{to_binary, Addr, StdResult, Storage, SubMsg, Uint128, WasmMsg};
+use crate::state::{HOOKS, HOOK_REPLY_ADDRS};
+use cosmwasm_std::{to_binary, Addr, Binary, StdResult, Storage, SubMsg, Uint128, WasmMsg};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -12,39 +12,48 @@ pub enum StakeChangedHookMsg {
 }
 
 pub fn stake_hook_msgs(
-    storage: &dyn Storage,
+    storage: &mut dyn Storage,
     addr: Addr,
     amount: Uint128,
 ) -> StdResult<Vec<SubMsg>> {
     let msg = to_binary(&StakeChangedExecuteMsg::StakeChangeHook(
         StakeChangedHookMsg::Stake { addr, amount },
     ))?;
-    HOOKS.prepare_hooks(storage, |a| {
-        let execute = WasmMsg::Execute {
-            contract_addr: a.to_string(),
-            msg: msg.clone(),
-            funds: vec![],
-        };
-        Ok(SubMsg::new(execute))
-    })
+    prepare_hook_msgs(storage, msg)
 }
 
 pub fn unstake_hook_msgs(
-    storage: &dyn Storage,
+    storage: &mut dyn Storage,
     addr: Addr,
     amount: Uint128,
 ) -> StdResult<Vec<SubMsg>> {
     let msg = to_binary(&StakeChangedExecuteMsg::StakeChangeHook(
         StakeChangedHookMsg::Unstake { addr, amount },
     ))?;
-    HOOKS.prepare_hooks(storage, |a| {
+    prepare_hook_msgs(storage, msg)
+}
+
+/// Prepares a message for every registered hook. These messages
+/// reply on error so that a failing hook can never block staking or
+/// unstaking. The address of the hook each reply ID was sent to is
+/// saved so that the reply handler can find it even if another hook
+/// has been removed in the meantime.
+fn prepare_hook_msgs(storage: &mut dyn Storage, msg: Binary) -> StdResult<Vec<SubMsg>> {
+    let mut addrs = vec![];
+    let msgs = HOOKS.prepare_hooks(storage, |a| {
         let execute = WasmMsg::Execute {
             contract_addr: a.to_string(),
             msg: msg.clone(),
             funds: vec![],
         };
-        Ok(SubMsg::new(execute))
-    })
+        let tmp = SubMsg::reply_on_error(execute, addrs.len() as u64);
+        addrs.push(a);
+        Ok(tmp)
+    })?;
+    for (id, addr) in addrs.into_iter().enumerate() {
+        HOOK_REPLY_ADDRS.save(storage, id as u64, &addr)?;
+    }
+    Ok(msgs)
 }
 
 // This is just a helper to properly serialize the above message
@@ -5,7 +5,10 @@ use serde::{Deserialize, Serialize};
 
 use cw_utils::Duration;
 
+use crate::state::HookFailurePolicy;
+
 pub use cw_controllers::ClaimsResponse;
+pub use indexable_hooks::{HookGasLimit, HookGasLimitsResponse};
 
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
 pub struct InstantiateMsg {
@@ -30,12 +33,21 @@ pub enum ExecuteMsg {
         manager: Option<String>,
         duration: Option<Duration>,
     },
+    /// Adds a hook that is called on every stake and unstake. If
+    /// `gas_limit` is set the hook may use at most that much gas,
+    /// otherwise it may use all remaining gas.
     AddHook {
         addr: String,
+        gas_limit: Option<u64>,
     },
     RemoveHook {
         addr: String,
     },
+    /// Sets what happens to a hook that fails during a stake or
+    /// unstake. Only callable by the owner.
+    UpdateHookFailurePolicy {
+        policy: HookFailurePolicy,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -64,6 +76,8 @@ pub enum QueryMsg {
         address: String,
     },
     GetHooks {},
+    GetHookGasLimits {},
+    GetHookFailurePolicy {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -106,3 +120,9 @@ pub struct GetConfigResponse {
 pub struct GetHooksResponse {
     pub hooks: Vec<String>,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct GetHookFailurePolicyResponse {
+    pub policy: HookFailurePolicy,
+}
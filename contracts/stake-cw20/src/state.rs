@@ -3,9 +3,9 @@ use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::{Addr, Uint128};
 use cw_controllers::Claims;
-use cw_controllers::Hooks;
-use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
+use indexable_hooks::Hooks;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct Config {
@@ -39,4 +39,23 @@ pub const CLAIMS: Claims = Claims::new("claims");
 pub const BALANCE: Item<Uint128> = Item::new("balance");
 
 // Hooks to contracts that will receive staking and unstaking messages
-pub const HOOKS: Hooks = Hooks::new("hooks");
+pub const HOOKS: Hooks = Hooks::new("hooks", "hook_gas_limits");
+
+// The hook each hook message reply ID was last sent to.
+pub const HOOK_REPLY_ADDRS: Map<u64, Addr> = Map::new("hook_reply_addrs");
+
+/// What to do with a hook whose execution fails.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HookFailurePolicy {
+    /// Remove the failing hook so it is no longer called.
+    RemoveHook,
+    /// Keep the failing hook registered and continue.
+    #[default]
+    Ignore,
+}
+
+// Policy applied when a hook fails. Stakes and unstakes never fail
+// because of a hook, regardless of the policy. Failing hooks are kept
+// unless the owner opts in to removing them.
+pub const HOOK_FAILURE_POLICY: Item<HookFailurePolicy> = Item::new("hook_failure_policy");
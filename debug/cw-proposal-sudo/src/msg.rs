@@ -20,5 +20,4 @@ pub enum ExecuteMsg {
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Admin {},
-    Dao {},
 }
@@ -1,5 +1,6 @@
 use cosmwasm_std::{to_binary, Addr, Empty, Uint128};
 use cw20::Cw20Coin;
+use cw_core::state::ProposalModule;
 use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 use indexable_hooks::HooksResponse;
 
@@ -116,6 +117,9 @@ fn instantiate_with_default_governance(
             admin: cw_core::msg::Admin::CoreContract {},
             label: "DAO DAO governance module".to_string(),
         }],
+        pause_guardian: None,
+        parent_dao: None,
+        admin: None,
         initial_items: None,
     };
 
@@ -141,7 +145,7 @@ fn test_counters() {
 
     let governance_addr =
         instantiate_with_default_governance(&mut app, govmod_id, instantiate, None);
-    let governance_modules: Vec<Addr> = app
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
             governance_addr,
@@ -153,7 +157,7 @@ fn test_counters() {
         .unwrap();
 
     assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
     let govmod_config: Config = app
         .wrap()
@@ -193,6 +197,7 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddProposalHook {
             address: counters.to_string(),
+            gas_limit: None,
         },
         &[],
     )
@@ -202,6 +207,7 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddVoteHook {
             address: counters.to_string(),
+            gas_limit: None,
         },
         &[],
     )
@@ -298,6 +304,7 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddProposalHook {
             address: failing_counters.to_string(),
+            gas_limit: None,
         },
         &[],
     )
@@ -307,6 +314,7 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddVoteHook {
             address: failing_counters.to_string(),
+            gas_limit: None,
         },
         &[],
     )
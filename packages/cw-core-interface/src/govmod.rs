@@ -0,0 +1,8 @@
+use cw_core_macros::govmod_query;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[govmod_query]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Query {}
@@ -1 +1,2 @@
+pub mod govmod;
 pub mod voting;
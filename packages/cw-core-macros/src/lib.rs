@@ -250,6 +250,7 @@ pub fn active_query(metadata: TokenStream, input: TokenStream) -> TokenStream {
 /// ```
 /// enum QueryMsg {
 ///     Info {},
+///     Dao {},
 /// }
 /// ```
 ///
@@ -284,8 +285,10 @@ pub fn govmod_query(metadata: TokenStream, input: TokenStream) -> TokenStream {
     match &mut ast.data {
         syn::Data::Enum(DataEnum { variants, .. }) => {
             let info: Variant = syn::parse2(quote! { Info {} }).unwrap();
+            let dao: Variant = syn::parse2(quote! { Dao {} }).unwrap();
 
             variants.push(info);
+            variants.push(dao);
         }
         _ => {
             return syn::Error::new(
@@ -15,6 +15,6 @@ fn govmod_query_derive() {
 
     // If this compiles we have won.
     match test {
-        Test::Foo | Test::Bar(_) | Test::Baz { .. } | Test::Info {} => "yay",
+        Test::Foo | Test::Bar(_) | Test::Baz { .. } | Test::Info {} | Test::Dao {} => "yay",
     };
 }
@@ -3,13 +3,26 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use cosmwasm_std::{Addr, CustomQuery, Deps, StdError, StdResult, Storage, SubMsg};
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct HooksResponse {
     pub hooks: Vec<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct HookGasLimit {
+    pub addr: String,
+    /// The maximum amount of gas the hook may use when it is
+    /// called. None if the hook may use all remaining gas.
+    pub gas_limit: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct HookGasLimitsResponse {
+    pub hooks: Vec<HookGasLimit>,
+}
+
 #[derive(Error, Debug, PartialEq)]
 pub enum HookError {
     #[error("{0}")]
@@ -20,34 +33,57 @@ pub enum HookError {
 
     #[error("Given address not registered as a hook")]
     HookNotRegistered {},
+
+    #[error("Hook gas limit must be greater than zero")]
+    ZeroGasLimit {},
 }
 
 // store all hook addresses in one item. We cannot have many of them before the contract becomes unusable anyway.
-pub struct Hooks<'a>(Item<'a, Vec<Addr>>);
+pub struct Hooks<'a> {
+    hooks: Item<'a, Vec<Addr>>,
+    // Gas limits set at registration. Hooks without an entry may use
+    // all remaining gas.
+    gas_limits: Map<'a, Addr, u64>,
+}
 
 impl<'a> Hooks<'a> {
-    pub const fn new(storage_key: &'a str) -> Self {
-        Hooks(Item::new(storage_key))
+    pub const fn new(storage_key: &'a str, gas_limits_key: &'a str) -> Self {
+        Hooks {
+            hooks: Item::new(storage_key),
+            gas_limits: Map::new(gas_limits_key),
+        }
     }
 
-    pub fn add_hook(&self, storage: &mut dyn Storage, addr: Addr) -> Result<(), HookError> {
-        let mut hooks = self.0.may_load(storage)?.unwrap_or_default();
+    pub fn add_hook(
+        &self,
+        storage: &mut dyn Storage,
+        addr: Addr,
+        gas_limit: Option<u64>,
+    ) -> Result<(), HookError> {
+        if gas_limit == Some(0) {
+            return Err(HookError::ZeroGasLimit {});
+        }
+        let mut hooks = self.hooks.may_load(storage)?.unwrap_or_default();
         if !hooks.iter().any(|h| h == &addr) {
+            if let Some(gas_limit) = gas_limit {
+                self.gas_limits.save(storage, addr.clone(), &gas_limit)?;
+            }
             hooks.push(addr);
         } else {
             return Err(HookError::HookAlreadyRegistered {});
         }
-        Ok(self.0.save(storage, &hooks)?)
+        Ok(self.hooks.save(storage, &hooks)?)
     }
 
     pub fn remove_hook(&self, storage: &mut dyn Storage, addr: Addr) -> Result<(), HookError> {
-        let mut hooks = self.0.load(storage)?;
+        let mut hooks = self.hooks.load(storage)?;
         if let Some(p) = hooks.iter().position(|x| x == &addr) {
             hooks.remove(p);
         } else {
             return Err(HookError::HookNotRegistered {});
         }
-        Ok(self.0.save(storage, &hooks)?)
+        self.gas_limits.remove(storage, addr);
+        Ok(self.hooks.save(storage, &hooks)?)
     }
 
     pub fn remove_hook_by_index(
@@ -55,27 +91,58 @@ impl<'a> Hooks<'a> {
         storage: &mut dyn Storage,
         index: u64,
     ) -> Result<(), HookError> {
-        let mut hooks = self.0.load(storage)?;
-        hooks.remove(index as usize);
-        Ok(self.0.save(storage, &hooks)?)
+        let mut hooks = self.hooks.load(storage)?;
+        let addr = hooks.remove(index as usize);
+        self.gas_limits.remove(storage, addr);
+        Ok(self.hooks.save(storage, &hooks)?)
     }
 
+    /// Prepares a submessage for every hook with `prep`. Hooks that
+    /// were registered with a gas limit have it set on their
+    /// submessage.
     pub fn prepare_hooks<F: FnMut(Addr) -> StdResult<SubMsg>>(
         &self,
         storage: &dyn Storage,
-        prep: F,
+        mut prep: F,
     ) -> StdResult<Vec<SubMsg>> {
-        self.0
+        self.hooks
             .may_load(storage)?
             .unwrap_or_default()
             .into_iter()
-            .map(prep)
+            .map(|addr| {
+                let gas_limit = self.gas_limits.may_load(storage, addr.clone())?;
+                let mut msg = prep(addr)?;
+                if gas_limit.is_some() {
+                    msg.gas_limit = gas_limit;
+                }
+                Ok(msg)
+            })
             .collect()
     }
 
     pub fn query_hooks<Q: CustomQuery>(&self, deps: Deps<Q>) -> StdResult<HooksResponse> {
-        let hooks = self.0.may_load(deps.storage)?.unwrap_or_default();
+        let hooks = self.hooks.may_load(deps.storage)?.unwrap_or_default();
         let hooks = hooks.into_iter().map(String::from).collect();
         Ok(HooksResponse { hooks })
     }
+
+    pub fn query_hook_gas_limits<Q: CustomQuery>(
+        &self,
+        deps: Deps<Q>,
+    ) -> StdResult<HookGasLimitsResponse> {
+        let hooks = self
+            .hooks
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|addr| {
+                let gas_limit = self.gas_limits.may_load(deps.storage, addr.clone())?;
+                Ok(HookGasLimit {
+                    addr: addr.into_string(),
+                    gas_limit,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(HookGasLimitsResponse { hooks })
+    }
 }
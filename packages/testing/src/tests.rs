@@ -65,7 +65,7 @@ where
         vec![TestVote {
             voter: "ekez".to_string(),
             position: Vote::Yes,
-            weight: Uint128::new(u128::max_value()),
+            weight: Uint128::new(u128::MAX),
             should_execute: ShouldExecute::Yes,
         }],
         Threshold::AbsolutePercentage {
@@ -84,7 +84,7 @@ where
         vec![TestVote {
             voter: "ekez".to_string(),
             position: Vote::Yes,
-            weight: Uint128::new(u128::max_value()),
+            weight: Uint128::new(u128::MAX),
             should_execute: ShouldExecute::Yes,
         }],
         Threshold::AbsolutePercentage {
@@ -105,7 +105,7 @@ where
             TestVote {
                 voter: "ekez".to_string(),
                 position: Vote::Yes,
-                weight: Uint128::new(u128::max_value() - 1),
+                weight: Uint128::new(u128::MAX - 1),
                 should_execute: ShouldExecute::Yes,
             },
         ],
@@ -132,7 +132,7 @@ where
             TestVote {
                 voter: "ekez".to_string(),
                 position: Vote::Yes,
-                weight: Uint128::new(u128::max_value() - 1),
+                weight: Uint128::new(u128::MAX - 1),
                 should_execute: ShouldExecute::No,
             },
         ],
@@ -154,7 +154,7 @@ where
             percentage: PercentageThreshold::Percent(Decimal::percent(99)),
         },
         Status::Open,
-        Some(Uint128::from(u128::max_value())),
+        Some(Uint128::from(u128::MAX)),
     );
 }
 
@@ -166,7 +166,7 @@ where
         vec![TestVote {
             voter: "ekez".to_string(),
             position: Vote::Abstain,
-            weight: Uint128::new(u64::max_value().into()),
+            weight: Uint128::new(u64::MAX.into()),
             should_execute: ShouldExecute::Yes,
         }],
         Threshold::AbsolutePercentage {
@@ -183,7 +183,7 @@ where
             vec![TestVote {
                 voter: "ekez".to_string(),
                 position: Vote::Abstain,
-                weight: Uint128::new(u64::max_value().into()),
+                weight: Uint128::new(u64::MAX.into()),
                 should_execute: ShouldExecute::Yes,
             }],
             Threshold::ThresholdQuorum {
@@ -548,8 +548,8 @@ where
     let mut rng = rand::thread_rng();
     let dist = rand::distributions::Uniform::<u64>::new(1, 200);
     for _ in 0..10 {
-        let yes: Vec<u64> = (0..50).map(|_| rng.sample(&dist)).collect();
-        let no: Vec<u64> = (0..50).map(|_| rng.sample(&dist)).collect();
+        let yes: Vec<u64> = (0..50).map(|_| rng.sample(dist)).collect();
+        let no: Vec<u64> = (0..50).map(|_| rng.sample(dist)).collect();
 
         let yes_sum: u64 = yes.iter().sum();
         let no_sum: u64 = no.iter().sum();